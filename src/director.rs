@@ -0,0 +1,389 @@
+//! `TournamentDirector` is an actor that runs one multi-table tournament for the `TableHub`.
+//! It keeps track of who registered, which table everyone is seated at, and the blind level
+//! that every table plays at. After each hand, a table reports to the hub, which passes it along here,
+//! and the director tells the hub which players to move so that the tables stay balanced,
+//! breaking tables as the field shrinks until everyone left is at the final table.
+
+use std::collections::{BTreeMap, HashMap};
+
+use actix::prelude::{Actor, Addr, Context, Handler};
+use actix::ActorContext;
+use json::object;
+use uuid::Uuid;
+
+use crate::hub::TableHub;
+use crate::logic::{balance_tables, seat_players, BlindLevel, Tournament};
+use crate::messages::{
+    CreateTournamentFields, Register, ReseatPlayers, SeatTournament, StartTournament, TableReport, TournamentLevelUp,
+    TournamentNotice, TournamentOver, Withdraw,
+};
+
+#[derive(Debug)]
+pub struct TournamentDirector {
+    hub_addr: Addr<TableHub>,
+    name: String,
+    creator_id: Uuid, // only the creator can start the tournament
+    fields: CreateTournamentFields,
+    tournament: Tournament, // the blind levels and finishing positions
+    registrants: HashMap<Uuid, String>, // everyone who has signed up, by id and name
+    seating: BTreeMap<String, Vec<Uuid>>, // table name to the players seated (or on their way to be seated) there
+    hands_this_level: usize, // hands reported since the level's hand count last went up
+}
+
+impl TournamentDirector {
+    pub fn new(hub_addr: Addr<TableHub>, name: String, creator_id: Uuid, fields: CreateTournamentFields) -> Self {
+        let levels = fields
+            .blind_levels
+            .clone()
+            .filter(|levels| !levels.is_empty())
+            .unwrap_or_else(|| {
+                vec![BlindLevel {
+                    small_blind: fields.small_blind,
+                    big_blind: fields.big_blind,
+                    ante: fields.ante,
+                }]
+            });
+        let tournament = Tournament::new(levels, fields.blind_schedule);
+        TournamentDirector {
+            hub_addr,
+            name,
+            creator_id,
+            fields,
+            tournament,
+            registrants: HashMap::new(),
+            seating: BTreeMap::new(),
+            hands_this_level: 0,
+        }
+    }
+
+    /// send a message to everyone who registered, whether they are still in the tournament or not
+    fn notify_everyone(&self, message: json::JsonValue) {
+        self.hub_addr.do_send(TournamentNotice {
+            ids: self.registrants.keys().cloned().collect(),
+            message: message.dump(),
+        });
+    }
+
+    fn notify_error(&self, id: Uuid, reason: &str) {
+        let message = object! {
+            msg_type: "error".to_owned(),
+            error: "tournament_error".to_owned(),
+            reason: reason.to_owned(),
+        };
+        self.hub_addr.do_send(TournamentNotice {
+            ids: vec![id],
+            message: message.dump(),
+        });
+    }
+
+    /// the player is out of the tournament, in the next worst position
+    fn eliminate(&mut self, id: Uuid) {
+        for ids in self.seating.values_mut() {
+            ids.retain(|seated| *seated != id);
+        }
+        if let Some(position) = self.tournament.eliminate(id) {
+            let message = object! {
+                msg_type: "tournament_busted".to_owned(),
+                tournament_name: self.name.clone(),
+                player_name: self.registrants.get(&id).cloned(),
+                position: position,
+                players_left: self.tournament_players_left(),
+            };
+            self.notify_everyone(message);
+        }
+    }
+
+    fn tournament_players_left(&self) -> usize {
+        self.seating.values().map(|ids| ids.len()).sum()
+    }
+
+    /// once the field has changed, either crown the winner, or move players to keep the tables balanced
+    fn rebalance(&mut self, ctx: &mut Context<Self>) {
+        if self.tournament_players_left() <= 1 {
+            // the last player standing finishes first
+            for id in self.seating.values().flatten() {
+                self.tournament.eliminate(*id);
+            }
+            self.seating.clear();
+            let results: Vec<json::JsonValue> = self
+                .tournament
+                .results()
+                .into_iter()
+                .map(|(position, player_name)| object! { position: position, player_name: player_name })
+                .collect();
+            let message = object! {
+                msg_type: "tournament_results".to_owned(),
+                tournament_name: self.name.clone(),
+                results: results,
+            };
+            self.notify_everyone(message);
+            self.hub_addr.do_send(TournamentOver {
+                tournament_name: self.name.clone(),
+            });
+            ctx.stop();
+            return;
+        }
+        let num_tables = self.seating.len();
+        let reseats = balance_tables(&mut self.seating, self.fields.max_players.into());
+        if reseats.is_empty() {
+            return;
+        }
+        if num_tables > 1 && self.seating.len() == 1 {
+            let message = object! {
+                msg_type: "final_table".to_owned(),
+                tournament_name: self.name.clone(),
+                table_name: self.seating.keys().next().cloned(),
+            };
+            self.notify_everyone(message);
+        }
+        self.hub_addr.do_send(ReseatPlayers { reseats });
+    }
+}
+
+/// Make actor from `TournamentDirector`
+impl Actor for TournamentDirector {
+    type Context = Context<Self>;
+}
+
+/// Handler for a player registering before the tournament starts
+impl Handler<Register> for TournamentDirector {
+    type Result = ();
+
+    fn handle(&mut self, msg: Register, _: &mut Context<Self>) {
+        let Register { id, name } = msg;
+        if self.tournament.has_started() {
+            self.notify_error(id, "The tournament has already started.");
+            return;
+        }
+        self.registrants.insert(id, name);
+        let message = object! {
+            msg_type: "tournament_registered".to_owned(),
+            tournament_name: self.name.clone(),
+            num_registered: self.registrants.len(),
+        };
+        self.notify_everyone(message);
+    }
+}
+
+/// Handler for the creator starting the tournament. Everyone who registered is seated at once
+impl Handler<StartTournament> for TournamentDirector {
+    type Result = ();
+
+    fn handle(&mut self, msg: StartTournament, _: &mut Context<Self>) {
+        let StartTournament { id, .. } = msg;
+        if id != self.creator_id {
+            self.notify_error(id, "Only the creator can start the tournament.");
+            return;
+        }
+        if self.tournament.has_started() {
+            self.notify_error(id, "The tournament has already started.");
+            return;
+        }
+        if self.registrants.len() < 2 {
+            self.notify_error(id, "A tournament needs at least two players.");
+            return;
+        }
+        let ids: Vec<Uuid> = self.registrants.keys().cloned().collect();
+        self.seating = seat_players(&self.name, &ids, self.fields.max_players.into());
+        self.tournament.start(self.registrants.clone());
+        self.hub_addr.do_send(SeatTournament {
+            tournament_name: self.name.clone(),
+            fields: self.fields.clone(),
+            level: self.tournament.current_level(),
+            seating: self.seating.clone(),
+        });
+        let message = object! {
+            msg_type: "tournament_started".to_owned(),
+            tournament_name: self.name.clone(),
+            num_players: ids.len(),
+            num_tables: self.seating.len(),
+        };
+        self.notify_everyone(message);
+    }
+}
+
+/// Handler for a table's report after a hand. The busted players are eliminated (from the smallest stack),
+/// and the blinds go up at every table once the level is over
+impl Handler<TableReport> for TournamentDirector {
+    type Result = ();
+
+    fn handle(&mut self, msg: TableReport, ctx: &mut Context<Self>) {
+        let TableReport {
+            table_name,
+            busted,
+            hand_played,
+            ..
+        } = msg;
+        for id in busted {
+            self.eliminate(id);
+        }
+        // the last hand at a table that just broke doesn't count
+        if hand_played && self.seating.contains_key(&table_name) {
+            // a level's hand count goes up once as many hands have been played as there are tables
+            self.hands_this_level += 1;
+            if self.hands_this_level >= self.seating.len() {
+                self.hands_this_level = 0;
+                if self.tournament.finish_hand() {
+                    self.hub_addr.do_send(TournamentLevelUp {
+                        table_names: self.seating.keys().cloned().collect(),
+                        level: self.tournament.level(),
+                        blinds: self.tournament.current_level(),
+                    });
+                }
+            }
+        }
+        self.rebalance(ctx);
+    }
+}
+
+/// Handler for a player who left the tournament (or could not be seated) without busting
+impl Handler<Withdraw> for TournamentDirector {
+    type Result = ();
+
+    fn handle(&mut self, msg: Withdraw, ctx: &mut Context<Self>) {
+        let Withdraw { id } = msg;
+        if !self.tournament.has_started() {
+            // they are simply no longer registered
+            self.registrants.remove(&id);
+            return;
+        }
+        self.eliminate(id);
+        self.rebalance(ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::BlindSchedule;
+    use crate::messages::MetaAction;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    type MetaActions = Arc<Mutex<VecDeque<MetaAction>>>;
+
+    /// a started tournament of five players, two to a table (so at ABCD-1, ABCD-2 and ABCD-3),
+    /// whose blinds go up every hand. The hub hands the meta actions for each table to the returned queues
+    fn start_director(ids: &[Uuid]) -> (TournamentDirector, HashMap<String, MetaActions>) {
+        let seating = seat_players("ABCD", ids, 2);
+        let queues: HashMap<String, MetaActions> = seating
+            .keys()
+            .map(|table_name| (table_name.clone(), Arc::new(Mutex::new(VecDeque::new()))))
+            .collect();
+        let hub_addr = TableHub::with_tournament_tables("ABCD", &queues).start();
+        let fields = CreateTournamentFields {
+            variant: Default::default(),
+            betting_structure: None,
+            max_players: 2,
+            small_blind: 1,
+            big_blind: 2,
+            ante: 0,
+            blind_levels: None,
+            blind_schedule: BlindSchedule::EveryNHands(1),
+            buy_in: 1000,
+        };
+        let mut director = TournamentDirector::new(hub_addr, "ABCD".to_string(), ids[0], fields);
+        director.registrants = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, format!("Player{}", i)))
+            .collect();
+        director.seating = seating;
+        director.tournament.start(director.registrants.clone());
+        (director, queues)
+    }
+
+    fn report(table_name: &str, busted: Vec<Uuid>) -> TableReport {
+        TableReport {
+            tournament_name: "ABCD".to_string(),
+            table_name: table_name.to_string(),
+            busted,
+            hand_played: true,
+        }
+    }
+
+    /// give the hub a moment to act on what the director sent it
+    async fn let_hub_catch_up() {
+        actix::clock::sleep(Duration::from_millis(50)).await;
+    }
+
+    #[test]
+    fn bust_breaks_table() {
+        actix::System::new().block_on(async {
+            let ids: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+            let (mut director, queues) = start_director(&ids);
+            let mut ctx = Context::new();
+            // ids are seated round robin: ABCD-1 has 0 and 3, ABCD-2 has 1 and 4, ABCD-3 has 2
+            director.handle(report("ABCD-1", vec![ids[0]]), &mut ctx);
+            let_hub_catch_up().await;
+
+            // four players fit at two tables, so ABCD-3 breaks and its player moves to the short table
+            assert_eq!(director.seating.len(), 2);
+            assert_eq!(director.seating["ABCD-1"], vec![ids[3], ids[2]]);
+            let meta_actions = queues["ABCD-3"].lock().unwrap();
+            assert_eq!(meta_actions.len(), 1);
+            assert!(matches!(
+                &meta_actions[0],
+                MetaAction::Reseat(id, to) if *id == ids[2] && to == "ABCD-1"
+            ));
+            assert!(queues["ABCD-1"].lock().unwrap().is_empty());
+            assert!(queues["ABCD-2"].lock().unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn level_up_reaches_every_table() {
+        actix::System::new().block_on(async {
+            let ids: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+            let (mut director, queues) = start_director(&ids);
+            let mut ctx = Context::new();
+            director.handle(report("ABCD-1", vec![]), &mut ctx);
+            director.handle(report("ABCD-2", vec![]), &mut ctx);
+            let_hub_catch_up().await;
+            // only two of the three tables have played a hand
+            assert_eq!(director.tournament.level(), 1);
+            for queue in queues.values() {
+                assert!(queue.lock().unwrap().is_empty());
+            }
+
+            director.handle(report("ABCD-3", vec![]), &mut ctx);
+            let_hub_catch_up().await;
+            assert_eq!(director.tournament.level(), 2);
+            for queue in queues.values() {
+                let meta_actions = queue.lock().unwrap();
+                assert_eq!(meta_actions.len(), 1);
+                assert!(matches!(
+                    &meta_actions[0],
+                    MetaAction::TournamentLevel(2, blinds) if blinds.small_blind == 2 && blinds.big_blind == 4
+                ));
+            }
+        });
+    }
+
+    #[test]
+    fn bust_order_to_winner() {
+        actix::System::new().block_on(async {
+            let ids: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+            let (mut director, queues) = start_director(&ids);
+            let mut ctx = Context::new();
+            director.handle(report("ABCD-1", vec![ids[0]]), &mut ctx);
+            // ABCD-3 broke, so ids[2] joined ids[3] at ABCD-1, where the smaller stack busts first
+            director.handle(report("ABCD-1", vec![ids[3], ids[2]]), &mut ctx);
+            assert_eq!(director.seating.len(), 1);
+            director.handle(report("ABCD-2", vec![ids[4]]), &mut ctx);
+            let_hub_catch_up().await;
+
+            assert!(director.seating.is_empty());
+            let results: Vec<(usize, String)> = [1, 4, 2, 3, 0]
+                .iter()
+                .enumerate()
+                .map(|(i, player)| (i + 1, format!("Player{}", player)))
+                .collect();
+            assert_eq!(director.tournament.results(), results);
+            // the final table is told to close
+            let meta_actions = queues["ABCD-2"].lock().unwrap();
+            assert!(matches!(meta_actions.back(), Some(MetaAction::TournamentOver)));
+        });
+    }
+}
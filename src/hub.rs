@@ -10,12 +10,18 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::logic::{Table, PlayerAction, PlayerConfig};
+use crate::director::TournamentDirector;
+use crate::logic::{
+    BlindLevel, Freezeout, GameRotation, Knockout, PlayerAction, PlayerConfig, Table, TableOptions, Tournament,
+    WildCards,
+};
 use crate::messages::{
-    Connect, Create, CreateFields, CreateTableError, GameOver, Join, ListTables, MetaAction, MetaActionMessage,
-    PlayerActionMessage, PlayerName, Returned, ReturnedReason, WsMessage,
+    Connect, Create, CreateFields, CreateTableError, CreateTournament, CreateTournamentFields, GameOver, Join,
+    ListTables, MetaAction, MetaActionMessage, PlayerActionMessage, PlayerName, Register, RegisterTournament,
+    Reseated, ReseatPlayers, Returned, ReturnedReason, SeatTournament, StartTournament, TableReport,
+    TournamentLevelUp, TournamentNotice, TournamentOver, Withdraw, WsMessage,
 };
-use actix::prelude::{Actor, Context, Handler, MessageResult};
+use actix::prelude::{Actor, Addr, Context, Handler, MessageResult};
 use actix::AsyncContext;
 use json::object;
 use rand::Rng;
@@ -41,6 +47,15 @@ pub struct TableHub {
 
     private_tables: HashSet<String>, // which games do not show up in the loby
 
+    // the director of each multi-table tournament, by tournament name
+    tournaments: HashMap<String, Addr<TournamentDirector>>,
+
+    // which tournament each tournament table belongs to
+    tables_to_tournaments: HashMap<String, String>,
+
+    // the prize money each player has won in deals that ended their games
+    prize_winnings: HashMap<Uuid, u32>,
+
     //visitor_count: Arc<AtomicUsize>,
 }
 
@@ -52,8 +67,55 @@ impl TableHub {
             tables_to_actions: HashMap::new(),
             tables_to_meta_actions: HashMap::new(),
             private_tables: HashSet::new(),
+            tournaments: HashMap::new(),
+            tables_to_tournaments: HashMap::new(),
+            prize_winnings: HashMap::new(),
+        }
+    }
+
+    /// a hub that already runs these tables of the named tournament (useful for unit tests)
+    #[cfg(test)]
+    pub fn with_tournament_tables(
+        tournament_name: &str,
+        tables: &HashMap<String, Arc<Mutex<VecDeque<MetaAction>>>>,
+    ) -> TableHub {
+        let mut hub = TableHub::new();
+        for (table_name, meta_actions) in tables {
+            hub.tables_to_meta_actions.insert(table_name.clone(), meta_actions.clone());
+            hub.tables_to_tournaments.insert(table_name.clone(), tournament_name.to_owned());
         }
-    }    
+        hub
+    }
+
+    /// create a new 4-char name that no table or tournament is using yet
+    fn new_name(&self) -> String {
+	let mut rng = rand::thread_rng();
+	loop {
+            let genned_name: String = (0..GAME_NAME_LEN)
+		.map(|_| {
+                    let idx = rng.gen_range(0..CHAR_SET.len());
+                    CHAR_SET[idx] as char
+		})
+		.collect();
+            if self.tables_to_actions.contains_key(&genned_name) || self.tournaments.contains_key(&genned_name) {
+		// unlikely, but we already have a table with this exact name
+		continue;
+            }
+            // we genned a name that is new
+            break genned_name;
+	}
+    }
+
+    /// send a message to a player, who might be in the lobby or at a table
+    fn send_message(&self, id: Uuid, message: String) {
+	if let Some(config) = self.main_lobby_connections.get(&id) {
+	    if let Some(addr) = &config.player_addr {
+		addr.do_send(WsMessage(message));
+	    }
+	} else if let Some(meta_actions) = self.players_to_table.get(&id).and_then(|table_name| self.tables_to_meta_actions.get(table_name)) {
+	    meta_actions.lock().unwrap().push_back(MetaAction::SendMessage(id, message));
+	}
+    }
 }
 
 /// Make actor from `TableHub`
@@ -282,6 +344,11 @@ impl Handler<Returned> for TableHub {
         if let Some(table_name) = self.players_to_table.remove(&config.id) {
             // we stil think this player is at table in our mapping, so remove it
             println!("removing player {:?} removed from {:?}", config, table_name);
+	    let director = self.tables_to_tournaments.get(&table_name).and_then(|name| self.tournaments.get(name));
+	    if let (Some(director), false) = (director, matches!(reason, ReturnedReason::Busted | ReturnedReason::TournamentOver)) {
+		// leaving a tournament table without busting still takes the player out of the tournament
+		director.do_send(Withdraw { id: config.id });
+	    }
         }
 
         // tell the player what happened (successful leave/why couldn't they join)
@@ -302,6 +369,14 @@ impl Handler<Returned> for TableHub {
                     message["error"] = "unable_to_join".into();		    
                     message["reason"] = err.to_string().into();
                 }
+                ReturnedReason::Busted => {
+                    message["msg_type"] = "left_game".into();
+                    message["reason"] = "You have no chips left.".into();
+                }
+                ReturnedReason::TournamentOver => {
+                    message["msg_type"] = "left_game".into();
+                    message["reason"] = "The tournament is over.".into();
+                }
             }
             addr.do_send(WsMessage(message.dump()));
         }
@@ -348,57 +423,144 @@ impl Handler<Create> for TableHub {
 	match serde_json::from_str(&create_msg) {
 	    Ok(create_fields) => {
 		let CreateFields {
+		    variant,
+		    rotation,
+		    rotation_schedule,
+		    betting_structure,
 		    max_players,
 		    small_blind,
 		    big_blind,
+		    ante,
+		    big_blind_ante,
+		    straddle,
+		    max_runouts,
+		    rabbit_hunting,
+		    jokers,
+		    wild_ranks,
+		    seven_deuce_bounty,
+		    tournament,
+		    blind_levels,
+		    blind_schedule,
+		    freezeout,
+		    max_rebuys,
+		    rebuy_hands,
+		    add_on,
+		    late_entry_hands,
+		    bounty,
+		    progressive_bounty,
 		    buy_in,
 		    num_bots,
 		    password,
 		} = create_fields;
 		println!("password in create game = {:?}", password);
-		
+		// a mixed game starts with the first variant in its rotation
+		let variants = rotation.clone().unwrap_or_else(|| vec![variant]);
+		if variants.is_empty() {
+		    self.main_lobby_connections.insert(player_config.id, player_config);
+		    return Err(CreateTableError::EmptyRotation);
+		}
+		let variant = variants[0];
+		let rotation = rotation.map(|variants| GameRotation::new(variants, rotation_schedule));
+		let tournament = if tournament {
+		    let levels = blind_levels
+			.filter(|levels| !levels.is_empty())
+			.unwrap_or_else(|| vec![BlindLevel { small_blind, big_blind, ante }]);
+		    Some(Tournament::new(levels, blind_schedule))
+		} else {
+		    None
+		};
+		if freezeout && tournament.is_some() {
+		    self.main_lobby_connections.insert(player_config.id, player_config);
+		    return Err(CreateTableError::TournamentFreezeout);
+		}
+		let freezeout = if freezeout {
+		    Some(Freezeout::new(max_rebuys, rebuy_hands, add_on, late_entry_hands))
+		} else {
+		    None
+		};
+		if bounty > 0 && tournament.is_none() && freezeout.is_none() {
+		    self.main_lobby_connections.insert(player_config.id, player_config);
+		    return Err(CreateTableError::NoEliminations);
+		}
+		let knockout = if bounty > 0 {
+		    Some(Knockout::new(bounty, progressive_bounty))
+		} else {
+		    None
+		};
 		if num_bots >= max_players {
 		    self.main_lobby_connections.insert(player_config.id, player_config);
 		    return Err(CreateTableError::TooManyBots);
 		}
 		
+		// every variant in the rotation must fit everyone at the table
+		let most_players = variants.iter().map(|v| v.max_players()).min().unwrap();
+		if max_players > most_players {
+		    self.main_lobby_connections.insert(player_config.id, player_config);
+		    return Err(CreateTableError::TooManyPlayers(most_players));
+		}
+		
+		let all_stud = variants.iter().all(|v| v.is_stud());
+		if big_blind_ante && all_stud {
+		    self.main_lobby_connections.insert(player_config.id, player_config);
+		    return Err(CreateTableError::NoBigBlindToAnte);
+		}
+		
+		if straddle.is_some() && all_stud {
+		    self.main_lobby_connections.insert(player_config.id, player_config);
+		    return Err(CreateTableError::NoBlindsToStraddle);
+		}
+		
 		if big_blind > buy_in || small_blind > buy_in {
 		    self.main_lobby_connections.insert(player_config.id, player_config);
 		    return Err(CreateTableError::TooLargeBlinds);		
 		}
 		
-		let mut rng = rand::thread_rng();
-		let table_name = loop {
-                    // create a new 4-char unique name for the table
-                    let genned_name: String = (0..GAME_NAME_LEN)
-			.map(|_| {
-                            let idx = rng.gen_range(0..CHAR_SET.len());
-                            CHAR_SET[idx] as char
-			})
-			.collect();
-                    if self.tables_to_actions.contains_key(&genned_name) {
-			// unlikely, but we already have a table with this exact name
-			continue;
-                    }
-                    // we genned a name that is new
-                    break genned_name;
+		let wild_cards = match WildCards::new(jokers, &wild_ranks) {
+		    Ok(wild_cards) => wild_cards,
+		    Err(error_msg) => {
+			self.main_lobby_connections.insert(player_config.id, player_config);
+			return Err(CreateTableError::InvalidWildCards(error_msg));
+		    }
 		};
+		let no_wilds_variant = variants.iter().find(|v| !v.allows_wild_cards());
+		if let (Some(variant), false) = (no_wilds_variant, wild_cards.is_empty()) {
+		    self.main_lobby_connections.insert(player_config.id, player_config);
+		    return Err(CreateTableError::NoWildCards(*variant));
+		}
+		
+		let table_name = self.new_name();
 		
 		let actions = Arc::new(Mutex::new(HashMap::new()));
 		let meta_actions = Arc::new(Mutex::new(VecDeque::new()));
 		let cloned_actions = actions.clone();
 		let cloned_meta_actions = meta_actions.clone();
 		
+		let options = TableOptions {
+		    rotation,
+		    tournament,
+		    freezeout,
+		    knockout,
+		    betting_structure,
+		    ante,
+		    big_blind_ante,
+		    straddle,
+		    max_runouts: max_runouts.clamp(1, 3),
+		    rabbit_hunting,
+		    wild_cards,
+		    seven_deuce_bounty,
+		    ..Default::default()
+		};
 		let mut game = Table::new(
                     ctx.address(),
                     table_name.clone(),
-                    None, // no deck needed to pass in
+                    variant,
                     max_players,
                     small_blind,
                     big_blind,
                     buy_in,
                     password.clone(),
 		    id, // the creator is the admin
+		    options,
 		);
 		
 		for i in 0..num_bots {
@@ -475,7 +637,7 @@ impl Handler<GameOver> for TableHub {
     type Result = ();
 
     fn handle(&mut self, msg: GameOver, _: &mut Context<Self>) {
-        let GameOver { table_name } = msg;
+        let GameOver { table_name, prizes } = msg;
         println!(
             "Handling game over in the hub for table name: {:?}",
             table_name
@@ -489,6 +651,12 @@ impl Handler<GameOver> for TableHub {
         if self.private_tables.remove(&table_name) {
             println!("removed properly from private_tables");
         }
+        self.tables_to_tournaments.remove(&table_name);
+        for (id, prize) in prizes {
+            let winnings = self.prize_winnings.entry(id).or_insert(0);
+            *winnings += prize;
+            println!("player {} won {} at table {}, and {} in all", id, prize, table_name, winnings);
+        }
     }
 }
 
@@ -511,3 +679,276 @@ impl Handler<MetaActionMessage> for TableHub {
         }
     }
 }
+
+/// create a multi-table tournament, cannot already be at a table.
+/// The creator is registered for it, and stays in the lobby until it starts
+impl Handler<CreateTournament> for TableHub {
+    type Result = Result<String, CreateTableError>;
+
+    fn handle(&mut self, msg: CreateTournament, ctx: &mut Context<Self>) -> Self::Result {
+        let CreateTournament { id, create_msg } = msg;
+
+        let name = match self.main_lobby_connections.get(&id) {
+            Some(player_config) => player_config.name.clone(),
+            None => {
+                if let Some(table_name) = self.players_to_table.get(&id) {
+                    return Err(CreateTableError::AlreadyAtTable(table_name.to_string()));
+                }
+                return Err(CreateTableError::PlayerDoesNotExist);
+            }
+        };
+        let name = name.ok_or(CreateTableError::NameNotSet)?;
+
+        let fields: CreateTournamentFields = serde_json::from_str(&create_msg)
+            .map_err(|e| CreateTableError::UnableToParseJson(e.to_string()))?;
+        if fields.max_players < 2 {
+            return Err(CreateTableError::TooFewPlayers);
+        }
+        if fields.max_players > fields.variant.max_players() {
+            return Err(CreateTableError::TooManyPlayers(fields.variant.max_players()));
+        }
+        let first_level = fields.blind_levels.as_ref().and_then(|levels| levels.first().cloned());
+        let big_blind = first_level.map_or(fields.big_blind, |level| level.big_blind);
+        if big_blind > fields.buy_in || fields.small_blind > fields.buy_in {
+            return Err(CreateTableError::TooLargeBlinds);
+        }
+
+        let tournament_name = self.new_name();
+        let director = TournamentDirector::new(ctx.address(), tournament_name.clone(), id, fields).start();
+        director.do_send(Register { id, name });
+        self.tournaments.insert(tournament_name.clone(), director);
+        Ok(tournament_name)
+    }
+}
+
+/// register for a multi-table tournament, which the player waits for in the lobby
+impl Handler<RegisterTournament> for TableHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: RegisterTournament, _: &mut Context<Self>) {
+        let RegisterTournament { id, tournament_name } = msg;
+        let player_config = match self.main_lobby_connections.get_mut(&id) {
+            Some(player_config) => player_config,
+            None => {
+                println!("player config not in the main lobby, so they can't register for a tournament");
+                return;
+            }
+        };
+        player_config.heart_beat = Instant::now(); // this counts as activity
+        let error_reason = match (&player_config.name, self.tournaments.get(&tournament_name)) {
+            (Some(name), Some(director)) => {
+                director.do_send(Register { id, name: name.clone() });
+                return;
+            }
+            (None, _) => "You cannot register for a tournament until you set your name!".to_owned(),
+            (_, None) => format!("no tournament named {} exists", tournament_name),
+        };
+        let message = object! {
+            msg_type: "error".to_owned(),
+            error: "unable_to_register".to_owned(),
+            reason: error_reason,
+        };
+        if let Some(addr) = &player_config.player_addr {
+            addr.do_send(WsMessage(message.dump()));
+        }
+    }
+}
+
+/// the creator wants to start their tournament, so pass it along to its director
+impl Handler<StartTournament> for TableHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: StartTournament, _: &mut Context<Self>) {
+        if let Some(director) = self.tournaments.get(&msg.tournament_name) {
+            director.do_send(msg);
+        } else {
+            let message = object! {
+                msg_type: "error".to_owned(),
+                error: "tournament_error".to_owned(),
+                reason: format!("no tournament named {} exists", msg.tournament_name),
+            };
+            self.send_message(msg.id, message.dump());
+        }
+    }
+}
+
+/// the director has seated everyone, so open each of the tournament's tables
+/// and move the players there from the lobby
+impl Handler<SeatTournament> for TableHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: SeatTournament, ctx: &mut Context<Self>) {
+        let SeatTournament {
+            tournament_name,
+            fields,
+            level,
+            seating,
+        } = msg;
+        let director = match self.tournaments.get(&tournament_name) {
+            Some(director) => director.clone(),
+            None => return,
+        };
+        for (table_name, ids) in seating {
+            let actions = Arc::new(Mutex::new(HashMap::new()));
+            let meta_actions = Arc::new(Mutex::new(VecDeque::new()));
+            let cloned_actions = actions.clone();
+            let cloned_meta_actions = meta_actions.clone();
+
+            // the director runs the tournament and eliminates the players, not the table,
+            // and none of the other options are played in a tournament
+            let options = TableOptions {
+                tournament_name: Some(tournament_name.clone()),
+                betting_structure: fields.betting_structure,
+                ante: level.ante,
+                ..Default::default()
+            };
+            let mut game = Table::new(
+                ctx.address(),
+                table_name.clone(),
+                fields.variant,
+                fields.max_players,
+                level.small_blind,
+                level.big_blind,
+                fields.buy_in,
+                None, // the tables are private to the tournament
+                Uuid::nil(), // nobody can change the blinds of a tournament table
+                options,
+            );
+            for id in ids {
+                match self.main_lobby_connections.remove(&id) {
+                    Some(player_config) => {
+                        meta_actions
+                            .lock()
+                            .unwrap()
+                            .push_back(MetaAction::TournamentSeat(player_config, fields.buy_in));
+                        self.players_to_table.insert(id, table_name.clone());
+                    }
+                    None => {
+                        // they registered, but have since joined another table or timed out
+                        director.do_send(Withdraw { id });
+                    }
+                }
+            }
+
+            std::thread::spawn(move || {
+                game.play(&cloned_actions, &cloned_meta_actions, None);
+            });
+
+            self.tables_to_actions.insert(table_name.clone(), actions);
+            self.tables_to_meta_actions.insert(table_name.clone(), meta_actions);
+            self.private_tables.insert(table_name.clone());
+            self.tables_to_tournaments.insert(table_name, tournament_name.clone());
+        }
+    }
+}
+
+/// a tournament table reports how its hand went, which the director needs to know
+impl Handler<TableReport> for TableHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: TableReport, _: &mut Context<Self>) {
+        if let Some(director) = self.tournaments.get(&msg.tournament_name) {
+            director.do_send(msg);
+        }
+    }
+}
+
+/// the director wants players moved, so tell each player's current table to let them go
+impl Handler<ReseatPlayers> for TableHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: ReseatPlayers, _: &mut Context<Self>) {
+        for reseat in msg.reseats {
+            println!("reseating player {} from {} to {}", reseat.id, reseat.from, reseat.to);
+            if let Some(meta_actions) = self.tables_to_meta_actions.get(&reseat.from) {
+                meta_actions
+                    .lock()
+                    .unwrap()
+                    .push_back(MetaAction::Reseat(reseat.id, reseat.to));
+            }
+        }
+    }
+}
+
+/// a table let go of a player, so seat them (with their stack) at the table they are moving to
+impl Handler<Reseated> for TableHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: Reseated, _: &mut Context<Self>) {
+        let Reseated {
+            config,
+            money,
+            table_name,
+        } = msg;
+        let id = config.id;
+        if let Some(meta_actions) = self.tables_to_meta_actions.get(&table_name) {
+            meta_actions
+                .lock()
+                .unwrap()
+                .push_back(MetaAction::TournamentSeat(config, money));
+            self.players_to_table.insert(id, table_name);
+        } else {
+            // the table is gone, so the player can't continue in the tournament
+            let tournament_name = self
+                .players_to_table
+                .remove(&id)
+                .and_then(|old_table| self.tables_to_tournaments.get(&old_table));
+            if let Some(director) = tournament_name.and_then(|name| self.tournaments.get(name)) {
+                director.do_send(Withdraw { id });
+            }
+            self.main_lobby_connections.insert(id, config);
+        }
+    }
+}
+
+/// the blinds went up, so every table of the tournament plays the next level
+impl Handler<TournamentLevelUp> for TableHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: TournamentLevelUp, _: &mut Context<Self>) {
+        let TournamentLevelUp {
+            table_names,
+            level,
+            blinds,
+        } = msg;
+        for table_name in table_names {
+            if let Some(meta_actions) = self.tables_to_meta_actions.get(&table_name) {
+                meta_actions
+                    .lock()
+                    .unwrap()
+                    .push_back(MetaAction::TournamentLevel(level, blinds));
+            }
+        }
+    }
+}
+
+/// the director has news for these players
+impl Handler<TournamentNotice> for TableHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: TournamentNotice, _: &mut Context<Self>) {
+        let TournamentNotice { ids, message } = msg;
+        for id in ids {
+            self.send_message(id, message.clone());
+        }
+    }
+}
+
+/// the tournament has a winner, so forget its director.
+/// Its tables close, and send the winner back to the lobby
+impl Handler<TournamentOver> for TableHub {
+    type Result = ();
+
+    fn handle(&mut self, msg: TournamentOver, _: &mut Context<Self>) {
+        println!("tournament {} is over", msg.tournament_name);
+        self.tournaments.remove(&msg.tournament_name);
+        for (table_name, tournament_name) in self.tables_to_tournaments.iter() {
+            if *tournament_name != msg.tournament_name {
+                continue;
+            }
+            if let Some(meta_actions) = self.tables_to_meta_actions.get(table_name) {
+                meta_actions.lock().unwrap().push_back(MetaAction::TournamentOver);
+            }
+        }
+    }
+}
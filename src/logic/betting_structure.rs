@@ -0,0 +1,45 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::game_hand::GameHand;
+
+/// The betting structure decides how much a player is allowed to bet at a table
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(clippy::enum_variant_names)] // these are just the names that every poker player knows
+pub enum BettingStructure {
+    NoLimit, // bet anything up to your whole stack
+    PotLimit, // bet at most the size of the pot
+    FixedLimit, // every bet and raise is a fixed size, with a cap on the number of raises per street
+}
+
+impl fmt::Display for BettingStructure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+	let output = match self {
+	    BettingStructure::NoLimit => "no_limit",
+	    BettingStructure::PotLimit => "pot_limit",
+	    BettingStructure::FixedLimit => "fixed_limit",
+	};
+	write!(f, "{}", output)
+    }
+}
+
+impl BettingStructure {
+    /// in fixed-limit, a street can have one bet and three raises before the betting is capped
+    pub const FIXED_LIMIT_MAX_BETS: u32 = 4;
+
+    pub fn is_fixed_limit(&self) -> bool {
+	matches!(self, BettingStructure::FixedLimit)
+    }
+
+    /// the largest bet that the player at the given index is allowed to make,
+    /// or None if they can bet as much as they have
+    pub fn max_bet(&self, gamehand: &GameHand, index: usize) -> Option<u32> {
+	match self {
+	    BettingStructure::NoLimit => None,
+	    BettingStructure::PotLimit => Some(gamehand.pot_limit_max_bet(index)),
+	    BettingStructure::FixedLimit => Some(gamehand.fixed_limit_bet()),
+	}
+    }
+}
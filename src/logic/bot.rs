@@ -1,7 +1,7 @@
 use std::cmp;
 
-use super::card::Card;
-use super::hand_analysis::{HandRanking, HandResult};
+use super::card::{Card, Rank};
+use super::hand_analysis::{combinations, HandRanking, HandRankingRules, HandResult, LowHandResult};
 use super::player::{Player, PlayerAction};
 use super::game_hand::{GameHand, Street};
 
@@ -16,6 +16,35 @@ enum BotActionError {
 
 /// given a player and gamehand, this function returns a player action depending on the state of the game
 pub fn get_bot_action(player: &Player, gamehand: &GameHand, players: &[Option<Player>; 9]) -> PlayerAction {
+    let action = get_unlimited_bot_action(player, gamehand, players);
+    match (&action, player.index) {
+	(PlayerAction::Bet(_), Some(_)) if gamehand.is_capped() => {
+	    // no more raises are allowed, so just call
+	    PlayerAction::Call
+	}
+	(PlayerAction::Bet(_), Some(index)) if gamehand.betting_structure.is_fixed_limit() => {
+	    // the bots size their bets as if there were no limit, but in fixed-limit
+	    // the bet is always the same (or all-in if they can't afford it)
+	    let all_in = gamehand.get_current_contributions_for_index(index) + player.money;
+	    PlayerAction::Bet(cmp::min(gamehand.fixed_limit_bet(), all_in))
+	}
+	(PlayerAction::Bet(amount), Some(index)) => {
+	    // the bots size their bets as if there were no limit, so cap it at
+	    // the most that the betting structure allows (e.g. the size of the pot)
+	    match gamehand.betting_structure.max_bet(gamehand, index) {
+		Some(max_bet) => PlayerAction::Bet(cmp::min(*amount, max_bet)),
+		None => PlayerAction::Bet(*amount),
+	    }
+	}
+	_ => action,
+    }
+}
+
+/// the bot's action, ignoring any limit on the size of bets
+fn get_unlimited_bot_action(player: &Player, gamehand: &GameHand, players: &[Option<Player>; 9]) -> PlayerAction {
+    if gamehand.variant.is_lowball() {
+	return get_lowball_action(player, gamehand).unwrap_or(PlayerAction::Fold);
+    }
     match gamehand.street {
         Street::Preflop => {
 	    let blah = get_preflop_action(player, gamehand, players); //
@@ -34,6 +63,17 @@ pub fn get_bot_action(player: &Player, gamehand: &GameHand, players: &[Option<Pl
 	    // this should never happen
 	    get_post_flop_action(player, gamehand).unwrap_or(PlayerAction::Fold)	    	    
 	}
+        Street::Third => {
+	    // the first stud street is played like preflop, judging the starting cards
+	    get_preflop_action(player, gamehand, players).unwrap_or(PlayerAction::Fold)
+        }
+        Street::Fourth | Street::Fifth | Street::Sixth | Street::Seventh => {
+	    get_post_flop_action(player, gamehand).unwrap_or(PlayerAction::Fold)
+        }
+        Street::PreDraw | Street::FirstDraw | Street::SecondDraw | Street::ThirdDraw => {
+	    // in five card draw there is no board, so the bot judges its own five cards
+	    get_post_flop_action(player, gamehand).unwrap_or(PlayerAction::Fold)
+        }
     }
 }
 
@@ -53,6 +93,7 @@ fn score_preflop_hand(hole_cards: &Vec<Card>) -> Result<f32, BotActionError>  {
     let lower = cmp::min(rank1, rank2);
 
     let mut score: f32 = match higher {
+	15 => 12.0, // joker, which can pair up with anything
 	14 => 10.0, // ace
 	13 => 8.0, // king
 	12 => 7.0, // queen
@@ -93,7 +134,20 @@ fn score_preflop_hand(hole_cards: &Vec<Card>) -> Result<f32, BotActionError>  {
     Ok(score.ceil())
 }
 
-#[derive(Debug, PartialEq)]
+/// the Chen Formula only knows about two hole cards, so for variants
+/// with more (e.g. Omaha) we score the best pair that could be made from them
+fn score_starting_hand(hole_cards: &[Card]) -> Result<f32, BotActionError> {
+    if hole_cards.len() <= 2 {
+	return score_preflop_hand(&hole_cards.to_vec());
+    }
+    combinations(hole_cards, 2)
+	.iter()
+	.map(score_preflop_hand)
+	.max_by(|a, b| a.as_ref().unwrap_or(&f32::MIN).total_cmp(b.as_ref().unwrap_or(&f32::MIN)))
+	.unwrap_or(Err(BotActionError::NoHoleCards))
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum HandQuality {
     Garbage,
     Mediocre,
@@ -108,10 +162,16 @@ fn qualify_hand(player: &Player, hand_result: &HandResult, gamehand: &GameHand)
     //       TwoPair with a board pair is also worse than good (unless top?)
     //       Sets are not as good as straight or flush? i.e. if a strong draw is on the board,
     //       then our set should be treated a bit more sceptically.
-    let top_rank = gamehand.highest_rank().unwrap();
-    let mut on_the_board = true; // if a hand result came from board cards and not hole cards
+    // in stud there is no board, so a pair is considered top pair if we hold nothing higher
+    let own_cards = player.cards();
+    let top_rank = gamehand.highest_rank()
+	.or_else(|| own_cards.iter().map(|card| card.rank).max())
+	.unwrap();
+    // if a hand result came from board cards and not hole cards.
+    // A wild card of our own can stand for any card, so it always plays
+    let mut on_the_board = !own_cards.iter().any(|card| card.is_wild(&gamehand.wild_cards.ranks));
     for used_card in &hand_result.constituent_cards {
-	for hole_card in &player.hole_cards {
+	for hole_card in &own_cards {
 	    if used_card == hole_card {
 		on_the_board = false;
 		break;
@@ -137,17 +197,33 @@ fn qualify_hand(player: &Player, hand_result: &HandResult, gamehand: &GameHand)
 	}
 	HandRanking::TwoPair => HandQuality::Good,
 	HandRanking::ThreeOfAKind => {
-	    if player.hole_cards[0].rank == player.hole_cards[1].rank {
-		// we have a set
+	    let trips_rank = hand_result.constituent_cards[0].rank;
+	    if player.hole_cards.iter().filter(|c| c.rank == trips_rank).count() >= 2 || gamehand.variant.is_stud() {
+		// we have a set (in stud, all of the cards are our own)
 		HandQuality::Great
 	    } else {
 		// just trips
 		HandQuality::Good
 	    }
 	},
-	HandRanking::Straight | HandRanking::Flush => HandQuality::Great,
-	HandRanking::FullHouse | HandRanking::FourOfAKind
-	    | HandRanking::StraightFlush | HandRanking::RoyalFlush => HandQuality::Exceptional,	    
+	HandRanking::Straight => {
+	    if gamehand.variant.hand_ranking_rules() == HandRankingRules::ShortDeck {
+		// with fewer ranks in the deck, straights come up much more often
+		HandQuality::Good
+	    } else {
+		HandQuality::Great
+	    }
+	},
+	HandRanking::Flush => {
+	    if gamehand.variant.hand_ranking_rules() == HandRankingRules::ShortDeck {
+		// a flush beats a full house in short-deck
+		HandQuality::Exceptional
+	    } else {
+		HandQuality::Great
+	    }
+	},
+	HandRanking::FullHouse | HandRanking::FourOfAKind | HandRanking::StraightFlush
+	    | HandRanking::RoyalFlush | HandRanking::FiveOfAKind => HandQuality::Exceptional,	    
 	}
 	
     }
@@ -443,7 +519,7 @@ fn get_preflop_action(player: &Player, gamehand: &GameHand, players: &[Option<Pl
     if player.index.is_none(){
 	return Err(BotActionError::NoIndexSet);
     }
-    let score = score_preflop_hand(&player.hole_cards)?;
+    let score = score_starting_hand(&player.cards())?;
     println!("inside preflop. score = {:?} with hole cards = {:?}", score, &player.hole_cards);
     let bot_contribution = gamehand.get_current_contributions_for_index(player.index.unwrap());    
     let cannot_check = bot_contribution < gamehand.current_bet;
@@ -505,10 +581,192 @@ fn get_post_flop_action(player: &Player, gamehand: &GameHand) -> Result<PlayerAc
     if player.index.is_none(){
 	return Err(BotActionError::NoIndexSet);
     }
-    let best_hand = player.determine_best_hand(gamehand).unwrap();
-    println!("inside flop action. best hand = {:?}", best_hand);
     println!("street contributions:\n{:?}", gamehand.street_contributions);
-    let quality = qualify_hand(player, &best_hand, gamehand);
+    if gamehand.extra_boards.is_empty() {
+	let best_hand = player.determine_best_hand(gamehand).unwrap();
+	println!("inside flop action. best hand = {:?}", best_hand);
+	let quality = qualify_hand(player, &best_hand, gamehand);
+	return Ok(get_action_for_quality(player, gamehand, quality));
+    }
+    // with more than one board (e.g. double-board Hold'em), a strong hand on one board
+    // only wins part of the pot, so the bot plays the hand it has on its worst board
+    let quality = gamehand.boards()
+	.iter()
+	.map(|board| {
+	    let board_hand = gamehand.with_board(board);
+	    let best_hand = player.determine_best_hand(&board_hand).unwrap();
+	    println!("inside flop action. best hand = {:?} on board {:?}", best_hand, board);
+	    qualify_hand(player, &best_hand, &board_hand)
+	})
+	.min()
+	.unwrap();
+    Ok(get_action_for_quality(player, gamehand, quality))
+}
+
+/// in a lowball game (e.g. Razz), the bot judges the quality of its best low hand
+fn get_lowball_action(player: &Player, gamehand: &GameHand) -> Result<PlayerAction, BotActionError> {
+    if player.index.is_none(){
+	return Err(BotActionError::NoIndexSet);
+    }
+    let low_hand = player.determine_best_low_hand(gamehand).ok_or(BotActionError::NoHoleCards)?;
+    println!("inside lowball action. best low hand = {:?}", low_hand);
+    // there is still hope for a bad hand if there are draws to come
+    let can_draw = gamehand.street != Street::ThirdDraw;
+    let quality = if gamehand.variant.is_badugi() {
+	qualify_badugi_hand(&low_hand, can_draw)
+    } else if gamehand.variant.is_deuce_to_seven() {
+	qualify_deuce_to_seven_hand(player, &low_hand, can_draw)
+    } else {
+	qualify_low_hand(&low_hand)
+    };
+    Ok(get_action_for_quality(player, gamehand, quality))
+}
+
+// returns a hand quality enum for a low hand, depending on how high its highest card is
+// (for use by bots)
+fn qualify_low_hand(low_hand: &LowHandResult) -> HandQuality {
+    let is_paired = low_hand.value >> 20 > 0;
+    let highest = LowHandResult::ace_low_rank(&low_hand.cards[0]);
+    let complete = low_hand.cards.len() == 5; // we can't complain about a high card we might still draw past
+    match (is_paired, highest) {
+	(true, _) => HandQuality::Garbage,
+	(false, 1..=6) if complete => HandQuality::Exceptional,
+	(false, 1..=6) => HandQuality::Good,
+	(false, 7) if complete => HandQuality::Great,
+	(false, 7..=8) => HandQuality::Mediocre,
+	_ => HandQuality::Garbage,
+    }
+}
+
+// returns a hand quality enum for a deuce-to-seven hand, depending on how high its highest card is,
+// or how many good cards it has to draw to (for use by bots)
+fn qualify_deuce_to_seven_hand(player: &Player, low_hand: &LowHandResult, can_draw: bool) -> HandQuality {
+    let is_made = low_hand.value >> 20 == HandRanking::HighCard as u32;
+    let highest = low_hand.cards[0].rank as u32;
+    let num_kept = player.hole_cards.len() - get_deuce_to_seven_discards(player).len();
+    match (is_made, highest) {
+	(true, 2..=7) => HandQuality::Exceptional,
+	(true, 8) => HandQuality::Great,
+	(true, 9) => HandQuality::Good,
+	(true, 10) => HandQuality::Mediocre,
+	_ if can_draw && num_kept >= 4 => HandQuality::Mediocre,
+	_ => HandQuality::Garbage,
+    }
+}
+
+// returns a hand quality enum for a badugi, depending on how many of its cards count,
+// and how high its highest card is (for use by bots)
+fn qualify_badugi_hand(low_hand: &LowHandResult, can_draw: bool) -> HandQuality {
+    let highest = LowHandResult::ace_low_rank(&low_hand.cards[0]);
+    match (low_hand.cards.len(), highest) {
+	(4, 1..=7) => HandQuality::Exceptional,
+	(4, 8..=9) => HandQuality::Great,
+	(4, 10..=11) => HandQuality::Good,
+	(4, _) => HandQuality::Mediocre,
+	(3, 1..=6) if can_draw => HandQuality::Mediocre,
+	_ => HandQuality::Garbage,
+    }
+}
+
+/// in a draw game, the bot chooses which cards to throw away
+pub fn get_bot_discards(player: &Player, gamehand: &GameHand) -> Vec<Card> {
+    if gamehand.variant.is_badugi() {
+	get_badugi_discards(player)
+    } else if gamehand.variant.is_deuce_to_seven() {
+	get_deuce_to_seven_discards(player)
+    } else {
+	get_high_hand_discards(player, gamehand)
+    }
+}
+
+/// in five card draw, the bot stands pat with a straight or better.
+/// Otherwise it keeps the cards that make its hand (e.g. a pair, or just its highest card)
+/// along with any wild cards, and throws away the rest
+fn get_high_hand_discards(player: &Player, gamehand: &GameHand) -> Vec<Card> {
+    let hand = gamehand.analyze_hand(player.hole_cards.clone());
+    if hand.hand_ranking >= HandRanking::Straight {
+	return vec![];
+    }
+    // the wild cards were resolved into other cards, so look for the kickers among the hole cards
+    player.hole_cards
+	.iter()
+	.filter(|card| !card.is_wild(&gamehand.wild_cards.ranks) && !hand.constituent_cards.contains(card))
+	.copied()
+	.collect()
+}
+
+/// in deuce-to-seven, the bot stands pat with any nine-low or better, and otherwise
+/// keeps one of each card from 2 to 8
+fn get_deuce_to_seven_discards(player: &Player) -> Vec<Card> {
+    let low_hand = LowHandResult::analyze_deuce_to_seven(player.hole_cards.clone());
+    let is_made = low_hand.value >> 20 == HandRanking::HighCard as u32;
+    if is_made && low_hand.cards[0].rank <= Rank::Nine {
+	return vec![];
+    }
+    let mut kept_ranks = vec![];
+    let mut discards = vec![];
+    // the cards are sorted from most to least significant, so look at the lowest first
+    for card in low_hand.cards.iter().rev() {
+	if card.rank <= Rank::Eight && !kept_ranks.contains(&card.rank) {
+	    kept_ranks.push(card.rank);
+	} else {
+	    discards.push(*card);
+	}
+    }
+    if discards.is_empty() {
+	// the cards are all low and different, but they make a straight or a flush,
+	// so break it up by throwing away the highest card
+	discards.push(low_hand.cards[0]);
+    }
+    discards
+}
+
+/// in Badugi, the bot stands pat with any four card badugi that is ten-high or better.
+/// Otherwise it keeps the cards that count towards its badugi, as long as they are eight or lower
+fn get_badugi_discards(player: &Player) -> Vec<Card> {
+    let badugi = LowHandResult::analyze_badugi(player.hole_cards.clone());
+    if badugi.cards.len() == 4 && LowHandResult::ace_low_rank(&badugi.cards[0]) <= 10 {
+	return vec![];
+    }
+    player.hole_cards
+	.iter()
+	.filter(|card| {
+	    !badugi.cards.iter().any(|kept| kept.is_identical(card)) || LowHandResult::ace_low_rank(card) > 8
+	})
+	.copied()
+	.collect()
+}
+
+/// in Pineapple, the bot chooses which of its three hole cards to throw away.
+/// Before the flop it keeps the two cards with the best starting hand score,
+/// and after the flop it keeps the two that make the best hand with the board
+pub fn get_bot_discard(player: &Player, gamehand: &GameHand) -> Card {
+    let board = gamehand.board_cards();
+    // the cards that are kept if the card at the given index is thrown away
+    let kept = |discard_idx: usize| -> Vec<Card> {
+	player.hole_cards.iter().enumerate()
+	    .filter(|(i, _)| *i != discard_idx)
+	    .map(|(_, card)| *card)
+	    .collect()
+    };
+    let indices = 0..player.hole_cards.len();
+    let discard_idx = if board.is_empty() {
+	let score = |i| score_preflop_hand(&kept(i)).unwrap_or(f32::MIN);
+	indices.max_by(|a, b| score(*a).total_cmp(&score(*b)))
+    } else {
+	indices.max_by_key(|i| {
+	    let cards: Vec<Card> = kept(*i).into_iter().chain(board.iter().copied()).collect();
+	    combinations(&cards, 5)
+		.into_iter()
+		.map(|hand| gamehand.analyze_hand(hand))
+		.max()
+	})
+    };
+    player.hole_cards[discard_idx.expect("the bot has no hole cards to throw away")]
+}
+
+/// once the bot knows how good its hand is, choose an action
+fn get_action_for_quality(player: &Player, gamehand: &GameHand, quality: HandQuality) -> PlayerAction {
     let bot_contribution = gamehand.get_current_contributions_for_index(player.index.unwrap());    
     let cannot_check = bot_contribution < gamehand.current_bet;
     let facing_raise = gamehand.current_bet > 0;
@@ -522,24 +780,24 @@ fn get_post_flop_action(player: &Player, gamehand: &GameHand) -> Result<PlayerAc
 	// TODO: need to consider number of players and position
 	HandQuality::Garbage => {
 	    println!("about to get garbage action");				
-	    Ok(get_garbage_action(player, gamehand, cannot_check, facing_raise, bet_size))		
+	    get_garbage_action(player, gamehand, cannot_check, facing_raise, bet_size)
 	}
 	HandQuality::Mediocre => {
 	    println!("about to get a mediocre action");		
-	    Ok(get_mediocre_action(player, gamehand, cannot_check, facing_raise, bet_size))
+	    get_mediocre_action(player, gamehand, cannot_check, facing_raise, bet_size)
 	}
 	HandQuality::Good => {
 	    println!("about to get a good action");		
-	    Ok(get_good_action(player, gamehand, cannot_check, facing_raise, bet_size))
+	    get_good_action(player, gamehand, cannot_check, facing_raise, bet_size)
 	}
 	HandQuality::Great => {
 	    println!("about to get a great action");				
-	    Ok(get_big_action(player, gamehand, cannot_check, facing_raise, bet_size))
+	    get_big_action(player, gamehand, cannot_check, facing_raise, bet_size)
 	}
 	HandQuality::Exceptional => {
 	    println!("about to get an exceptional action");
 	    let amount: u32 = std::cmp::min(player.money, bet_size);
-	    Ok(PlayerAction::Bet(amount))
+	    PlayerAction::Bet(amount)
 	}
     }    
 }
@@ -549,6 +807,7 @@ fn get_post_flop_action(player: &Player, gamehand: &GameHand) -> Result<PlayerAc
 mod tests {
     use super::*;
     use crate::logic::card::{Card, Rank, Suit};
+    use crate::logic::variant::Variant;
 
     use std::convert::TryInto;
 
@@ -1048,6 +1307,43 @@ mod tests {
 	assert_eq!(quality, HandQuality::Garbage);
     }
 
+    /// at a short-deck table a flush beats a full house, so the bot should value it as such
+    #[test]
+    fn qualify_short_deck_flush() {
+	let (mut players, mut gamehand) = set_up_game_hand(2, 2);
+	gamehand.variant = Variant::ShortDeck;
+	let bot0 = players[0].as_mut().unwrap();
+        bot0.hole_cards.push(Card {
+            rank: Rank::King,
+            suit: Suit::Heart,
+        });
+        bot0.hole_cards.push(Card {
+            rank: Rank::Six,
+            suit: Suit::Heart,
+        });
+
+	gamehand.street = Street::Flop;
+	gamehand.flop = Some(vec![
+	    Card {
+		rank: Rank::Ten,
+		suit: Suit::Heart,
+            },
+	    Card {
+		rank: Rank::Jack,
+		suit: Suit::Heart,
+            },
+	    Card {
+		rank: Rank::Eight,
+		suit: Suit::Heart,
+            }
+	]);
+
+	let best_hand = bot0.determine_best_hand(&gamehand).unwrap();
+	assert_eq!(best_hand.hand_ranking, HandRanking::Flush);
+	let quality = qualify_hand(bot0, &best_hand, &gamehand);
+	assert_eq!(quality, HandQuality::Exceptional);
+    }
+
     /// if a bot has a good hand, but gets raised on the river with a strong
     /// draw on the board, they will likely fold
     #[test]
@@ -1110,5 +1406,81 @@ mod tests {
 	}
         assert!(num_folds > 12);
     }
-    
+
+    /// in five card draw, the bot keeps its pair and draws to it, but stands pat with a straight
+    #[test]
+    fn five_card_draw_discards() {
+	let (mut players, mut gamehand) = set_up_game_hand(2, 2);
+	gamehand.variant = Variant::FiveCardDraw;
+	gamehand.street = Street::FirstDraw;
+	let bot0 = players[0].as_mut().unwrap();
+	bot0.hole_cards = vec![
+	    Card { rank: Rank::Queen, suit: Suit::Heart },
+	    Card { rank: Rank::Seven, suit: Suit::Club },
+	    Card { rank: Rank::Queen, suit: Suit::Diamond },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	    Card { rank: Rank::Ace, suit: Suit::Club },
+	];
+	let discards = get_bot_discards(players[0].as_ref().unwrap(), &gamehand);
+	assert_eq!(discards.len(), 3);
+	assert!(discards.iter().all(|card| card.rank != Rank::Queen));
+
+	let bot0 = players[0].as_mut().unwrap();
+	bot0.hole_cards[2] = Card { rank: Rank::Jack, suit: Suit::Diamond };
+	bot0.hole_cards[1] = Card { rank: Rank::King, suit: Suit::Club };
+	bot0.hole_cards[3] = Card { rank: Rank::Ten, suit: Suit::Spade };
+	assert!(get_bot_discards(players[0].as_ref().unwrap(), &gamehand).is_empty());
+    }
+
+    /// in Badugi, the bot throws away the cards that don't count and any high cards,
+    /// but stands pat with a good four card badugi
+    #[test]
+    fn badugi_discards() {
+	let (mut players, mut gamehand) = set_up_game_hand(2, 2);
+	gamehand.variant = Variant::BadugiTripleDraw;
+	gamehand.street = Street::FirstDraw;
+	let bot0 = players[0].as_mut().unwrap();
+	bot0.hole_cards = vec![
+	    Card { rank: Rank::Three, suit: Suit::Heart },
+	    Card { rank: Rank::Five, suit: Suit::Heart },
+	    Card { rank: Rank::Queen, suit: Suit::Diamond },
+	    Card { rank: Rank::Ace, suit: Suit::Spade },
+	];
+	// the five of hearts doesn't count and the queen is too high
+	let discards = get_bot_discards(players[0].as_ref().unwrap(), &gamehand);
+	assert_eq!(discards.len(), 2);
+	assert!(discards.iter().any(|card| card.is_identical(&Card { rank: Rank::Five, suit: Suit::Heart })));
+	assert!(discards.iter().any(|card| card.is_identical(&Card { rank: Rank::Queen, suit: Suit::Diamond })));
+
+	let bot0 = players[0].as_mut().unwrap();
+	bot0.hole_cards[1] = Card { rank: Rank::Eight, suit: Suit::Club };
+	bot0.hole_cards[2] = Card { rank: Rank::Nine, suit: Suit::Diamond };
+	assert!(get_bot_discards(players[0].as_ref().unwrap(), &gamehand).is_empty());
+    }
+
+    /// in Pineapple, the bot keeps its best two starting cards before the flop,
+    /// and after the flop it keeps the cards that make the best hand
+    #[test]
+    fn pineapple_discards() {
+	let (mut players, mut gamehand) = set_up_game_hand(2, 2);
+	gamehand.variant = Variant::CrazyPineapple;
+	let bot0 = players[0].as_mut().unwrap();
+	bot0.hole_cards = vec![
+	    Card { rank: Rank::Ace, suit: Suit::Heart },
+	    Card { rank: Rank::Seven, suit: Suit::Club },
+	    Card { rank: Rank::Ace, suit: Suit::Diamond },
+	];
+	let bot0 = players[0].as_ref().unwrap();
+	// a pair of aces is the best start
+	assert!(get_bot_discard(bot0, &gamehand).is_identical(&Card { rank: Rank::Seven, suit: Suit::Club }));
+
+	// but the flop gives the seven trips, which beats two pair with the aces
+	gamehand.street = Street::Flop;
+	gamehand.flop = Some(vec![
+	    Card { rank: Rank::Seven, suit: Suit::Spade },
+	    Card { rank: Rank::Seven, suit: Suit::Diamond },
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	]);
+	assert_eq!(get_bot_discard(bot0, &gamehand).rank, Rank::Ace);
+    }
 }
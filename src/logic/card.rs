@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use std::fmt;
+use std::str::FromStr;
 ///
 /// This file contains structs/enums/methods for defining, using, and comparing cards and hands of cards
 ///
@@ -20,6 +21,8 @@ pub enum Rank {
     Queen = 12,
     King = 13,
     Ace = 14,
+    #[strum(disabled)] // not in a standard deck, so it is left out when iterating over the ranks
+    Joker = 15, // always wild, and can stand for any card
 }
 
 impl fmt::Display for Rank {
@@ -38,11 +41,35 @@ impl fmt::Display for Rank {
             Rank::Queen => "Q",
             Rank::King => "K",
             Rank::Ace => "A",
+            Rank::Joker => "X",
         };
         write!(f, "{}", string)
     }
 }
 
+impl Rank {
+    /// parse a rank from the character that we display it with, e.g. 'T' or '2'
+    pub fn from_char(c: char) -> Option<Rank> {
+        match c.to_ascii_uppercase() {
+            '2' => Some(Rank::Two),
+            '3' => Some(Rank::Three),
+            '4' => Some(Rank::Four),
+            '5' => Some(Rank::Five),
+            '6' => Some(Rank::Six),
+            '7' => Some(Rank::Seven),
+            '8' => Some(Rank::Eight),
+            '9' => Some(Rank::Nine),
+            'T' => Some(Rank::Ten),
+            'J' => Some(Rank::Jack),
+            'Q' => Some(Rank::Queen),
+            'K' => Some(Rank::King),
+            'A' => Some(Rank::Ace),
+            'X' => Some(Rank::Joker),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Eq, PartialEq, PartialOrd, Ord, Debug, Copy, Clone, EnumIter, Hash)]
 pub enum Suit {
     Club,
@@ -75,6 +102,44 @@ impl fmt::Display for Card {
     }
 }
 
+impl Card {
+    /// Cards are compared by their rank alone, so this checks if two cards are exactly the same card
+    pub fn is_identical(&self, other: &Card) -> bool {
+        self.rank == other.rank && self.suit == other.suit
+    }
+
+    /// a joker is always wild, and otherwise a card is wild if its rank is one of the wild ranks,
+    /// e.g. every Two when playing deuces wild
+    pub fn is_wild(&self, wild_ranks: &[Rank]) -> bool {
+        self.rank == Rank::Joker || wild_ranks.contains(&self.rank)
+    }
+}
+
+/// parse a card from the same format that we display it with, e.g. "Th" or "2c"
+impl FromStr for Card {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (rank_char, suit_char) = match (chars.next(), chars.next(), chars.next()) {
+            (Some(rank_char), Some(suit_char), None) => (rank_char, suit_char),
+            _ => return Err(format!("invalid card: {}", s)),
+        };
+        let rank = match Rank::from_char(rank_char) {
+            Some(rank) => rank,
+            None => return Err(format!("invalid rank for card: {}", s)),
+        };
+        let suit = match suit_char.to_ascii_lowercase() {
+            'c' => Suit::Club,
+            'd' => Suit::Diamond,
+            'h' => Suit::Heart,
+            's' => Suit::Spade,
+            _ => return Err(format!("invalid suit for card: {}", s)),
+        };
+        Ok(Card { rank, suit })
+    }
+}
+
 /// We simply compare Cards based on their rank field.
 impl Ord for Card {
     fn cmp(&self, other: &Self) -> Ordering {
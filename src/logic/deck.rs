@@ -14,26 +14,63 @@ pub trait Deck: Send + std::fmt::Debug {
     /// shuffle the deck to randomize (possibly) the output of future cards
     fn shuffle(&mut self);
 
-    /// give us a single card. Optional, because the deck may be exhausted
+    /// give us a single card. Optional, because the deck may be exhausted.
+    /// If the deck runs out in a draw game, the discards are shuffled to form a new deck
     fn draw_card(&mut self) -> Option<Card>;
+
+    /// put a card that a player threw away (e.g. in a draw game) into the discard pile
+    fn discard(&mut self, card: Card);
+}
+
+/// when the deck has run out, the discarded cards are shuffled and put back into the deck.
+/// The cards vec always holds every card of the deck, so we move the discards out of
+/// the dealt cards, and put them after the dealt cards to be drawn next
+fn reshuffle_discards(cards: &mut Vec<Card>, top: &mut usize, discards: &mut Vec<Card>, randomize: bool) {
+    cards.retain(|card| !discards.iter().any(|discard| discard.is_identical(card)));
+    *top = cards.len();
+    if randomize {
+        discards.shuffle(&mut rand::thread_rng());
+    }
+    cards.append(discards);
 }
 
 #[derive(Debug)]
 pub struct StandardDeck {
     cards: Vec<Card>,
     top: usize, // index that we deal the next card from
+    discards: Vec<Card>, // cards thrown away during the current hand
 }
 
 impl StandardDeck {
     pub fn new() -> Self {
         // returns a new unshuffled deck of 52 cards
+        StandardDeck::with_ranks(Rank::iter())
+    }
+
+    /// the short deck is used for short-deck (6+) Hold'em,
+    /// in which all of the 2s, 3s, 4s, and 5s are removed, leaving 36 cards
+    pub fn short() -> Self {
+        StandardDeck::with_ranks(Rank::iter().filter(|rank| *rank >= Rank::Six))
+    }
+
+    /// an unshuffled deck with every suit of each of the given ranks
+    fn with_ranks(ranks: impl Iterator<Item = Rank>) -> Self {
         let mut cards = Vec::<Card>::with_capacity(52);
-        for rank in Rank::iter() {
+        for rank in ranks {
             for suit in Suit::iter() {
                 cards.push(Card { rank, suit });
             }
         }
-        Self { cards, top: 0 }
+        Self { cards, top: 0, discards: vec![] }
+    }
+
+    /// a standard deck with some jokers added, up to one black and one red joker
+    pub fn with_jokers(num_jokers: u8) -> Self {
+        let mut deck = StandardDeck::new();
+        for suit in [Suit::Spade, Suit::Heart].iter().take(num_jokers as usize) {
+            deck.cards.push(Card { rank: Rank::Joker, suit: *suit });
+        }
+        deck
     }
 }
 
@@ -42,9 +79,13 @@ impl Deck for StandardDeck {
         // shuffle the deck of cards
         self.cards.shuffle(&mut rand::thread_rng());
         self.top = 0;
+        self.discards.clear();
     }
 
     fn draw_card(&mut self) -> Option<Card> {
+        if self.top == self.cards.len() && !self.discards.is_empty() {
+            reshuffle_discards(&mut self.cards, &mut self.top, &mut self.discards, true);
+        }
         // take the top card from the deck and move the index of the top of the deck
         if self.top == self.cards.len() {
             // the deck is exhausted, no card to give
@@ -55,6 +96,10 @@ impl Deck for StandardDeck {
             Some(card)
         }
     }
+
+    fn discard(&mut self, card: Card) {
+        self.discards.push(card);
+    }
 }
 
 /// the rigged deck is useful for unit testing
@@ -62,13 +107,14 @@ impl Deck for StandardDeck {
 pub struct RiggedDeck {
     cards: Vec<Card>,
     top: usize, // index that we deal the next card from
+    discards: Vec<Card>, // cards thrown away during the current hand
 }
 
 impl RiggedDeck {
     #[allow(dead_code)]    
     pub fn new() -> Self {
         let cards = Vec::<Card>::new();
-        Self { cards, top: 0 }
+        Self { cards, top: 0, discards: vec![] }
     }
 
     /// push a card into the deck.
@@ -83,7 +129,11 @@ impl Deck for RiggedDeck {
     /// shuffle does nothing
     fn shuffle(&mut self) {}
 
+    /// when the rigged deck runs out, the discards are dealt again in the order they were thrown away
     fn draw_card(&mut self) -> Option<Card> {
+        if self.top == self.cards.len() && !self.discards.is_empty() {
+            reshuffle_discards(&mut self.cards, &mut self.top, &mut self.discards, false);
+        }
         // take the top card from the deck and move the index of the top of the deck
         if self.top == self.cards.len() {
             // the deck is exhausted, no card to give
@@ -94,4 +144,8 @@ impl Deck for RiggedDeck {
             Some(card)
         }
     }
+
+    fn discard(&mut self, card: Card) {
+        self.discards.push(card);
+    }
 }
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+/// everything one player has bought at a freezeout table
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Purchases {
+    pub player_name: Option<String>,
+    pub buy_ins: u32, // how many times they bought in for the starting stack (once, unless they left and came back)
+    pub rebuys: u32,
+    pub add_on: bool,
+    pub total: u32, // how many chips they paid for altogether
+    pub prize: u32, // what they were paid out of the prize pool
+}
+
+/// In a freezeout, a player who runs out of chips is eliminated instead of sitting at the table with an empty stack.
+/// For the first few hands (the rebuy window), a busted player can buy back in a limited number of times,
+/// and everyone can take a one-time add-on at the end of the window.
+/// New players can still join until the late entry hand.
+/// Every purchase goes into the prize pool, which is kept in a ledger
+#[derive(Debug, Default)]
+pub struct Freezeout {
+    max_rebuys: u32, // how many times each player can rebuy (0 for never)
+    rebuy_hands: u32, // rebuys are allowed through this hand
+    add_on: u32, // how many chips the add-on is for (0 for no add-on)
+    late_entry_hands: u32, // new players can join through this hand
+    ledger: HashMap<Uuid, Purchases>,
+    eliminated: Vec<Uuid>,
+}
+
+impl Freezeout {
+    pub fn new(max_rebuys: u32, rebuy_hands: u32, add_on: u32, late_entry_hands: u32) -> Self {
+	Self {
+	    max_rebuys,
+	    rebuy_hands,
+	    add_on,
+	    late_entry_hands,
+	    ..Default::default()
+	}
+    }
+
+    /// can a new player join before the given hand is dealt.
+    /// Anyone can join before the first hand, and nobody who was eliminated can come back
+    pub fn can_enter(&self, id: Uuid, hand_num: u32) -> bool {
+	self.late_entry_open(hand_num) && !self.eliminated.contains(&id)
+    }
+
+    pub fn late_entry_open(&self, hand_num: u32) -> bool {
+	hand_num <= self.late_entry_hands.max(1)
+    }
+
+    /// a player bought in for the starting stack
+    pub fn buy_in(&mut self, id: Uuid, player_name: Option<String>, amount: u32) {
+	let purchases = self.ledger.entry(id).or_default();
+	purchases.player_name = player_name;
+	purchases.buy_ins += 1;
+	purchases.total += amount;
+    }
+
+    /// the rebuy window is open from the first hand until the break after the last rebuy hand
+    pub fn rebuys_open(&self, hand_num: u32) -> bool {
+	self.max_rebuys > 0 && hand_num <= self.rebuy_hands + 1
+    }
+
+    /// a busted player can rebuy if the window is open and they have rebuys left
+    pub fn can_rebuy(&self, id: Uuid, hand_num: u32) -> bool {
+	let rebuys = self.ledger.get(&id).map_or(0, |purchases| purchases.rebuys);
+	self.rebuys_open(hand_num) && rebuys < self.max_rebuys
+    }
+
+    pub fn rebuy(&mut self, id: Uuid, amount: u32) {
+	let purchases = self.ledger.entry(id).or_default();
+	purchases.rebuys += 1;
+	purchases.total += amount;
+    }
+
+    /// the add-on can be taken just before, or just after, the last hand of the rebuy window
+    pub fn add_on_open(&self, hand_num: u32) -> bool {
+	self.add_on > 0 && (hand_num == self.rebuy_hands || hand_num == self.rebuy_hands + 1)
+    }
+
+    /// how many chips the player gets for the add-on, if they can still take it
+    pub fn take_add_on(&mut self, id: Uuid, hand_num: u32) -> Option<u32> {
+	if !self.add_on_open(hand_num) {
+	    return None;
+	}
+	let purchases = self.ledger.entry(id).or_default();
+	if purchases.add_on {
+	    return None;
+	}
+	purchases.add_on = true;
+	purchases.total += self.add_on;
+	Some(self.add_on)
+    }
+
+    /// record what the player won out of the prize pool (e.g. in a deal to end the game)
+    pub fn award_prize(&mut self, id: Uuid, amount: u32) {
+	self.ledger.entry(id).or_default().prize += amount;
+    }
+
+    pub fn eliminate(&mut self, id: Uuid) {
+	if !self.eliminated.contains(&id) {
+	    self.eliminated.push(id);
+	}
+    }
+
+    /// how many players have bought in
+    pub fn num_entrants(&self) -> usize {
+	self.ledger.len()
+    }
+
+    /// every chip that has been paid for
+    pub fn prize_pool(&self) -> u32 {
+	self.ledger.values().map(|purchases| purchases.total).sum()
+    }
+
+    /// what each player has bought, from who paid the most
+    pub fn ledger(&self) -> Vec<Purchases> {
+	let mut ledger: Vec<Purchases> = self.ledger.values().cloned().collect();
+	ledger.sort_by_key(|purchases| std::cmp::Reverse(purchases.total));
+	ledger
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebuys_and_add_on() {
+	let mut freezeout = Freezeout::new(2, 5, 500, 3);
+	let id = Uuid::new_v4();
+	assert!(freezeout.can_enter(id, 3));
+	assert!(!freezeout.can_enter(id, 4));
+	freezeout.buy_in(id, Some("Adam".to_string()), 1000);
+
+	// two rebuys at most, and only during the window
+	assert!(freezeout.can_rebuy(id, 2));
+	freezeout.rebuy(id, 1000);
+	freezeout.rebuy(id, 1000);
+	assert!(!freezeout.can_rebuy(id, 2));
+	let other = Uuid::new_v4();
+	assert!(freezeout.can_rebuy(other, 6));
+	assert!(!freezeout.can_rebuy(other, 7));
+
+	// the add-on is only at the end of the window, and only once
+	assert_eq!(freezeout.take_add_on(id, 4), None);
+	assert_eq!(freezeout.take_add_on(id, 5), Some(500));
+	assert_eq!(freezeout.take_add_on(id, 6), None);
+
+	assert_eq!(freezeout.prize_pool(), 3500);
+	let ledger = freezeout.ledger();
+	assert_eq!(ledger[0], Purchases {
+	    player_name: Some("Adam".to_string()),
+	    buy_ins: 1,
+	    rebuys: 2,
+	    add_on: true,
+	    total: 3500,
+	    prize: 0,
+	});
+
+	// nobody comes back once they are eliminated
+	freezeout.eliminate(other);
+	assert!(!freezeout.can_enter(other, 1));
+    }
+}
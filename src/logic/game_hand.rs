@@ -2,10 +2,13 @@ use std::fmt;
 use std::collections::{HashMap, HashSet};
 
 use super::card::{Card, Rank};
-use super::hand_analysis::HandResult;
+use super::hand_analysis::{HandResult, LowHandResult};
 
 use super::player::{Player, PlayerConfig, PlayerAction};
-use super::pot::PotManager;
+use super::pot::{Pot, PotManager};
+use super::variant::Variant;
+use super::wild_cards::WildCards;
+use super::betting_structure::BettingStructure;
 
 use json::object;
 use uuid::Uuid;
@@ -13,6 +16,9 @@ use uuid::Uuid;
 use strum_macros::EnumIter;
 use strum::IntoEnumIterator;
 
+/// how many streets there are across all variants (used to size per-street arrays)
+pub const NUM_STREETS: usize = 14;
+
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, EnumIter)]
 pub enum Street {
     Preflop = 0,
@@ -20,6 +26,17 @@ pub enum Street {
     Turn = 2,
     River = 3,
     ShowDown = 4,
+    // the stud streets are named after how many cards each player has been dealt
+    Third = 5,
+    Fourth = 6,
+    Fifth = 7,
+    Sixth = 8,
+    Seventh = 9,
+    // the draw streets are the betting rounds of a draw game, named after the draw that comes before them
+    PreDraw = 10,
+    FirstDraw = 11,
+    SecondDraw = 12,
+    ThirdDraw = 13,
 }
 
 impl fmt::Display for Street {
@@ -31,25 +48,60 @@ impl fmt::Display for Street {
 	    Street::Turn => "turn".to_owned(),
 	    Street::River => "river".to_owned(),
 	    Street::ShowDown => "showdown".to_owned(),
+	    Street::Third => "third_street".to_owned(),
+	    Street::Fourth => "fourth_street".to_owned(),
+	    Street::Fifth => "fifth_street".to_owned(),
+	    Street::Sixth => "sixth_street".to_owned(),
+	    Street::Seventh => "seventh_street".to_owned(),
+	    Street::PreDraw => "predraw".to_owned(),
+	    Street::FirstDraw => "first_draw".to_owned(),
+	    Street::SecondDraw => "second_draw".to_owned(),
+	    Street::ThirdDraw => "third_draw".to_owned(),
 	};
         write!(f, "{}", output)
     }
 }
 
+impl Street {
+    /// in a draw game, the players draw new cards before the betting on these streets
+    pub fn follows_draw(&self) -> bool {
+	matches!(self, Street::FirstDraw | Street::SecondDraw | Street::ThirdDraw)
+    }
+}
+
+/// in a hi-lo game, the low half of a pot goes to the player(s) with the best qualifying low hand
+struct LowSplit {
+    winner_ids: HashSet<Uuid>,
+    amount: u32, // how much each low winner gets
+}
+
+/// who is paid out of one pot, and who needs to show their cards for it
+struct PotSettlement<'a> {
+    pot_idx: usize,
+    best_ids: HashSet<Uuid>, // the winners of the pot (or of its high half, in a hi-lo game)
+    best_hand: Option<&'a HandResult>,
+    amount: u32, // how much each winner gets
+    low_split: Option<LowSplit>,
+    showing_ids: HashSet<Uuid>, // who needs to show their cards
+    elligible_ids: HashSet<Uuid>, // who was in the pot (and gets a settlement)
+}
+
 pub enum HandStatus {
     KeepPlaying,
     NextStreet,
     HandOver,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GameHand {
+    pub variant: Variant,
+    pub betting_structure: BettingStructure,
     pub big_blind: u32,
     pub num_starting_players: usize,
     pub street: Street,
     pot_manager: PotManager,
     pub street_contributions: HashMap<Street, [u32; 9]>, // how much a player contributed to the pot during each street
-    pub street_num_bets: [u32; 5], // how many bets per street. each index is for a given street
+    pub street_num_bets: [u32; NUM_STREETS], // how many bets per street. each index is for a given street
     pub actions_per_street_per_player: HashMap<Street, HashMap<usize, Vec<PlayerAction>>>,
     pub last_action: Option<PlayerAction>, // the last thing anyone did (or None)	
     pub current_bet: u32, // the current street bet at any moment
@@ -58,12 +110,26 @@ pub struct GameHand {
     pub turn: Option<Card>,
     pub river: Option<Card>,
     pub index_to_act: Option<usize>,
+    pub small_blind_idx: Option<usize>, // the seat posting the small blind this hand, if any
+    pub big_blind_idx: Option<usize>, // the seat posting the big blind this hand, if any
+    pub straddle_idx: Option<usize>, // the player straddling this hand, if anyone
+    // any boards besides the flop/turn/river, e.g. the second board in double-board Hold'em,
+    // or the extra runouts when running it more than once
+    pub extra_boards: Vec<Vec<Card>>,
+    pub wild_cards: WildCards, // any jokers or wild ranks that this hand is played with
 }
 
 impl GameHand {
 
     /// a new() constructor when we know the blind and the starting players
+    /// (a Hold'em hand, which is useful for unit tests)
+    #[cfg(test)]
     pub fn new(big_blind: u32, players: &[Option<Player>]) -> Self {
+	GameHand::with_variant(Variant::Holdem, big_blind, players)
+    }
+
+    /// a constructor for a hand of a specific variant (e.g. Omaha)
+    pub fn with_variant(variant: Variant, big_blind: u32, players: &[Option<Player>]) -> Self {
 	let mut street_contributions = HashMap::new();
 	let mut actions_per_street_per_player = HashMap::new();	
 	for street in Street::iter() {
@@ -77,12 +143,14 @@ impl GameHand {
 	let num_starting_players = players.iter().flatten().filter(|p| p.money > 0).count();
 
         GameHand {
+	    variant,
+	    betting_structure: variant.default_betting_structure(),
 	    big_blind,
 	    num_starting_players,
-            street: Street::Preflop,
+            street: variant.first_street(),
             pot_manager: PotManager::new(),
             street_contributions,
-	    street_num_bets: [0;5],
+	    street_num_bets: [0; NUM_STREETS],
 	    actions_per_street_per_player,
 	    last_action: None,
 	    current_bet: 0,
@@ -91,6 +159,11 @@ impl GameHand {
             turn: None,
             river: None,
 	    index_to_act: None,
+	    small_blind_idx: None,
+	    big_blind_idx: None,
+	    straddle_idx: None,
+	    extra_boards: vec![vec![]; variant.num_boards() - 1],
+	    wild_cards: WildCards::default(),
         }
     }
 
@@ -100,15 +173,24 @@ impl GameHand {
 	    Street::Flop => Some(Street::Preflop),
 	    Street::Turn => Some(Street::Flop),
 	    Street::River => Some(Street::Turn),
-	    Street::ShowDown=> Some(Street::River),
+	    Street::ShowDown => Some(self.variant.last_street()),
+	    Street::Third => None,
+	    Street::Fourth => Some(Street::Third),
+	    Street::Fifth => Some(Street::Fourth),
+	    Street::Sixth => Some(Street::Fifth),
+	    Street::Seventh => Some(Street::Sixth),
+	    Street::PreDraw => None,
+	    Street::FirstDraw => Some(Street::PreDraw),
+	    Street::SecondDraw => Some(Street::FirstDraw),
+	    Street::ThirdDraw => Some(Street::SecondDraw),
 	}
     }
 
     pub fn enact_player_action(&mut self, index: usize, action: PlayerAction, players: &mut [Option<Player>; 9]) {
 	println!("action = {:?}", action);
-	self.last_action = Some(action);
+	self.last_action = Some(action.clone());
 
-	self.actions_per_street_per_player.get_mut(&self.street).unwrap().get_mut(&index).unwrap().push(action);
+	self.actions_per_street_per_player.get_mut(&self.street).unwrap().get_mut(&index).unwrap().push(action.clone());
 
 	println!("poo");
 	println!("{:?}", self.street_contributions);
@@ -118,11 +200,12 @@ impl GameHand {
 	// any meta actions, we are free to respond and mutate the player
 	// so we re-borrow it as mutable
 	let player = players[index].as_mut().unwrap();
-	player.last_action = Some(action);
+	player.last_action = Some(action.clone());
 	match action {
 	    PlayerAction::PostSmallBlind(amount) => {	
 		player.money -= amount;		    	    
-		self.current_bet = amount;
+		// a button straddle is posted before the small blind
+		self.current_bet = std::cmp::max(amount, self.current_bet);
 		self.contribute(index, player.id, amount, player.is_all_in(), false);
 	    }
 	    PlayerAction::PostBigBlind(amount) => {
@@ -135,6 +218,40 @@ impl GameHand {
 		// Just count the big blind as to not double count them.
 		self.contribute(index, player.id, amount, player.is_all_in(), true);
 	    }
+	    PlayerAction::PostStraddle(amount) => {
+		player.money -= amount;
+		self.current_bet = std::cmp::max(amount, self.current_bet);
+		// the straddle acts like a bigger big blind, so the next raise must be at least as big
+		self.min_raise = std::cmp::max(amount, self.min_raise);
+		self.contribute(index, player.id, amount, player.is_all_in(), true);
+	    }
+	    PlayerAction::PostAnte(amount) => {
+		// the ante is dead money, so it goes into the pot without counting
+		// towards the player's contribution to the street bet.
+		// Everyone antes the same amount, so it still counts towards what they can win,
+		// which gives the right side pots if the ante puts a player all-in
+		player.money -= amount;
+		self.pot_manager.contribute(player.id, amount, player.is_all_in());
+	    }
+	    PlayerAction::PostBigBlindAnte(amount) => {
+		// the big blind ante is paid on behalf of the whole table, so it is
+		// dead money that doesn't count towards what the big blind can win
+		player.money -= amount;
+		self.pot_manager.add_dead_money(amount);
+	    }
+	    PlayerAction::PostBombPot(amount) => {
+		// the bomb pot counts as the player's preflop contribution,
+		// but it is not a bet that anyone needs to call
+		player.money -= amount;
+		self.contribute(index, player.id, amount, player.is_all_in(), false);
+	    }
+	    PlayerAction::PostBringIn(amount) => {
+		player.money -= amount;
+		self.current_bet = amount;
+		// the next player can "complete" the bring-in up to a full bet
+		self.min_raise = self.big_blind.saturating_sub(amount).max(1);
+		self.contribute(index, player.id, amount, player.is_all_in(), false);
+	    }
 	    PlayerAction::Fold => {
 		player.deactivate();
 	    }
@@ -143,6 +260,16 @@ impl GameHand {
 	    }
 	    PlayerAction::Check => {
 
+	    }
+	    PlayerAction::RunIt(_) => {
+		// the vote is counted by the table, and it does not change the money in the pot
+	    }
+	    PlayerAction::Draw(_) => {
+		// the table swaps the cards, since it holds the deck.
+		// Drawing does not change the money in the pot
+	    }
+	    PlayerAction::Discard(_) => {
+		// the table takes the card away, and like drawing it does not change the pot
 	    }
 	    PlayerAction::Call => {
 		let difference = self.current_bet - player_cumulative;
@@ -157,8 +284,9 @@ impl GameHand {
 		    println!("the new bet did not meet the min raise amount, so there must be an all-in");
 		    true
 		} else {
-		    println!("setting new minumum raise to {raise_amount}");			
-		    self.min_raise = raise_amount;
+		    // (completing a stud bring-in is a smaller raise, but the next raise must still be a full bet)
+		    self.min_raise = std::cmp::max(raise_amount, self.big_blind);
+		    println!("setting new minumum raise to {}", self.min_raise);
 		    false
 		};
 		let difference = new_bet - player_cumulative;
@@ -168,7 +296,8 @@ impl GameHand {
 		    // just to make sure the code is doing what we think it is
 		    assert!(player.is_all_in());
 		}
-		self.contribute(index, player.id, difference, player.is_all_in(), false);
+		// an all-in that is too small to be a full raise does not count as another bet
+		self.contribute(index, player.id, difference, player.is_all_in(), !must_all_in);
 	    }
 	}
 	
@@ -189,8 +318,8 @@ impl GameHand {
 		if player.is_all_in() {
 		    num_all_in += 1;
 		} else {
-		    if let Some(PlayerAction::PostBigBlind(_)) = player.last_action {
-			// posting the big blind does not count as being "settled",
+		    if let Some(PlayerAction::PostBigBlind(_) | PlayerAction::PostStraddle(_)) = player.last_action {
+			// posting the big blind (or a straddle) does not count as being "settled",
 			// since they get a chance to raise again.
 			continue
 		    }
@@ -239,7 +368,23 @@ impl GameHand {
     pub fn is_showdown(&self) -> bool {
 	Street::ShowDown == self.street
     }
+
+    /// the index of the last pot that the player was in, which is where their last chips went
+    pub fn last_pot_index(&self, id: Uuid) -> Option<usize> {
+	self.pot_manager
+	    .iter()
+	    .enumerate()
+	    .filter(|(_, pot)| pot.money > 0 && pot.is_elligible(&id))
+	    .map(|(pot_idx, _)| pot_idx)
+	    .max()
+    }
     
+    /// was the pot put together by more than one player. A pot with only one player in it
+    /// (e.g. a bet that nobody could call) just goes back to them, rather than being won
+    pub fn is_contested(&self, pot_idx: usize) -> bool {
+	self.pot_manager.get(pot_idx).is_some_and(|pot| pot.num_contributors() > 1)
+    }
+
     pub fn is_preflop(&self) -> bool {
 	Street::Preflop == self.street
     }
@@ -248,7 +393,7 @@ impl GameHand {
     pub fn get_previous_street_last_action_for_index(&self, index: usize) -> Option<PlayerAction> {
 	let prev_street = self.get_previous_street();
 	if let Some(street) = prev_street {
-	    self.actions_per_street_per_player[&street][&index].last().copied()
+	    self.actions_per_street_per_player[&street][&index].last().cloned()
 	} else {
 	    None
 	}
@@ -262,7 +407,91 @@ impl GameHand {
     pub fn get_current_num_bets(&self)  -> u32 {
 	self.street_num_bets[self.street as usize]
     }
+
+    /// in fixed-limit, every bet and raise on this street is exactly this size.
+    /// The small bet (the big blind) is used on the early streets, and the big bet (twice as much) on the later ones
+    pub fn fixed_limit_bet_size(&self) -> u32 {
+	match self.street {
+	    Street::Preflop | Street::Flop | Street::Third | Street::Fourth | Street::PreDraw => {
+		self.big_blind
+	    }
+	    // in triple draw the bet doubles after the second draw, but five card draw only has the one
+	    Street::FirstDraw if self.variant.last_street() != Street::FirstDraw => {
+		self.big_blind
+	    }
+	    _ => 2 * self.big_blind,
+	}
+    }
+
+    /// in fixed-limit, the only bet allowed is one more bet on top of the current bet.
+    /// If the current bet is less than a full bet (e.g. a stud bring-in), the bet completes it instead
+    pub fn fixed_limit_bet(&self) -> u32 {
+	let bet_size = self.fixed_limit_bet_size();
+	if self.current_bet < bet_size {
+	    bet_size
+	} else {
+	    self.current_bet + bet_size
+	}
+    }
+
+    /// in fixed-limit, once there has been a bet and three raises, players can only call or fold
+    pub fn is_capped(&self) -> bool {
+	self.betting_structure.is_fixed_limit()
+	    && self.get_current_num_bets() >= BettingStructure::FIXED_LIMIT_MAX_BETS
+    }
+
+    /// the largest bet that the player at the given index can make in a pot-limit game.
+    /// This is the current bet, plus the size of the pot after the player calls.
+    /// Every bet goes straight into the pots, so the pots already include the trailing bets on this street
+    pub fn pot_limit_max_bet(&self, index: usize) -> u32 {
+	let call_amount = self.current_bet - self.get_current_contributions_for_index(index);
+	let pot_after_call = self.total_money() + call_amount;
+	self.current_bet + pot_after_call
+    }
+
+    /// all the community cards that have been dealt so far
+    pub fn board_cards(&self) -> Vec<Card> {
+	let mut board = Vec::with_capacity(5);
+	if let Some(flop) = &self.flop {
+	    board.extend(flop.iter());
+	}
+	board.extend(self.turn.iter());
+	board.extend(self.river.iter());
+	board
+    }
     
+    /// every board in the hand, starting with the flop, turn, and river
+    pub fn boards(&self) -> Vec<Vec<Card>> {
+	std::iter::once(self.board_cards()).chain(self.extra_boards.iter().cloned()).collect()
+    }
+
+    /// replace the flop, turn, and river with the given cards (which may be a partial board)
+    fn set_board(&mut self, board: &[Card]) {
+	self.flop = board.get(0..3).map(|flop| flop.to_vec());
+	self.turn = board.get(3).copied();
+	self.river = board.get(4).copied();
+    }
+
+    /// a copy of this hand that is only played on the given board,
+    /// e.g. to find the best hands on one board of a double-board hand
+    pub fn with_board(&self, board: &[Card]) -> GameHand {
+	let mut board_hand = self.clone();
+	board_hand.set_board(board);
+	board_hand.extra_boards.clear();
+	board_hand
+    }
+
+    /// analyze five cards with this variant's hand rankings,
+    /// resolving any wild cards into the best possible hand
+    pub fn analyze_hand(&self, five_cards: Vec<Card>) -> HandResult {
+	HandResult::analyze_hand_with_wilds(five_cards, self.variant.hand_ranking_rules(), &self.wild_cards.ranks)
+    }
+
+    /// analyze fewer than five cards (e.g. the up cards in stud), resolving any wild cards
+    pub fn analyze_partial_hand(&self, cards: Vec<Card>) -> HandResult {
+	HandResult::analyze_partial_hand_with_wilds(cards, &self.wild_cards.ranks)
+    }
+
     pub fn contribute(&mut self, index: usize, player_id: Uuid, amount: u32, all_in: bool, is_raise: bool) {
 	let current_contributions = self.street_contributions.get_mut(&self.street).unwrap();	
         current_contributions[index] += amount;
@@ -279,6 +508,8 @@ impl GameHand {
     /// Returns a list of settlements of the paid (or active at showdown) players.
     /// A settlement shows the payout and hole cards of winning players, OR possibly the hole cards
     /// of losing players (if they had to show in the final reveal order of cards - starting with most aggression)
+    /// If there is more than one board (e.g. double-board Hold'em, or running it more than once),
+    /// every pot is split evenly across the boards, and each settlement says which board it was for
     pub fn divvy_pots(
 	&self,
 	players: &mut [Option<Player>; 9],
@@ -286,12 +517,53 @@ impl GameHand {
 	starting_idx: usize
     )
     -> Vec<json::JsonValue> {
-        let hand_results: HashMap<Uuid, Option<HandResult>> = players
-            .iter()
-            .flatten()
-            .filter(|player| player_ids_to_configs.contains_key(&player.id)) // make sure still in the game
-            .map(|player| (player.id, player.determine_best_hand(self)))
-            .collect();
+	if self.extra_boards.is_empty() || !self.is_showdown() {
+	    return self.divvy_pots_for_board(players, player_ids_to_configs, starting_idx);
+	}
+	let boards = self.boards();
+	let mut settlements = vec![];
+	for (board_index, board) in boards.iter().enumerate() {
+	    let mut board_hand = self.with_board(board);
+	    board_hand.pot_manager.split_for_board(board_index, boards.len());
+	    let board_string: String = board.iter().map(|card| card.to_string()).collect();
+	    for mut settlement in board_hand.divvy_pots_for_board(players, player_ids_to_configs, starting_idx) {
+		settlement["board_index"] = board_index.into();
+		settlement["board"] = board_string.clone().into();
+		settlements.push(settlement);
+	    }
+	}
+	settlements
+    }
+
+    /// pay out the pots for a single board
+    fn divvy_pots_for_board(
+	&self,
+	players: &mut [Option<Player>; 9],
+	player_ids_to_configs: &HashMap::<Uuid, PlayerConfig>,
+	starting_idx: usize
+    )
+    -> Vec<json::JsonValue> {
+        let hand_results: HashMap<Uuid, Option<HandResult>> = if self.variant.is_lowball() {
+	    // only the low hands matter in a lowball game
+	    HashMap::new()
+	} else {
+	    players
+		.iter()
+		.flatten()
+		.filter(|player| player_ids_to_configs.contains_key(&player.id)) // make sure still in the game
+		.map(|player| (player.id, player.determine_best_hand(self)))
+		.collect()
+	};
+	let low_results: HashMap<Uuid, Option<LowHandResult>> = if self.variant.is_hi_lo() || self.variant.is_lowball() {
+	    players
+		.iter()
+		.flatten()
+		.filter(|player| player_ids_to_configs.contains_key(&player.id))
+		.map(|player| (player.id, player.determine_best_low_hand(self)))
+		.collect()
+	} else {
+	    HashMap::new()
+	};
 	
         let is_showdown = self.is_showdown();
         let mut settlements: Vec<json::JsonValue> = vec![];	
@@ -301,7 +573,24 @@ impl GameHand {
 	    // for each pot, we determine who should get paid out
 	    // a player can only get paid for a pot that they contributed to
 	    // so each pot has its own best_hand calculation
-            let (best_ids, best_hand, amount, showing_ids, elligible_ids) = if is_showdown {
+	    // In a hi-lo game, half of the pot goes to the best qualifying low hand (if there is one)
+	    let low_split = if is_showdown && self.variant.is_hi_lo() {
+		GameHand::find_low_split(pot, players, &low_results)
+	    } else {
+		None
+	    };
+	    let high_money = match low_split {
+		Some(_) => pot.get_money() - pot.get_money() / 2, // the odd chip goes to the high hand
+		None => pot.get_money(),
+	    };
+            let (best_ids, best_hand, amount, mut showing_ids, elligible_ids) = if is_showdown && self.variant.is_lowball() {
+		// in a lowball game (e.g. Razz), the best low hand wins the whole pot
+		let (best_ids, showing_ids, elligible_ids) =
+		    GameHand::find_lowball_winners(pot, players, &low_results, showdown_starting_idx);
+		let num_winners = std::cmp::max(best_ids.len(), 1);
+		let amount = (pot.get_money() as f64 / num_winners as f64) as u32;
+		(best_ids, None, amount, showing_ids, elligible_ids)
+	    } else if is_showdown {
 		// if we made it to show down, there are multiple players left, so we need to see who
 		// has the best hand.
 		println!("Multiple active players made it to showdown!");
@@ -338,7 +627,7 @@ impl GameHand {
 		}
 		// divy the pot to all the winners
 		let num_winners = best_ids.len();
-		let amount = (pot.get_money() as f64 / num_winners as f64) as u32;
+		let amount = (high_money as f64 / num_winners as f64) as u32;
 		(best_ids, best_hand, amount, showing_ids, elligible_ids)
             } else {
 		// the hand ended before Showdown, so we simple find the one active player remaining
@@ -355,30 +644,128 @@ impl GameHand {
 		let elligible_ids = best_ids.clone();		
 		(best_ids, best_hand, amount, showing_ids, elligible_ids)
             };
-	    self.settle_players(&mut settlements, players, player_ids_to_configs, &hand_results, pot_idx,
-				     best_ids, best_hand, amount, showing_ids, elligible_ids, showdown_starting_idx);
+	    if let Some(low_split) = &low_split {
+		// the low winners need to show their cards too
+		showing_ids.extend(low_split.winner_ids.iter());
+	    }
+	    let pot_settlement = PotSettlement {
+		pot_idx,
+		best_ids,
+		best_hand,
+		amount,
+		low_split,
+		showing_ids,
+		elligible_ids,
+	    };
+	    settlements.extend(self.settle_players(players, player_ids_to_configs, &hand_results, &low_results,
+						   pot_settlement, showdown_starting_idx));
 	    
 	}
 	settlements
     }
 
-    /// iterate through the players, and any with an id in best_ids gets their money increased by amount.
-    /// Moreover, construct a json settlement message for each one of these payouts,
-    /// and add it to the given settlements vec (if they need to show)
+    /// find which players that are elligible for the given pot have the best qualifying low hand.
+    /// They split the low half of the pot.
+    /// Returns None if nobody qualifies for the low, in which case the high hand wins the whole pot
+    fn find_low_split(
+	pot: &Pot,
+	players: &[Option<Player>; 9],
+	low_results: &HashMap<Uuid, Option<LowHandResult>>,
+    ) -> Option<LowSplit> {
+	let mut best_low: Option<&LowHandResult> = None;
+	let mut winner_ids = HashSet::<Uuid>::new();
+	for player in players.iter().flatten() {
+	    if !pot.is_elligible(&player.id) {
+		continue;
+	    }
+	    if let Some(Some(low_result)) = low_results.get(&player.id) {
+		match best_low {
+		    Some(best) if low_result < best => (),
+		    Some(best) if low_result == best => {
+			// another player also has the best low
+			winner_ids.insert(player.id);
+		    }
+		    _ => {
+			best_low = Some(low_result);
+			winner_ids.clear();
+			winner_ids.insert(player.id);
+		    }
+		}
+	    }
+	}
+	if winner_ids.is_empty() {
+	    println!("no qualifying low hand for this pot");
+	    return None;
+	}
+	let amount = pot.get_money() / 2 / winner_ids.len() as u32;
+	Some(LowSplit { winner_ids, amount })
+    }
+
+    /// in a lowball game, find which players that are elligible for the given pot have the best low hand.
+    /// Returns a tuple of the (winning ids, ids that need to show, elligible ids), where players
+    /// only need to show if their hand was the best so far in the showdown order
+    fn find_lowball_winners(
+	pot: &Pot,
+	players: &[Option<Player>; 9],
+	low_results: &HashMap<Uuid, Option<LowHandResult>>,
+	showdown_starting_idx: usize,
+    ) -> (HashSet<Uuid>, HashSet<Uuid>, HashSet<Uuid>) {
+	let mut best_ids = HashSet::<Uuid>::new();
+	let mut showing_ids = HashSet::<Uuid>::new();
+	let mut elligible_ids = HashSet::<Uuid>::new();
+	let mut best_low: Option<&LowHandResult> = None;
+	for i in (showdown_starting_idx..9).chain(0..showdown_starting_idx) {
+	    if let Some(player) = &players[i] {
+		if !pot.is_elligible(&player.id) {
+		    continue;
+		}
+		if let Some(Some(low_result)) = low_results.get(&player.id) {
+		    elligible_ids.insert(player.id);
+		    match best_low {
+			Some(best) if low_result < best => {
+			    println!("low hand worse for id {:?}", player.id);
+			    continue;
+			}
+			Some(best) if low_result == best => {
+			    println!("equally good low hand for id {:?}", player.id);
+			    best_ids.insert(player.id);
+			}
+			_ => {
+			    println!("new best low hand for id {:?}", player.id);
+			    best_low = Some(low_result);
+			    best_ids.clear();
+			    best_ids.insert(player.id);
+			}
+		    }
+		    showing_ids.insert(player.id);
+		}
+	    }
+	}
+	(best_ids, showing_ids, elligible_ids)
+    }
+
+    /// iterate through the players, and any with an id in the pot's best_ids gets their money increased by its amount.
+    /// Moreover, construct a json settlement message for each one of these payouts (with their cards if they need to show),
+    /// and return them
     fn settle_players(
 	&self, 
-	settlements: &mut Vec<json::JsonValue>,
 	players: &mut [Option<Player>; 9],
 	player_ids_to_configs: &HashMap::<Uuid, PlayerConfig>,
 	hand_results: &HashMap<Uuid, Option<HandResult>>,	
-	pot_idx: usize,
-        best_ids: HashSet<Uuid>,
-        best_hand: Option<&HandResult>,
-        amount: u32,
-	showing_ids: HashSet<Uuid>,
-	elligible_ids: HashSet<Uuid>,	
+	low_results: &HashMap<Uuid, Option<LowHandResult>>,
+	pot_settlement: PotSettlement,
 	showdown_starting_idx: usize,
-    ) {
+    ) -> Vec<json::JsonValue> {
+	let PotSettlement {
+	    pot_idx,
+	    best_ids,
+	    best_hand,
+	    amount,
+	    low_split,
+	    showing_ids,
+	    elligible_ids,
+	} = pot_settlement;
+	let mut settlements = vec![];
         let is_showdown = self.is_showdown();
         for i in (showdown_starting_idx..9).chain(0..showdown_starting_idx) {
 	    if let Some(player) = &mut players[i]  {
@@ -400,30 +787,55 @@ impl GameHand {
 		    pot_index: pot_idx,
 		};
 		
+		let mut payout = 0;
 		if best_ids.contains(&player.id) {
-		    message["winner"] = true.into();		    
-		    message["payout"] = amount.into();
 		    println!(
 			"paying out {:?} to {:?}, with hand result = {:?}",
 			amount, player.id, best_hand
 		    );
-		    player.pay(amount);		    
+		    payout += amount;
+		}
+		if is_showdown && self.variant.is_hi_lo() {
+		    // report the high and low halves of the pot separately
+		    let high_winner = best_ids.contains(&player.id);
+		    message["high_winner"] = high_winner.into();
+		    if high_winner {
+			message["high_payout"] = amount.into();
+		    }
+		    let low_winner = matches!(&low_split, Some(low) if low.winner_ids.contains(&player.id));
+		    message["low_winner"] = low_winner.into();
+		    if let (true, Some(low)) = (low_winner, &low_split) {
+			println!("paying out low half {:?} to {:?}", low.amount, player.id);
+			message["low_payout"] = low.amount.into();
+			payout += low.amount;
+		    }
+		}
+		if payout > 0 {
+		    message["winner"] = true.into();
+		    message["payout"] = payout.into();
+		    player.pay(payout);
 		} else {
 		    message["winner"] = false.into();
 		}
 		if is_showdown && showing_ids.contains(&player.id) {		    
-		    let hole_string = format!("{}{}", player.hole_cards[0], player.hole_cards[1]);
-		    message["hole_cards"] = hole_string.into();
-		    if let Some(hand_result) = hand_results.get(&player.id).unwrap() {
+		    message["hole_cards"] = player.hole_cards_string().into();
+		    if !player.up_cards.is_empty() {
+			message["up_cards"] = player.up_cards_string().into();
+		    }
+		    if let Some(Some(hand_result)) = hand_results.get(&player.id) {
 			message["hand_result"] = hand_result.hand_ranking_string().into();			
 			message["constituent_cards"] = hand_result.constituent_cards_string().into();
 			message["kickers"] = hand_result.kickers_string().into();
 		    }
+		    if let Some(Some(low_result)) = low_results.get(&player.id) {
+			message["low_hand"] = low_result.cards_string().into();
+		    }
 		    
 		}
 		settlements.push(message);
             }
         }
+	settlements
     }
 
     pub fn highest_rank(&self) -> Option<Rank> {
@@ -461,3 +873,479 @@ impl GameHand {
 }
 
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::card::Suit;
+
+    /// sets up an Omaha Hi-Lo hand at showdown where each player has put 100 into the pot
+    fn set_up_hi_lo_showdown(
+	hole_cards: Vec<Vec<Card>>,
+	board: Vec<Card>,
+    ) -> ([Option<Player>; 9], HashMap<Uuid, PlayerConfig>, GameHand) {
+	let mut players: [Option<Player>; 9] = Default::default();
+	let mut ids_to_configs = HashMap::new();
+	for (i, cards) in hole_cards.into_iter().enumerate() {
+	    let mut player = Player::new_bot(900);
+	    player.is_active = true;
+	    player.index = Some(i);
+	    player.hole_cards = cards;
+	    ids_to_configs.insert(player.id, PlayerConfig::new(player.id, Some(format!("Bot {i}")), None));
+	    players[i] = Some(player);
+	}
+	let mut gamehand = GameHand::with_variant(Variant::OmahaHiLo, 2, &players);
+	for (i, player) in players.iter().enumerate() {
+	    if let Some(player) = player {
+		gamehand.contribute(i, player.id, 100, false, false);
+	    }
+	}
+	gamehand.flop = Some(board[0..3].to_vec());
+	gamehand.turn = Some(board[3]);
+	gamehand.river = Some(board[4]);
+	gamehand.street = Street::ShowDown;
+	(players, ids_to_configs, gamehand)
+    }
+
+    fn low_board() -> Vec<Card> {
+	vec![
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	    Card { rank: Rank::Four, suit: Suit::Diamond },
+	    Card { rank: Rank::Seven, suit: Suit::Club },
+	    Card { rank: Rank::King, suit: Suit::Heart },
+	    Card { rank: Rank::Queen, suit: Suit::Heart },
+	]
+    }
+
+    fn kings() -> Vec<Card> {
+	vec![
+	    Card { rank: Rank::King, suit: Suit::Spade },
+	    Card { rank: Rank::King, suit: Suit::Diamond },
+	    Card { rank: Rank::Nine, suit: Suit::Club },
+	    Card { rank: Rank::Nine, suit: Suit::Diamond },
+	]
+    }
+
+    fn ace_three_jacks() -> Vec<Card> {
+	vec![
+	    Card { rank: Rank::Ace, suit: Suit::Club },
+	    Card { rank: Rank::Three, suit: Suit::Club },
+	    Card { rank: Rank::Jack, suit: Suit::Diamond },
+	    Card { rank: Rank::Jack, suit: Suit::Spade },
+	]
+    }
+
+    fn eight_six_tens() -> Vec<Card> {
+	vec![
+	    Card { rank: Rank::Eight, suit: Suit::Club },
+	    Card { rank: Rank::Six, suit: Suit::Diamond },
+	    Card { rank: Rank::Ten, suit: Suit::Club },
+	    Card { rank: Rank::Ten, suit: Suit::Diamond },
+	]
+    }
+
+    /// the set of Kings wins the high half, and A-3 wins the low half
+    #[test]
+    fn hi_lo_split_pot() {
+	let (mut players, configs, gamehand) = set_up_hi_lo_showdown(
+	    vec![kings(), ace_three_jacks(), eight_six_tens()],
+	    low_board(),
+	);
+	let settlements = gamehand.divvy_pots(&mut players, &configs, 0);
+	assert_eq!(players[0].as_ref().unwrap().money, 1050);
+	assert_eq!(players[1].as_ref().unwrap().money, 1050);
+	assert_eq!(players[2].as_ref().unwrap().money, 900);
+
+	let kings_settlement = settlements.iter().find(|s| s["index"] == 0).unwrap();
+	assert_eq!(kings_settlement["high_winner"], true);
+	assert_eq!(kings_settlement["low_winner"], false);
+	let low_settlement = settlements.iter().find(|s| s["index"] == 1).unwrap();
+	assert_eq!(low_settlement["high_winner"], false);
+	assert_eq!(low_settlement["low_winner"], true);
+	assert_eq!(low_settlement["low_payout"], 150);
+	assert_eq!(low_settlement["low_hand"], "7c-4d-3c-2s-Ac");
+    }
+
+    /// trip twos with A-3 wins both the high and the low
+    #[test]
+    fn hi_lo_scoop() {
+	let ace_three_twos = vec![
+	    Card { rank: Rank::Ace, suit: Suit::Club },
+	    Card { rank: Rank::Three, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Diamond },
+	];
+	let (mut players, configs, gamehand) = set_up_hi_lo_showdown(
+	    vec![ace_three_twos, eight_six_tens()],
+	    low_board(),
+	);
+	gamehand.divvy_pots(&mut players, &configs, 0);
+	assert_eq!(players[0].as_ref().unwrap().money, 1100);
+	assert_eq!(players[1].as_ref().unwrap().money, 900);
+    }
+
+    /// two players have the same A-3 low, so they each get a quarter of the pot
+    #[test]
+    fn hi_lo_quartered() {
+	let ace_three_nines = vec![
+	    Card { rank: Rank::Ace, suit: Suit::Diamond },
+	    Card { rank: Rank::Three, suit: Suit::Diamond },
+	    Card { rank: Rank::Nine, suit: Suit::Spade },
+	    Card { rank: Rank::Nine, suit: Suit::Heart },
+	];
+	let (mut players, configs, gamehand) = set_up_hi_lo_showdown(
+	    vec![kings(), ace_three_jacks(), ace_three_nines],
+	    low_board(),
+	);
+	gamehand.divvy_pots(&mut players, &configs, 0);
+	assert_eq!(players[0].as_ref().unwrap().money, 1050);
+	assert_eq!(players[1].as_ref().unwrap().money, 975);
+	assert_eq!(players[2].as_ref().unwrap().money, 975);
+    }
+
+    /// with only one low card on the board, nobody can make a low, so the high hand takes it all
+    #[test]
+    fn hi_lo_no_qualifying_low() {
+	let high_board = vec![
+	    Card { rank: Rank::King, suit: Suit::Heart },
+	    Card { rank: Rank::Queen, suit: Suit::Heart },
+	    Card { rank: Rank::Jack, suit: Suit::Club },
+	    Card { rank: Rank::Nine, suit: Suit::Spade },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	];
+	let (mut players, configs, gamehand) = set_up_hi_lo_showdown(
+	    vec![kings(), ace_three_jacks()],
+	    high_board,
+	);
+	let settlements = gamehand.divvy_pots(&mut players, &configs, 0);
+	assert_eq!(players[0].as_ref().unwrap().money, 1100);
+	assert_eq!(players[1].as_ref().unwrap().money, 900);
+	assert!(settlements.iter().all(|s| s["low_winner"] == false));
+    }
+
+    /// in Razz the best ace-to-five low wins the whole pot, even though the
+    /// other player has a higher hand (a straight)
+    #[test]
+    fn razz_low_hand_wins_pot() {
+	let mut players: [Option<Player>; 9] = Default::default();
+	let mut ids_to_configs = HashMap::new();
+	let seven_cards = [
+	    vec![
+		Card { rank: Rank::Ace, suit: Suit::Club },
+		Card { rank: Rank::Two, suit: Suit::Club },
+		Card { rank: Rank::King, suit: Suit::Club },
+		Card { rank: Rank::Four, suit: Suit::Heart },
+		Card { rank: Rank::Six, suit: Suit::Diamond },
+		Card { rank: Rank::Seven, suit: Suit::Spade },
+		Card { rank: Rank::King, suit: Suit::Diamond },
+	    ],
+	    vec![
+		Card { rank: Rank::Three, suit: Suit::Spade },
+		Card { rank: Rank::Four, suit: Suit::Spade },
+		Card { rank: Rank::Five, suit: Suit::Club },
+		Card { rank: Rank::Six, suit: Suit::Heart },
+		Card { rank: Rank::Seven, suit: Suit::Club },
+		Card { rank: Rank::Seven, suit: Suit::Diamond },
+		Card { rank: Rank::Queen, suit: Suit::Heart },
+	    ],
+	];
+	for (i, cards) in seven_cards.iter().enumerate() {
+	    let mut player = Player::new_bot(900);
+	    player.is_active = true;
+	    player.index = Some(i);
+	    player.hole_cards = vec![cards[0], cards[1], cards[6]];
+	    player.up_cards = cards[2..6].to_vec();
+	    ids_to_configs.insert(player.id, PlayerConfig::new(player.id, Some(format!("Bot {i}")), None));
+	    players[i] = Some(player);
+	}
+	let mut gamehand = GameHand::with_variant(Variant::Razz, 8, &players);
+	for (i, player) in players.iter().enumerate() {
+	    if let Some(player) = player {
+		gamehand.contribute(i, player.id, 100, false, false);
+	    }
+	}
+	gamehand.street = Street::ShowDown;
+	let settlements = gamehand.divvy_pots(&mut players, &ids_to_configs, 0);
+	// 7-6-4-2-A beats 7-6-5-4-3
+	assert_eq!(players[0].as_ref().unwrap().money, 1100);
+	assert_eq!(players[1].as_ref().unwrap().money, 900);
+	let winner = settlements.iter().find(|s| s["index"] == 0).unwrap();
+	assert_eq!(winner["low_hand"], "7s-6d-4h-2c-Ac");
+	assert!(winner["hand_result"].is_null());
+    }
+
+    /// in stud hi-lo the straight wins the high half, and the better eight-or-better low wins the low half
+    #[test]
+    fn stud_hi_lo_split_pot() {
+	let mut players: [Option<Player>; 9] = Default::default();
+	let mut ids_to_configs = HashMap::new();
+	let seven_cards = [
+	    vec![
+		Card { rank: Rank::Ace, suit: Suit::Club },
+		Card { rank: Rank::Two, suit: Suit::Club },
+		Card { rank: Rank::King, suit: Suit::Club },
+		Card { rank: Rank::Four, suit: Suit::Heart },
+		Card { rank: Rank::Six, suit: Suit::Diamond },
+		Card { rank: Rank::Seven, suit: Suit::Spade },
+		Card { rank: Rank::King, suit: Suit::Diamond },
+	    ],
+	    vec![
+		Card { rank: Rank::Three, suit: Suit::Spade },
+		Card { rank: Rank::Four, suit: Suit::Spade },
+		Card { rank: Rank::Five, suit: Suit::Club },
+		Card { rank: Rank::Six, suit: Suit::Heart },
+		Card { rank: Rank::Seven, suit: Suit::Club },
+		Card { rank: Rank::Seven, suit: Suit::Diamond },
+		Card { rank: Rank::Queen, suit: Suit::Heart },
+	    ],
+	];
+	for (i, cards) in seven_cards.iter().enumerate() {
+	    let mut player = Player::new_bot(900);
+	    player.is_active = true;
+	    player.index = Some(i);
+	    player.hole_cards = vec![cards[0], cards[1], cards[6]];
+	    player.up_cards = cards[2..6].to_vec();
+	    ids_to_configs.insert(player.id, PlayerConfig::new(player.id, Some(format!("Bot {i}")), None));
+	    players[i] = Some(player);
+	}
+	let mut gamehand = GameHand::with_variant(Variant::SevenCardStudHiLo, 8, &players);
+	for (i, player) in players.iter().enumerate() {
+	    if let Some(player) = player {
+		gamehand.contribute(i, player.id, 100, false, false);
+	    }
+	}
+	gamehand.street = Street::ShowDown;
+	gamehand.divvy_pots(&mut players, &ids_to_configs, 0);
+	// 7-6-4-2-A wins the low, and the seven-high straight wins the high
+	assert_eq!(players[0].as_ref().unwrap().money, 1000);
+	assert_eq!(players[1].as_ref().unwrap().money, 1000);
+    }
+
+    /// in five card draw there is no board, so the best five hole cards win
+    #[test]
+    fn five_card_draw_showdown() {
+	let mut players: [Option<Player>; 9] = Default::default();
+	let mut ids_to_configs = HashMap::new();
+	let hole_cards = [
+	    // two pair
+	    vec![
+		Card { rank: Rank::Jack, suit: Suit::Club },
+		Card { rank: Rank::Jack, suit: Suit::Diamond },
+		Card { rank: Rank::Four, suit: Suit::Heart },
+		Card { rank: Rank::Four, suit: Suit::Spade },
+		Card { rank: Rank::Ace, suit: Suit::Diamond },
+	    ],
+	    // three of a kind
+	    vec![
+		Card { rank: Rank::Three, suit: Suit::Spade },
+		Card { rank: Rank::Three, suit: Suit::Club },
+		Card { rank: Rank::Three, suit: Suit::Heart },
+		Card { rank: Rank::King, suit: Suit::Heart },
+		Card { rank: Rank::Nine, suit: Suit::Club },
+	    ],
+	];
+	for (i, cards) in hole_cards.iter().enumerate() {
+	    let mut player = Player::new_bot(900);
+	    player.is_active = true;
+	    player.index = Some(i);
+	    player.hole_cards = cards.clone();
+	    ids_to_configs.insert(player.id, PlayerConfig::new(player.id, Some(format!("Bot {i}")), None));
+	    players[i] = Some(player);
+	}
+	let mut gamehand = GameHand::with_variant(Variant::FiveCardDraw, 8, &players);
+	for (i, player) in players.iter().enumerate() {
+	    if let Some(player) = player {
+		gamehand.contribute(i, player.id, 100, false, false);
+	    }
+	}
+	gamehand.street = Street::ShowDown;
+	// the hand is over after the one draw
+	assert_eq!(gamehand.get_previous_street(), Some(Street::FirstDraw));
+	gamehand.divvy_pots(&mut players, &ids_to_configs, 0);
+	assert_eq!(players[0].as_ref().unwrap().money, 900);
+	assert_eq!(players[1].as_ref().unwrap().money, 1100);
+    }
+
+    /// when the board is run twice, each runout is played for half of the pot
+    #[test]
+    fn run_it_twice_splits_pot() {
+	let mut players: [Option<Player>; 9] = Default::default();
+	let mut ids_to_configs = HashMap::new();
+	let hole_cards = [
+	    vec![Card { rank: Rank::Ace, suit: Suit::Club }, Card { rank: Rank::Ace, suit: Suit::Diamond }],
+	    vec![Card { rank: Rank::King, suit: Suit::Club }, Card { rank: Rank::King, suit: Suit::Diamond }],
+	];
+	for (i, cards) in hole_cards.iter().enumerate() {
+	    let mut player = Player::new_bot(0);
+	    player.is_active = true;
+	    player.hole_cards = cards.clone();
+	    ids_to_configs.insert(player.id, PlayerConfig::new(player.id, Some(format!("Bot {i}")), None));
+	    players[i] = Some(player);
+	}
+	let mut gamehand = GameHand::new(8, &players);
+	for (i, player) in players.iter().enumerate() {
+	    if let Some(player) = player {
+		gamehand.contribute(i, player.id, 101, true, false);
+	    }
+	}
+	// the first runout is a blank board, and the second gives the kings a set
+	gamehand.set_board(&[
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	    Card { rank: Rank::Seven, suit: Suit::Spade },
+	    Card { rank: Rank::Nine, suit: Suit::Heart },
+	    Card { rank: Rank::Jack, suit: Suit::Spade },
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	]);
+	gamehand.extra_boards = vec![vec![
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	    Card { rank: Rank::Seven, suit: Suit::Spade },
+	    Card { rank: Rank::Nine, suit: Suit::Heart },
+	    Card { rank: Rank::King, suit: Suit::Heart },
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	]];
+	gamehand.street = Street::ShowDown;
+	let settlements = gamehand.divvy_pots(&mut players, &ids_to_configs, 0);
+	// the aces win the first runout, and the kings win the second
+	assert_eq!(players[0].as_ref().unwrap().money, 101);
+	assert_eq!(players[1].as_ref().unwrap().money, 101);
+	let second_winner = settlements.iter().find(|s| s["board_index"] == 1 && s["winner"] == true).unwrap();
+	assert_eq!(second_winner["index"], 1);
+	assert_eq!(second_winner["board"], "2h7s9hKh4c");
+    }
+
+    /// in double-board Hold'em, half of the pot goes to the best hand on each board
+    #[test]
+    fn double_board_splits_pot() {
+	let mut players: [Option<Player>; 9] = Default::default();
+	let mut ids_to_configs = HashMap::new();
+	let hole_cards = [
+	    vec![Card { rank: Rank::Ace, suit: Suit::Club }, Card { rank: Rank::Ace, suit: Suit::Diamond }],
+	    vec![Card { rank: Rank::King, suit: Suit::Club }, Card { rank: Rank::King, suit: Suit::Diamond }],
+	];
+	for (i, cards) in hole_cards.iter().enumerate() {
+	    let mut player = Player::new_bot(0);
+	    player.is_active = true;
+	    player.hole_cards = cards.clone();
+	    ids_to_configs.insert(player.id, PlayerConfig::new(player.id, Some(format!("Bot {i}")), None));
+	    players[i] = Some(player);
+	}
+	let mut gamehand = GameHand::with_variant(Variant::DoubleBoardHoldem, 8, &players);
+	assert_eq!(gamehand.extra_boards.len(), 1);
+	for (i, player) in players.iter().enumerate() {
+	    if let Some(player) = player {
+		gamehand.contribute(i, player.id, 100, true, false);
+	    }
+	}
+	// the aces win the first board, and the second board is a royal flush that everyone plays
+	gamehand.set_board(&[
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	    Card { rank: Rank::Seven, suit: Suit::Spade },
+	    Card { rank: Rank::Nine, suit: Suit::Heart },
+	    Card { rank: Rank::Jack, suit: Suit::Spade },
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	]);
+	gamehand.extra_boards[0] = vec![
+	    Card { rank: Rank::Ten, suit: Suit::Spade },
+	    Card { rank: Rank::Jack, suit: Suit::Spade },
+	    Card { rank: Rank::Queen, suit: Suit::Spade },
+	    Card { rank: Rank::King, suit: Suit::Spade },
+	    Card { rank: Rank::Ace, suit: Suit::Spade },
+	];
+	gamehand.street = Street::ShowDown;
+	let settlements = gamehand.divvy_pots(&mut players, &ids_to_configs, 0);
+	assert_eq!(players[0].as_ref().unwrap().money, 150);
+	assert_eq!(players[1].as_ref().unwrap().money, 50);
+	assert!(settlements.iter().all(|s| s["board_index"] == 0 || s["board"] == "TsJsQsKsAs"));
+    }
+
+    /// in fixed-limit, the bets are a small bet on the early streets and a big bet on the later ones,
+    /// and the betting is capped after a bet and three raises
+    #[test]
+    fn fixed_limit_bet_sizes_and_cap() {
+	let mut players: [Option<Player>; 9] = Default::default();
+	for spot in players.iter_mut().take(2) {
+	    let mut player = Player::new_bot(1000);
+	    player.is_active = true;
+	    *spot = Some(player);
+	}
+	let mut gamehand = GameHand::new(8, &players);
+	gamehand.betting_structure = BettingStructure::FixedLimit;
+	gamehand.enact_player_action(0, PlayerAction::PostSmallBlind(4), &mut players);
+	gamehand.enact_player_action(1, PlayerAction::PostBigBlind(8), &mut players);
+	assert_eq!(gamehand.fixed_limit_bet(), 16);
+	gamehand.enact_player_action(0, PlayerAction::Bet(16), &mut players);
+	gamehand.enact_player_action(1, PlayerAction::Bet(24), &mut players);
+	assert!(!gamehand.is_capped());
+	gamehand.enact_player_action(0, PlayerAction::Bet(32), &mut players);
+	// the big blind, plus three raises
+	assert!(gamehand.is_capped());
+
+	gamehand.street = Street::Turn;
+	gamehand.current_bet = 0;
+	assert_eq!(gamehand.fixed_limit_bet(), 16);
+	assert!(!gamehand.is_capped());
+    }
+
+    /// in pot-limit, the most a player can bet is the current bet plus the pot after they call,
+    /// which includes the bets made on this street. Any variant can be played pot-limit
+    #[test]
+    fn pot_limit_max_bet_with_trailing_bets() {
+	let mut players: [Option<Player>; 9] = Default::default();
+	for spot in players.iter_mut().take(2) {
+	    let mut player = Player::new_bot(1000);
+	    player.is_active = true;
+	    *spot = Some(player);
+	}
+	let mut gamehand = GameHand::new(8, &players);
+	assert_eq!(gamehand.betting_structure.max_bet(&gamehand, 0), None);
+	gamehand.betting_structure = BettingStructure::PotLimit;
+	gamehand.enact_player_action(0, PlayerAction::PostSmallBlind(4), &mut players);
+	gamehand.enact_player_action(1, PlayerAction::PostBigBlind(8), &mut players);
+	// the small blind can bet 8, plus a pot of 12, plus their call of 4
+	assert_eq!(gamehand.betting_structure.max_bet(&gamehand, 0), Some(24));
+	gamehand.enact_player_action(0, PlayerAction::Bet(24), &mut players);
+	// the big blind can bet 24, plus a pot of 32, plus their call of 16
+	assert_eq!(gamehand.betting_structure.max_bet(&gamehand, 1), Some(72));
+    }
+
+    /// an ante that puts a player all-in still makes them elligible for the main pot only,
+    /// and the antes don't count towards the current bet
+    #[test]
+    fn ante_all_in_makes_side_pot() {
+	let mut players: [Option<Player>; 9] = Default::default();
+	for (i, spot) in players.iter_mut().take(3).enumerate() {
+	    let mut player = Player::new_bot(if i == 0 { 1 } else { 1000 });
+	    player.is_active = true;
+	    *spot = Some(player);
+	}
+	let mut gamehand = GameHand::new(8, &players);
+	for i in 0..3 {
+	    gamehand.enact_player_action(i, PlayerAction::PostAnte(1), &mut players);
+	}
+	assert_eq!(gamehand.current_bet, 0);
+	assert!(players[0].as_ref().unwrap().is_all_in());
+	gamehand.enact_player_action(1, PlayerAction::Bet(10), &mut players);
+	gamehand.enact_player_action(2, PlayerAction::Call, &mut players);
+	assert_eq!(gamehand.pot_repr(), vec![3, 20]);
+    }
+
+    /// the big blind ante is dead money in the main pot, so it doesn't make
+    /// the big blind's side pot any bigger when the small blind is all-in
+    #[test]
+    fn big_blind_ante_is_dead_money() {
+	let mut players: [Option<Player>; 9] = Default::default();
+	for (i, spot) in players.iter_mut().take(2).enumerate() {
+	    let mut player = Player::new_bot(if i == 0 { 5 } else { 1000 });
+	    player.is_active = true;
+	    *spot = Some(player);
+	}
+	let mut gamehand = GameHand::new(8, &players);
+	gamehand.enact_player_action(1, PlayerAction::PostBigBlindAnte(10), &mut players);
+	gamehand.enact_player_action(0, PlayerAction::PostSmallBlind(4), &mut players);
+	gamehand.enact_player_action(1, PlayerAction::PostBigBlind(8), &mut players);
+	assert_eq!(gamehand.current_bet, 8);
+	gamehand.enact_player_action(0, PlayerAction::Call, &mut players);
+	assert!(players[0].as_ref().unwrap().is_all_in());
+	assert_eq!(gamehand.pot_repr(), vec![20, 3]);
+	assert_eq!(gamehand.total_money(), 23);
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::{HashMap, HashSet};
 
 use super::card::{Card, Rank, Suit};
 
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 #[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone, EnumIter)]
@@ -17,8 +18,44 @@ pub enum HandRanking {
     FourOfAKind = 8,
     StraightFlush = 9,
     RoyalFlush = 10,
+    FiveOfAKind = 11, // only possible with wild cards
 }
 
+/// The rules used to rank a five card hand.
+/// In short-deck (6+) Hold'em the deck has no 2s through 5s, so a flush is harder to make
+/// and beats a full house, and the Ace plays low in A-6-7-8-9 rather than A-2-3-4-5.
+/// In deuce-to-seven lowball the Ace is always high, so A-2-3-4-5 is not a straight
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum HandRankingRules {
+    Standard,
+    ShortDeck,
+    DeuceToSeven,
+}
+
+impl HandRankingRules {
+    /// the lowest rank in the deck, which an Ace can play below to complete the lowest straight
+    pub fn lowest_rank(&self) -> Rank {
+	match self {
+	    HandRankingRules::Standard | HandRankingRules::DeuceToSeven => Rank::Two,
+	    HandRankingRules::ShortDeck => Rank::Six,
+	}
+    }
+
+    /// whether an Ace can play below the lowest rank to make a straight
+    pub fn ace_plays_low(&self) -> bool {
+	!matches!(self, HandRankingRules::DeuceToSeven)
+    }
+
+    /// the numerical strength of a hand ranking under these rules,
+    /// which is used as the most significant part of a hand's value
+    pub fn ranking_value(&self, hand_ranking: HandRanking) -> u32 {
+	match (self, hand_ranking) {
+	    (HandRankingRules::ShortDeck, HandRanking::Flush) => HandRanking::FullHouse as u32,
+	    (HandRankingRules::ShortDeck, HandRanking::FullHouse) => HandRanking::Flush as u32,
+	    _ => hand_ranking as u32,
+	}
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum DrawType {
@@ -140,8 +177,9 @@ impl HandResult {
         hand_ranking: HandRanking,
         constituent_cards: &Vec<Card>,
         kickers: &[Card],
+	rules: HandRankingRules,
     ) -> u32 {
-        let mut value = hand_ranking as u32;
+        let mut value = rules.ranking_value(hand_ranking);
         value <<= 20; // shift it into the most significant area we need
 
         // add the values of the constituent cards and then kickers
@@ -173,8 +211,74 @@ impl HandResult {
 
     /// Given a hand of 5 cards, we return a HandResult, which tells
     /// us the hand ranking, the constituent cards, kickers, and hand score    
-    pub fn analyze_hand(mut five_cards: Vec<Card>) -> Self {
+    #[cfg(test)]
+    pub fn analyze_hand(five_cards: Vec<Card>) -> Self {
+	HandResult::analyze_hand_with_rules(five_cards, HandRankingRules::Standard)
+    }
+
+    /// Same as analyze_hand, but the hand is ranked according to the given rules,
+    /// e.g. a flush beats a full house with the short-deck rules.
+    /// Any jokers are wild, and are resolved into the best possible hand
+    pub fn analyze_hand_with_rules(five_cards: Vec<Card>, rules: HandRankingRules) -> Self {
+	HandResult::analyze_hand_with_wilds(five_cards, rules, &[])
+    }
+
+    /// Same as analyze_hand_with_rules, but every card of the given wild ranks (along with any joker)
+    /// can stand for any card. The wild cards are resolved into whichever cards make the best hand,
+    /// so five of a kind is possible, which beats even a royal flush
+    pub fn analyze_hand_with_wilds(five_cards: Vec<Card>, rules: HandRankingRules, wild_ranks: &[Rank]) -> Self {
         assert!(five_cards.len() == 5);
+	let (wilds, naturals): (Vec<Card>, Vec<Card>) = five_cards
+	    .into_iter()
+	    .partition(|card| card.is_wild(wild_ranks));
+	if wilds.is_empty() {
+	    return HandResult::analyze_natural_hand(naturals, rules);
+	}
+	if naturals.is_empty() {
+	    // nothing beats five Aces
+	    return HandResult::analyze_natural_hand(vec![Card { rank: Rank::Ace, suit: Suit::Spade }; 5], rules);
+	}
+	// a wild card only needs to try each rank in the suit of a possible flush,
+	// and in one other suit, since the rest of the suits would make the same hands
+	let flush_suit = naturals[0].suit;
+	let other_suit = Suit::iter().find(|suit| *suit != flush_suit).unwrap();
+	let mut suits = vec![other_suit];
+	if naturals.iter().all(|card| card.suit == flush_suit) {
+	    suits.push(flush_suit);
+	}
+	let candidates: Vec<Card> = Rank::iter()
+	    .filter(|rank| *rank >= rules.lowest_rank())
+	    .flat_map(|rank| suits.iter().map(move |suit| Card { rank, suit: *suit }))
+	    .collect();
+	HandResult::best_with_wilds(naturals, &candidates, wilds.len(), &|cards| HandResult::analyze_natural_hand(cards, rules))
+    }
+
+    /// try every way of replacing the wild cards with the candidate cards, and return the best hand.
+    /// The order of the wild cards doesn't matter, so each wild card only tries
+    /// the candidates from where the previous wild card left off
+    fn best_with_wilds(
+	naturals: Vec<Card>,
+	candidates: &[Card],
+	num_wilds: usize,
+	analyze: &dyn Fn(Vec<Card>) -> HandResult,
+    ) -> HandResult {
+	if num_wilds == 0 {
+	    return analyze(naturals);
+	}
+	candidates
+	    .iter()
+	    .enumerate()
+	    .map(|(i, candidate)| {
+		let mut cards = naturals.clone();
+		cards.push(*candidate);
+		HandResult::best_with_wilds(cards, &candidates[i..], num_wilds - 1, analyze)
+	    })
+	    .max()
+	    .unwrap()
+    }
+
+    /// analyze a hand of five cards without any wild cards
+    fn analyze_natural_hand(mut five_cards: Vec<Card>, rules: HandRankingRules) -> Self {
         five_cards.sort(); // first sort by Rank
 
         let hand_ranking: HandRanking;
@@ -191,8 +295,9 @@ impl HandResult {
             if card.suit != first_suit {
                 is_flush = false;
             }
-            if is_straight && i == 4 && card.rank == Rank::Ace && first_rank == 2 {
-                // completing the straight with an Ace on 2-->Ace
+            if is_straight && i == 4 && card.rank == Rank::Ace && rules.ace_plays_low()
+                && first_rank == rules.lowest_rank() as usize {
+                // completing the straight with an Ace on 2-->Ace (or 6-->Ace in short-deck)
                 is_low_ace_straight = true;
             } else if card.rank as usize != first_rank + i {
                 is_straight = false;
@@ -203,19 +308,21 @@ impl HandResult {
         let mut kickers = Vec::new();
 
         if is_flush && is_straight {
-            if let Rank::Ace = five_cards[4].rank {
+            if five_cards[4].rank == Rank::Ace && !is_low_ace_straight {
                 hand_ranking = HandRanking::RoyalFlush;
             } else {
                 hand_ranking = HandRanking::StraightFlush;
             }
             constituent_cards.extend(five_cards);
         } else {
+            let mut num_fives = 0;
             let mut num_fours = 0;
             let mut num_threes = 0;
             let mut num_twos = 0;
             for count in rank_counts.values() {
                 //println!("rank = {:?}, count = {}", rank, count);
                 match count {
+                    5 => num_fives += 1,
                     4 => num_fours += 1,
                     3 => num_threes += 1,
                     2 => num_twos += 1,
@@ -223,7 +330,10 @@ impl HandResult {
                 }
             }
 
-            if num_fours == 1 {
+            if num_fives == 1 {
+                hand_ranking = HandRanking::FiveOfAKind;
+                constituent_cards.extend(five_cards);
+            } else if num_fours == 1 {
                 hand_ranking = HandRanking::FourOfAKind;
                 for card in five_cards {
                     match *rank_counts.get(&card.rank).unwrap() {
@@ -297,7 +407,7 @@ impl HandResult {
         }
 
         kickers.sort();
-        let value = HandResult::score_hand(hand_ranking, &constituent_cards, &kickers);
+        let value = HandResult::score_hand(hand_ranking, &constituent_cards, &kickers, rules);
         Self {
             hand_ranking,
             constituent_cards,
@@ -306,9 +416,66 @@ impl HandResult {
         }
     }
 
+    /// Given fewer than five cards (e.g. the up cards in stud), we return a HandResult
+    /// that only considers pairs, trips, and quads, since it takes five cards
+    /// to make a straight or a flush.
+    /// Any wild cards are resolved into the best possible hand. Only the ranks matter for a partial hand,
+    /// so a wild card just pairs up with the highest card
+    pub fn analyze_partial_hand_with_wilds(cards: Vec<Card>, wild_ranks: &[Rank]) -> Self {
+	assert!(cards.len() < 5);
+	let (wilds, naturals): (Vec<Card>, Vec<Card>) = cards
+	    .into_iter()
+	    .partition(|card| card.is_wild(wild_ranks));
+	let candidates: Vec<Card> = Rank::iter().map(|rank| Card { rank, suit: Suit::Spade }).collect();
+	HandResult::best_with_wilds(naturals, &candidates, wilds.len(), &HandResult::analyze_natural_partial_hand)
+    }
+
+    /// analyze fewer than five cards without any wild cards
+    fn analyze_natural_partial_hand(mut cards: Vec<Card>) -> Self {
+	cards.sort();
+
+	let mut rank_counts: HashMap<Rank, u8> = HashMap::new();
+	for card in cards.iter() {
+	    let count = rank_counts.entry(card.rank).or_insert(0);
+	    *count += 1;
+	}
+	let num_pairs = rank_counts.values().filter(|count| **count == 2).count();
+	let hand_ranking = match rank_counts.values().max() {
+	    Some(4) => HandRanking::FourOfAKind,
+	    Some(3) => HandRanking::ThreeOfAKind,
+	    Some(2) if num_pairs == 2 => HandRanking::TwoPair,
+	    Some(2) => HandRanking::Pair,
+	    _ => HandRanking::HighCard,
+	};
+
+	let mut constituent_cards = Vec::new();
+	let mut kickers = Vec::new();
+	for card in cards {
+	    match *rank_counts.get(&card.rank).unwrap() {
+		1 => kickers.push(card),
+		_ => constituent_cards.push(card),
+	    }
+	}
+	if hand_ranking == HandRanking::HighCard {
+	    // the highest card is the constituent card, just like a five card high card hand
+	    if let Some(high_card) = kickers.pop() {
+		constituent_cards.push(high_card);
+	    }
+	}
+
+	let value = HandResult::score_hand(hand_ranking, &constituent_cards, &kickers, HandRankingRules::Standard);
+	Self {
+	    hand_ranking,
+	    constituent_cards,
+	    kickers,
+	    value,
+	}
+    }
+
     /// Given a hand of cards, we return a possible 
     /// us the hand ranking, the constituent cards, kickers, and hand score    
-    pub fn determine_draw_types(mut cards: Vec<Card>) -> Vec<DrawType> {
+    /// The rules decide which cards an Ace can make a low straight with
+    pub fn determine_draw_types(mut cards: Vec<Card>, rules: HandRankingRules) -> Vec<DrawType> {
 	// need at least three cards to look at, e.g. when analyze just
 	// the flop cards
 	assert!(cards.len() >= 3); 
@@ -323,7 +490,7 @@ impl HandResult {
 	    is_straight = true;
 	    let first_rank = cards[0].rank as usize;	    
 	    for (i, card) in cards.iter().enumerate() {
-		if (is_straight && i == 4 && card.rank == Rank::Ace && first_rank == 2)
+		if (is_straight && i == 4 && card.rank == Rank::Ace && first_rank == rules.lowest_rank() as usize)
 		    || card.rank as usize != first_rank + i {
 			// completing the straight with an Ace on 2-->Ace
 			is_straight = false;
@@ -347,10 +514,11 @@ impl HandResult {
 		    }
 		}
 		if start_index == 0 && cards.last().unwrap().rank == Rank::Ace  {		    
-		    if cards[0].rank == Rank::Two {
+		    let lowest_rank = rules.lowest_rank() as usize;
+		    if cards[0].rank as usize == lowest_rank {
 			used_ace = true;
 			contiguous += 1;
-		    } else if cards[0].rank == Rank::Three {
+		    } else if cards[0].rank as usize == lowest_rank + 1 {
 			used_ace = true;			
 			one_gapper +=1 ;			
 		    }
@@ -427,6 +595,170 @@ impl HandResult {
 
 
 
+/// The result of evaluating five cards as a low hand (e.g. for the low half of Omaha Hi-Lo, or Razz).
+/// In ace-to-five lowball, Aces are always low, and straights and flushes do not count against you,
+/// so the best possible low is 5-4-3-2-A (the wheel). Pairs do count against you.
+/// Like a HandResult, the value packs the hand into bits, with the category of pairs
+/// (0 for no pair, 1 for one pair, etc), then each of the ranks from most to least significant.
+/// e.g. a hand of 8, 6, 4, 2, A would look like
+/// value = [0] | [8] | [6] | [4] | [2] | [1]
+/// Note that for a low hand, a LOWER value is a better hand, so the Ord implementation is reversed,
+/// i.e. the better low hand compares as greater, just like with a HandResult
+#[derive(Debug, Eq)]
+pub struct LowHandResult {
+    pub cards: Vec<Card>, // sorted from the highest (i.e. most significant) card to the lowest
+    pub value: u32,
+}
+
+/// A better low hand has a smaller value, so it should compare as the greater hand
+impl Ord for LowHandResult {
+    fn cmp(&self, other: &Self) -> Ordering {
+	other.value.cmp(&self.value)
+    }
+}
+
+impl PartialOrd for LowHandResult {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+	Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for LowHandResult {
+    fn eq(&self, other: &Self) -> bool {
+	self.value == other.value
+    }
+}
+
+impl LowHandResult {
+    /// in ace-to-five lowball, the Ace is the lowest card
+    pub fn ace_low_rank(card: &Card) -> u32 {
+	match card.rank {
+	    Rank::Ace => 1,
+	    rank => rank as u32,
+	}
+    }
+
+    /// Given a hand of 5 cards, we return a LowHandResult, using ace-to-five rankings.
+    /// Fewer cards can be given too (e.g. the up cards in Razz), but such a hand
+    /// should only be compared against others with the same number of cards
+    pub fn analyze_ace_to_five(mut five_cards: Vec<Card>) -> Self {
+	assert!(!five_cards.is_empty() && five_cards.len() <= 5);
+	let mut rank_counts: HashMap<u32, u32> = HashMap::new();
+	for card in five_cards.iter() {
+	    *rank_counts.entry(LowHandResult::ace_low_rank(card)).or_insert(0) += 1;
+	}
+	// the cards that are paired are the most significant, then the highest ranks
+	five_cards.sort_by(|a, b| {
+	    let a_rank = LowHandResult::ace_low_rank(a);
+	    let b_rank = LowHandResult::ace_low_rank(b);
+	    rank_counts[&b_rank].cmp(&rank_counts[&a_rank]).then(b_rank.cmp(&a_rank))
+	});
+	let num_pairs = rank_counts.values().filter(|count| **count == 2).count();
+	let category = match rank_counts.values().max().unwrap() {
+	    1 => 0, // no pair
+	    2 if num_pairs == 1 => 1, // one pair
+	    2 => 2, // two pair
+	    3 if num_pairs == 0 => 3, // three of a kind
+	    3 => 4, // full house
+	    _ => 5, // four of a kind
+	};
+	let mut value = category << 20;
+	let mut shift_amount = 16;
+	for card in five_cards.iter() {
+	    value += LowHandResult::ace_low_rank(card) << shift_amount;
+	    shift_amount -= 4;
+	}
+	Self {
+	    cards: five_cards,
+	    value,
+	}
+    }
+
+    /// Given a hand of 5 cards, we return a LowHandResult, using deuce-to-seven rankings.
+    /// This is the opposite of a normal high hand: Aces are always high, and straights and flushes
+    /// count against you, so the best possible low is 7-5-4-3-2 (unsuited).
+    /// The value is simply the value of the high hand, since the lowest high hand is the best low hand
+    pub fn analyze_deuce_to_seven(five_cards: Vec<Card>) -> Self {
+	let high_result = HandResult::analyze_hand_with_rules(five_cards, HandRankingRules::DeuceToSeven);
+	let cards = high_result.constituent_cards.iter().rev()
+	    .chain(high_result.kickers.iter().rev())
+	    .copied()
+	    .collect();
+	Self {
+	    cards,
+	    value: high_result.value,
+	}
+    }
+
+    /// Given a hand of 4 cards, we return a LowHandResult for Badugi.
+    /// Only cards of different suits and different ranks count, so the hand is made of
+    /// the most cards that share no suit or rank, e.g. a four card badugi beats any three card hand.
+    /// Aces are low, and between hands with as many cards, the lowest high card wins (then the next, etc).
+    /// The category of the value is how many cards don't count, so the best possible badugi
+    /// is 4-3-2-A of four different suits
+    pub fn analyze_badugi(four_cards: Vec<Card>) -> Self {
+	assert!(four_cards.len() == 4);
+	(1..=4)
+	    .rev()
+	    .find_map(|num_cards| {
+		combinations(&four_cards, num_cards)
+		    .into_iter()
+		    .filter(|cards| {
+			cards.iter().enumerate().all(|(i, card)| {
+			    cards[..i].iter().all(|other| other.rank != card.rank && other.suit != card.suit)
+			})
+		    })
+		    .map(|mut cards| {
+			cards.sort_by_key(|card| std::cmp::Reverse(LowHandResult::ace_low_rank(card)));
+			let mut value = (4 - num_cards as u32) << 20;
+			let mut shift_amount = 16;
+			for card in cards.iter() {
+			    value += LowHandResult::ace_low_rank(card) << shift_amount;
+			    shift_amount -= 4;
+			}
+			Self { cards, value }
+		    })
+		    .max()
+	    })
+	    .unwrap()
+    }
+
+    /// a low hand only qualifies in a split pot game (e.g. Omaha Hi-Lo) if it
+    /// has five different ranks that are all eight or lower
+    pub fn is_eight_or_better(&self) -> bool {
+	let no_pairs = self.value >> 20 == 0;
+	no_pairs && LowHandResult::ace_low_rank(&self.cards[0]) <= 8
+    }
+
+    pub fn cards_string(&self) -> String {
+	self.cards
+	    .iter()
+	    .map(|x| x.to_string())
+	    .collect::<Vec<String>>()
+	    .join("-")
+    }
+}
+
+/// returns every way of choosing k cards out of the given cards (order within each choice is preserved)
+/// e.g. useful in Omaha, where we need every pair of hole cards and every three board cards
+pub fn combinations(cards: &[Card], k: usize) -> Vec<Vec<Card>> {
+    if k == 0 {
+	return vec![vec![]];
+    }
+    if cards.len() < k {
+	return vec![];
+    }
+    let mut combos = vec![];
+    for (i, card) in cards.iter().enumerate() {
+	for mut rest in combinations(&cards[i+1..], k - 1) {
+	    rest.insert(0, *card);
+	    combos.push(rest);
+	}
+    }
+    combos
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1067,5 +1399,333 @@ mod tests {
         assert_eq!(result2.hand_ranking, HandRanking::Flush);
         assert!(result1 > result2);
     }
-    
+
+    /// in ace-to-five lowball, the wheel (5-4-3-2-A) is the best low,
+    /// and an eight-high low qualifies, but a nine-high or paired hand does not
+    #[test]
+    fn compare_low_hands() {
+	let wheel = LowHandResult::analyze_ace_to_five(vec![
+	    Card { rank: Rank::Ace, suit: Suit::Spade },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	    Card { rank: Rank::Three, suit: Suit::Spade },
+	    Card { rank: Rank::Four, suit: Suit::Spade },
+	    Card { rank: Rank::Five, suit: Suit::Spade },
+	]);
+	let six_four = LowHandResult::analyze_ace_to_five(vec![
+	    Card { rank: Rank::Six, suit: Suit::Heart },
+	    Card { rank: Rank::Four, suit: Suit::Spade },
+	    Card { rank: Rank::Three, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	    Card { rank: Rank::Ace, suit: Suit::Diamond },
+	]);
+	let eight_seven = LowHandResult::analyze_ace_to_five(vec![
+	    Card { rank: Rank::Eight, suit: Suit::Heart },
+	    Card { rank: Rank::Seven, suit: Suit::Spade },
+	    Card { rank: Rank::Three, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	    Card { rank: Rank::Ace, suit: Suit::Diamond },
+	]);
+	let nine_high = LowHandResult::analyze_ace_to_five(vec![
+	    Card { rank: Rank::Nine, suit: Suit::Heart },
+	    Card { rank: Rank::Four, suit: Suit::Spade },
+	    Card { rank: Rank::Three, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	    Card { rank: Rank::Ace, suit: Suit::Diamond },
+	]);
+	let paired = LowHandResult::analyze_ace_to_five(vec![
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	    Card { rank: Rank::Four, suit: Suit::Spade },
+	    Card { rank: Rank::Three, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	    Card { rank: Rank::Ace, suit: Suit::Diamond },
+	]);
+	assert!(wheel > six_four);
+	assert!(six_four > eight_seven);
+	assert!(eight_seven > nine_high);
+	assert!(nine_high > paired);
+
+	assert!(wheel.is_eight_or_better());
+	assert!(eight_seven.is_eight_or_better());
+	assert!(!nine_high.is_eight_or_better());
+	assert!(!paired.is_eight_or_better());
+	assert_eq!(six_four.cards_string(), "6h-4s-3c-2s-Ad");
+    }
+
+    /// in deuce-to-seven, aces are high and straights and flushes count against you
+    #[test]
+    fn compare_deuce_to_seven_hands() {
+	let number_one = LowHandResult::analyze_deuce_to_seven(vec![
+	    Card { rank: Rank::Seven, suit: Suit::Heart },
+	    Card { rank: Rank::Five, suit: Suit::Spade },
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	    Card { rank: Rank::Three, suit: Suit::Spade },
+	    Card { rank: Rank::Two, suit: Suit::Diamond },
+	]);
+	let eight_six = LowHandResult::analyze_deuce_to_seven(vec![
+	    Card { rank: Rank::Eight, suit: Suit::Heart },
+	    Card { rank: Rank::Six, suit: Suit::Spade },
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	    Card { rank: Rank::Three, suit: Suit::Spade },
+	    Card { rank: Rank::Two, suit: Suit::Diamond },
+	]);
+	let ace_high = LowHandResult::analyze_deuce_to_seven(vec![
+	    Card { rank: Rank::Ace, suit: Suit::Heart },
+	    Card { rank: Rank::Five, suit: Suit::Spade },
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	    Card { rank: Rank::Three, suit: Suit::Spade },
+	    Card { rank: Rank::Two, suit: Suit::Diamond },
+	]);
+	let straight = LowHandResult::analyze_deuce_to_seven(vec![
+	    Card { rank: Rank::Six, suit: Suit::Heart },
+	    Card { rank: Rank::Five, suit: Suit::Spade },
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	    Card { rank: Rank::Three, suit: Suit::Spade },
+	    Card { rank: Rank::Two, suit: Suit::Diamond },
+	]);
+	let flush = LowHandResult::analyze_deuce_to_seven(vec![
+	    Card { rank: Rank::Seven, suit: Suit::Spade },
+	    Card { rank: Rank::Five, suit: Suit::Spade },
+	    Card { rank: Rank::Four, suit: Suit::Spade },
+	    Card { rank: Rank::Three, suit: Suit::Spade },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	]);
+	let paired = LowHandResult::analyze_deuce_to_seven(vec![
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	    Card { rank: Rank::Five, suit: Suit::Spade },
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	    Card { rank: Rank::Three, suit: Suit::Spade },
+	    Card { rank: Rank::Two, suit: Suit::Diamond },
+	]);
+	assert!(number_one > eight_six);
+	// A-5-4-3-2 is not a straight, just a bad ace high
+	assert!(eight_six > ace_high);
+	assert!(ace_high > paired);
+	assert!(paired > straight);
+	assert!(straight > flush);
+	assert_eq!(eight_six.cards_string(), "8h-6s-4c-3s-2d");
+    }
+
+    /// with the short-deck rules, a flush beats a full house, and A-6-7-8-9 is the lowest straight
+    #[test]
+    fn short_deck_rankings() {
+	let flush = vec![
+	    Card { rank: Rank::Six, suit: Suit::Heart },
+	    Card { rank: Rank::Eight, suit: Suit::Heart },
+	    Card { rank: Rank::Ten, suit: Suit::Heart },
+	    Card { rank: Rank::Jack, suit: Suit::Heart },
+	    Card { rank: Rank::King, suit: Suit::Heart },
+	];
+	let full_house = vec![
+	    Card { rank: Rank::Ace, suit: Suit::Club },
+	    Card { rank: Rank::Ace, suit: Suit::Diamond },
+	    Card { rank: Rank::Ace, suit: Suit::Heart },
+	    Card { rank: Rank::King, suit: Suit::Spade },
+	    Card { rank: Rank::King, suit: Suit::Club },
+	];
+	assert!(HandResult::analyze_hand(full_house.clone()) > HandResult::analyze_hand(flush.clone()));
+	let short_flush = HandResult::analyze_hand_with_rules(flush, HandRankingRules::ShortDeck);
+	let short_full_house = HandResult::analyze_hand_with_rules(full_house, HandRankingRules::ShortDeck);
+	assert_eq!(short_flush.hand_ranking, HandRanking::Flush);
+	assert_eq!(short_full_house.hand_ranking, HandRanking::FullHouse);
+	assert!(short_flush > short_full_house);
+
+	let ace_low = vec![
+	    Card { rank: Rank::Ace, suit: Suit::Club },
+	    Card { rank: Rank::Six, suit: Suit::Diamond },
+	    Card { rank: Rank::Seven, suit: Suit::Heart },
+	    Card { rank: Rank::Eight, suit: Suit::Spade },
+	    Card { rank: Rank::Nine, suit: Suit::Club },
+	];
+	let ten_high = vec![
+	    Card { rank: Rank::Ten, suit: Suit::Club },
+	    Card { rank: Rank::Six, suit: Suit::Diamond },
+	    Card { rank: Rank::Seven, suit: Suit::Heart },
+	    Card { rank: Rank::Eight, suit: Suit::Spade },
+	    Card { rank: Rank::Nine, suit: Suit::Club },
+	];
+	assert_eq!(HandResult::analyze_hand(ace_low.clone()).hand_ranking, HandRanking::HighCard);
+	let short_ace_low = HandResult::analyze_hand_with_rules(ace_low, HandRankingRules::ShortDeck);
+	let short_ten_high = HandResult::analyze_hand_with_rules(ten_high, HandRankingRules::ShortDeck);
+	assert_eq!(short_ace_low.hand_ranking, HandRanking::Straight);
+	assert_eq!(short_ace_low.constituent_cards_string(), "Ac-6d-7h-8s-9c");
+	assert!(short_ten_high > short_ace_low);
+    }
+
+    /// with fewer than five cards, only pairs, trips, and quads count
+    #[test]
+    fn compare_partial_hands() {
+	let pair = HandResult::analyze_partial_hand_with_wilds(vec![
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	    Card { rank: Rank::Four, suit: Suit::Heart },
+	], &[]);
+	let ace_king = HandResult::analyze_partial_hand_with_wilds(vec![
+	    Card { rank: Rank::Ace, suit: Suit::Club },
+	    Card { rank: Rank::King, suit: Suit::Club },
+	], &[]);
+	let two_pair = HandResult::analyze_partial_hand_with_wilds(vec![
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	    Card { rank: Rank::Four, suit: Suit::Heart },
+	    Card { rank: Rank::Three, suit: Suit::Club },
+	    Card { rank: Rank::Three, suit: Suit::Heart },
+	], &[]);
+	let trips = HandResult::analyze_partial_hand_with_wilds(vec![
+	    Card { rank: Rank::Two, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	], &[]);
+	assert_eq!(ace_king.hand_ranking, HandRanking::HighCard);
+	assert_eq!(ace_king.constituent_cards_string(), "Ac");
+	assert_eq!(pair.hand_ranking, HandRanking::Pair);
+	assert_eq!(two_pair.hand_ranking, HandRanking::TwoPair);
+	assert_eq!(trips.hand_ranking, HandRanking::ThreeOfAKind);
+	assert!(pair > ace_king);
+	assert!(two_pair > pair);
+	assert!(trips > two_pair);
+    }
+
+    /// a joker becomes whichever card makes the best hand, and four Aces with a joker
+    /// make five of a kind, which beats a royal flush
+    #[test]
+    fn jokers_are_wild() {
+	let joker = Card { rank: Rank::Joker, suit: Suit::Spade };
+	let five_aces = HandResult::analyze_hand(vec![
+	    Card { rank: Rank::Ace, suit: Suit::Club },
+	    Card { rank: Rank::Ace, suit: Suit::Diamond },
+	    Card { rank: Rank::Ace, suit: Suit::Heart },
+	    Card { rank: Rank::Ace, suit: Suit::Spade },
+	    joker,
+	]);
+	let royal_flush = HandResult::analyze_hand(vec![
+	    Card { rank: Rank::Ten, suit: Suit::Heart },
+	    Card { rank: Rank::Jack, suit: Suit::Heart },
+	    Card { rank: Rank::Queen, suit: Suit::Heart },
+	    Card { rank: Rank::King, suit: Suit::Heart },
+	    Card { rank: Rank::Ace, suit: Suit::Heart },
+	]);
+	assert_eq!(five_aces.hand_ranking, HandRanking::FiveOfAKind);
+	assert_eq!(royal_flush.hand_ranking, HandRanking::RoyalFlush);
+	assert!(five_aces > royal_flush);
+
+	// the joker fills the gap in the straight flush
+	let straight_flush = HandResult::analyze_hand(vec![
+	    Card { rank: Rank::Five, suit: Suit::Club },
+	    Card { rank: Rank::Six, suit: Suit::Club },
+	    Card { rank: Rank::Eight, suit: Suit::Club },
+	    Card { rank: Rank::Nine, suit: Suit::Club },
+	    joker,
+	]);
+	assert_eq!(straight_flush.hand_ranking, HandRanking::StraightFlush);
+	assert_eq!(straight_flush.constituent_cards_string(), "5c-6c-7c-8c-9c");
+
+	// the joker completes the flush with an Ace, rather than pairing the King
+	let flush = HandResult::analyze_hand(vec![
+	    Card { rank: Rank::Two, suit: Suit::Diamond },
+	    Card { rank: Rank::Seven, suit: Suit::Diamond },
+	    Card { rank: Rank::Nine, suit: Suit::Diamond },
+	    Card { rank: Rank::King, suit: Suit::Diamond },
+	    joker,
+	]);
+	assert_eq!(flush.hand_ranking, HandRanking::Flush);
+	assert_eq!(flush.constituent_cards_string(), "2d-7d-9d-Kd-Ad");
+
+	// two jokers with a pair make four of a kind
+	let quads = HandResult::analyze_hand(vec![
+	    Card { rank: Rank::Three, suit: Suit::Club },
+	    Card { rank: Rank::Three, suit: Suit::Diamond },
+	    Card { rank: Rank::Nine, suit: Suit::Heart },
+	    joker,
+	    Card { rank: Rank::Joker, suit: Suit::Heart },
+	]);
+	assert_eq!(quads.hand_ranking, HandRanking::FourOfAKind);
+	assert!(quads < royal_flush);
+    }
+
+    /// when deuces are wild, every Two can stand for any card, in full hands and partial ones
+    #[test]
+    fn deuces_wild() {
+	let wild_ranks = [Rank::Two];
+	let five_kings = HandResult::analyze_hand_with_wilds(vec![
+	    Card { rank: Rank::King, suit: Suit::Club },
+	    Card { rank: Rank::King, suit: Suit::Diamond },
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	    Card { rank: Rank::King, suit: Suit::Spade },
+	], HandRankingRules::Standard, &wild_ranks);
+	let five_queens = HandResult::analyze_hand_with_wilds(vec![
+	    Card { rank: Rank::Queen, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Diamond },
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	    Card { rank: Rank::Queen, suit: Suit::Spade },
+	], HandRankingRules::Standard, &wild_ranks);
+	assert_eq!(five_kings.hand_ranking, HandRanking::FiveOfAKind);
+	assert_eq!(five_queens.hand_ranking, HandRanking::FiveOfAKind);
+	assert!(five_kings > five_queens);
+
+	// without deuces wild, it's just a full house
+	let full_house = HandResult::analyze_hand_with_rules(vec![
+	    Card { rank: Rank::King, suit: Suit::Club },
+	    Card { rank: Rank::King, suit: Suit::Diamond },
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	    Card { rank: Rank::King, suit: Suit::Spade },
+	], HandRankingRules::Standard);
+	assert_eq!(full_house.hand_ranking, HandRanking::FullHouse);
+
+	// a deuce completes the wheel, or makes a higher straight when it can
+	let straight = HandResult::analyze_hand_with_wilds(vec![
+	    Card { rank: Rank::Three, suit: Suit::Club },
+	    Card { rank: Rank::Four, suit: Suit::Diamond },
+	    Card { rank: Rank::Five, suit: Suit::Heart },
+	    Card { rank: Rank::Six, suit: Suit::Spade },
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	], HandRankingRules::Standard, &wild_ranks);
+	assert_eq!(straight.hand_ranking, HandRanking::Straight);
+	assert_eq!(straight.constituent_cards_string(), "3c-4d-5h-6s-7d");
+
+	let up_cards = HandResult::analyze_partial_hand_with_wilds(vec![
+	    Card { rank: Rank::Nine, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	], &wild_ranks);
+	assert_eq!(up_cards.hand_ranking, HandRanking::Pair);
+	assert_eq!(up_cards.constituent_cards_string(), "9c-9s");
+    }
+
+    /// in Badugi only the cards with different suits and ranks count,
+    /// so more cards beat fewer, then the lowest high card wins
+    #[test]
+    fn compare_badugi_hands() {
+	let four_card = LowHandResult::analyze_badugi(vec![
+	    Card { rank: Rank::King, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Diamond },
+	    Card { rank: Rank::Three, suit: Suit::Heart },
+	    Card { rank: Rank::Ace, suit: Suit::Spade },
+	]);
+	let best = LowHandResult::analyze_badugi(vec![
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Diamond },
+	    Card { rank: Rank::Three, suit: Suit::Heart },
+	    Card { rank: Rank::Ace, suit: Suit::Spade },
+	]);
+	// the two clubs can't both count, so the higher one is left out
+	let three_card = LowHandResult::analyze_badugi(vec![
+	    Card { rank: Rank::Four, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Club },
+	    Card { rank: Rank::Three, suit: Suit::Heart },
+	    Card { rank: Rank::Ace, suit: Suit::Spade },
+	]);
+	// the pair of Twos can't both count either
+	let paired = LowHandResult::analyze_badugi(vec![
+	    Card { rank: Rank::Five, suit: Suit::Club },
+	    Card { rank: Rank::Two, suit: Suit::Diamond },
+	    Card { rank: Rank::Two, suit: Suit::Heart },
+	    Card { rank: Rank::Ace, suit: Suit::Spade },
+	]);
+	assert_eq!(best.cards_string(), "4c-3h-2d-As");
+	assert_eq!(three_card.cards_string(), "3h-2c-As");
+	assert_eq!(paired.cards.len(), 3);
+	assert!(best > four_card);
+	assert!(four_card > three_card);
+	assert!(three_card > paired);
+    }
 }
@@ -0,0 +1,245 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How the prize money is split when the players agree to end the game early
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DealMethod {
+    Icm, // everyone gets their equity under the Independent Chip Model
+    ChipChop, // everyone gets the smallest remaining payout, and the rest is split by chip count
+}
+
+impl fmt::Display for DealMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+	let output = match self {
+	    DealMethod::Icm => "icm",
+	    DealMethod::ChipChop => "chip_chop",
+	};
+	write!(f, "{}", output)
+    }
+}
+
+/// The Independent Chip Model: a player's chance of finishing first is their share of the chips,
+/// and their chance of finishing in each later place follows from the same rule among the players left.
+/// Returns each player's expected prize money, in the same order as the stacks.
+/// The payouts are for first place, second place, etc., and any unpaid places are worth nothing
+pub fn icm_equities(stacks: &[u32], payouts: &[u32]) -> Vec<f64> {
+    let everyone = (1usize << stacks.len()) - 1;
+    let mut memo = HashMap::new();
+    place_equities(stacks, payouts, everyone, &mut memo)
+}
+
+/// the equities of the players in the mask (as a bit set) for the places that are left
+/// once everyone outside the mask has finished
+fn place_equities(stacks: &[u32], payouts: &[u32], mask: usize, memo: &mut HashMap<usize, Vec<f64>>) -> Vec<f64> {
+    if let Some(equities) = memo.get(&mask) {
+	return equities.clone();
+    }
+    let mut equities = vec![0.0; stacks.len()];
+    let place = stacks.len() - mask.count_ones() as usize;
+    if mask == 0 || place >= payouts.len() {
+	return equities;
+    }
+    let remaining: Vec<usize> = (0..stacks.len()).filter(|i| mask & (1 << i) != 0).collect();
+    let total: u32 = remaining.iter().map(|i| stacks[*i]).sum();
+    for i in remaining.iter() {
+	// with no chips left between them, everyone is as likely as everyone else to finish here
+	let chance = if total == 0 {
+	    1.0 / remaining.len() as f64
+	} else {
+	    f64::from(stacks[*i]) / f64::from(total)
+	};
+	if chance == 0.0 {
+	    continue;
+	}
+	equities[*i] += chance * f64::from(payouts[place]);
+	let rest = place_equities(stacks, payouts, mask & !(1 << i), memo);
+	for (equity, rest_equity) in equities.iter_mut().zip(rest) {
+	    *equity += chance * rest_equity;
+	}
+    }
+    memo.insert(mask, equities.clone());
+    equities
+}
+
+/// A chip chop: everyone is guaranteed the smallest payout among the places left,
+/// and the rest of the prize money is split in proportion to the chips
+pub fn chip_chop_equities(stacks: &[u32], payouts: &[u32]) -> Vec<f64> {
+    let num_players = stacks.len();
+    if num_players == 0 {
+	return vec![];
+    }
+    let prize_pool: u32 = payouts.iter().take(num_players).sum();
+    let guaranteed = if payouts.len() >= num_players {
+	payouts.iter().take(num_players).min().copied().unwrap_or(0)
+    } else {
+	0
+    };
+    let rest = f64::from(prize_pool - guaranteed * num_players as u32);
+    let total: u32 = stacks.iter().sum();
+    stacks
+	.iter()
+	.map(|stack| {
+	    let share = if total == 0 {
+		1.0 / num_players as f64
+	    } else {
+		f64::from(*stack) / f64::from(total)
+	    };
+	    f64::from(guaranteed) + share * rest
+	})
+	.collect()
+}
+
+/// round the equities down to whole amounts, then hand out whatever is left over one at a time
+/// to the biggest fractions, so that the amounts add up to exactly the prize money
+fn round_equities(equities: &[f64], prize_pool: u32) -> Vec<u32> {
+    let mut amounts: Vec<u32> = equities.iter().map(|equity| equity.floor() as u32).collect();
+    let mut by_fraction: Vec<usize> = (0..equities.len()).collect();
+    by_fraction.sort_by(|a, b| {
+	let (fraction_a, fraction_b) = (equities[*a].fract(), equities[*b].fract());
+	fraction_b.partial_cmp(&fraction_a).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let left_over = prize_pool.saturating_sub(amounts.iter().sum());
+    for i in by_fraction.into_iter().cycle().take(left_over as usize) {
+	amounts[i] += 1;
+    }
+    amounts
+}
+
+/// each player's share of the prize money under the given method, in whole amounts
+/// (the payouts go down from first place, and their total fits in a u32)
+pub fn split_prizes(method: DealMethod, stacks: &[u32], payouts: &[u32]) -> Vec<u32> {
+    let equities = match method {
+	DealMethod::Icm => icm_equities(stacks, payouts),
+	DealMethod::ChipChop => chip_chop_equities(stacks, payouts),
+    };
+    let prize_pool = payouts.iter().take(stacks.len()).sum();
+    round_equities(&equities, prize_pool)
+}
+
+/// how a prize pool is paid out, from first place down, depending on how many players entered:
+/// winner take all for up to four players, 65/35 for up to six, and 50/30/20 beyond that.
+/// Any odd chips go to first place
+pub fn payout_structure(prize_pool: u32, num_entrants: usize) -> Vec<u32> {
+    let percentages: &[u32] = match num_entrants {
+	0..=4 => &[100],
+	5..=6 => &[65, 35],
+	_ => &[50, 30, 20],
+    };
+    let mut payouts: Vec<u32> = percentages
+	.iter()
+	.map(|percentage| (u64::from(prize_pool) * u64::from(*percentage) / 100) as u32)
+	.collect();
+    payouts[0] += prize_pool - payouts.iter().sum::<u32>();
+    payouts
+}
+
+/// A deal that one player proposed to end the game, splitting the remaining payouts between everyone still in.
+/// Both the ICM and the chip chop splits are worked out so that everyone can compare them,
+/// and the deal only goes through if every player in it accepts
+#[derive(Debug)]
+pub struct Deal {
+    pub proposer: Uuid,
+    pub method: DealMethod,
+    pub payouts: Vec<u32>, // the prize money for first place, second place, etc.
+    pub ids: Vec<Uuid>, // the players in the deal
+    pub stacks: Vec<u32>, // their chips when the deal was proposed
+    pub icm: Vec<u32>,
+    pub chip_chop: Vec<u32>,
+    accepted: HashSet<Uuid>,
+    proposed_at: Instant,
+}
+
+impl Deal {
+    /// the players are given with their stacks. The proposer accepts their own deal
+    pub fn new(proposer: Uuid, method: DealMethod, payouts: Vec<u32>, players: &[(Uuid, u32)]) -> Self {
+	let ids: Vec<Uuid> = players.iter().map(|(id, _)| *id).collect();
+	let stacks: Vec<u32> = players.iter().map(|(_, stack)| *stack).collect();
+	let icm = split_prizes(DealMethod::Icm, &stacks, &payouts);
+	let chip_chop = split_prizes(DealMethod::ChipChop, &stacks, &payouts);
+	let mut accepted = HashSet::new();
+	accepted.insert(proposer);
+	Self {
+	    proposer,
+	    method,
+	    payouts,
+	    ids,
+	    stacks,
+	    icm,
+	    chip_chop,
+	    accepted,
+	    proposed_at: Instant::now(),
+	}
+    }
+
+    pub fn is_in_deal(&self, id: Uuid) -> bool {
+	self.ids.contains(&id)
+    }
+
+    /// returns false if the player isn't in the deal
+    pub fn accept(&mut self, id: Uuid) -> bool {
+	if !self.is_in_deal(id) {
+	    return false;
+	}
+	self.accepted.insert(id);
+	true
+    }
+
+    pub fn has_accepted(&self, id: Uuid) -> bool {
+	self.accepted.contains(&id)
+    }
+
+    pub fn is_unanimous(&self) -> bool {
+	self.ids.iter().all(|id| self.accepted.contains(id))
+    }
+
+    /// has the deal been waiting on the players for longer than the timeout
+    pub fn is_expired(&self, timeout: Duration) -> bool {
+	self.proposed_at.elapsed() >= timeout
+    }
+
+    /// what each player in the deal is paid, under the proposed method
+    pub fn prizes(&self) -> &[u32] {
+	match self.method {
+	    DealMethod::Icm => &self.icm,
+	    DealMethod::ChipChop => &self.chip_chop,
+	}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icm_and_chip_chop() {
+	let stacks = [5000, 3000, 2000];
+	let payouts = [50, 30, 20];
+	let equities = icm_equities(&stacks, &payouts);
+	assert!((equities[0] - 38.393).abs() < 0.001);
+	assert!((equities[1] - 32.75).abs() < 0.001);
+	assert!((equities[2] - 28.857).abs() < 0.001);
+	// the odd dollars go to the biggest fractions
+	assert_eq!(split_prizes(DealMethod::Icm, &stacks, &payouts), vec![38, 33, 29]);
+	// everyone is guaranteed 20, and the other 40 goes by chips
+	assert_eq!(split_prizes(DealMethod::ChipChop, &stacks, &payouts), vec![40, 32, 28]);
+	// winner take all is the same as the chip count
+	assert_eq!(split_prizes(DealMethod::Icm, &stacks, &[100]), vec![50, 30, 20]);
+	assert_eq!(payout_structure(1001, 6), vec![651, 350]);
+	assert_eq!(payout_structure(900, 9), vec![450, 270, 180]);
+
+	let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+	let players: Vec<(Uuid, u32)> = ids.iter().cloned().zip(stacks.iter().cloned()).collect();
+	let mut deal = Deal::new(ids[0], DealMethod::ChipChop, payouts.to_vec(), &players);
+	assert!(!deal.is_unanimous());
+	assert!(deal.accept(ids[1]));
+	assert!(!deal.accept(Uuid::new_v4()));
+	assert!(deal.accept(ids[2]));
+	assert!(deal.is_unanimous());
+	assert_eq!(deal.prizes(), &[40, 32, 28]);
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+/// one eliminator's share of a knocked out player's bounty
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BountyAward {
+    pub id: Uuid,
+    pub amount: u32, // paid out to the eliminator
+    pub bounty_increase: u32, // added to the eliminator's own bounty (in progressive mode)
+}
+
+/// In a knockout game, every player carries a bounty, which is paid (on top of the prize pool) to whoever busts them.
+/// In progressive mode, only half of it is paid out, and the other half is added to the eliminator's own bounty,
+/// so the big stacks become bigger targets
+#[derive(Debug)]
+pub struct Knockout {
+    starting_bounty: u32, // everyone's bounty when they buy in
+    progressive: bool,
+    bounties: HashMap<Uuid, u32>, // the bounties that have grown, or been collected
+    collected: HashMap<Uuid, u32>, // how much each player has won in bounties
+}
+
+impl Knockout {
+    pub fn new(starting_bounty: u32, progressive: bool) -> Self {
+	Self {
+	    starting_bounty,
+	    progressive,
+	    bounties: HashMap::new(),
+	    collected: HashMap::new(),
+	}
+    }
+
+    pub fn is_progressive(&self) -> bool {
+	self.progressive
+    }
+
+    /// the bounty on the player's head
+    pub fn bounty(&self, id: Uuid) -> u32 {
+	*self.bounties.get(&id).unwrap_or(&self.starting_bounty)
+    }
+
+    /// how much the player has won in bounties
+    pub fn collected(&self, id: Uuid) -> u32 {
+	*self.collected.get(&id).unwrap_or(&0)
+    }
+
+    /// the busted player's bounty is split evenly between everyone who eliminated them (e.g. they split the pot),
+    /// with any odd chips going to the first eliminator. The busted player starts over with the starting bounty
+    /// if they rebuy
+    pub fn knock_out(&mut self, busted_id: Uuid, eliminator_ids: &[Uuid]) -> Vec<BountyAward> {
+	if eliminator_ids.is_empty() {
+	    return vec![];
+	}
+	let bounty = self.bounty(busted_id);
+	self.bounties.remove(&busted_id);
+	let num_eliminators = eliminator_ids.len() as u32;
+	let mut awards = vec![];
+	for (i, id) in eliminator_ids.iter().enumerate() {
+	    let mut share = bounty / num_eliminators;
+	    if i == 0 {
+		share += bounty % num_eliminators;
+	    }
+	    let bounty_increase = if self.progressive { share / 2 } else { 0 };
+	    let amount = share - bounty_increase;
+	    *self.bounties.entry(*id).or_insert(self.starting_bounty) += bounty_increase;
+	    *self.collected.entry(*id).or_insert(0) += amount;
+	    awards.push(BountyAward { id: *id, amount, bounty_increase });
+	}
+	awards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progressive_bounties() {
+	let mut knockout = Knockout::new(101, true);
+	let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+	let awards = knockout.knock_out(ids[0], &[ids[1]]);
+	assert_eq!(awards, vec![BountyAward { id: ids[1], amount: 51, bounty_increase: 50 }]);
+	assert_eq!(knockout.bounty(ids[1]), 151);
+	assert_eq!(knockout.collected(ids[1]), 51);
+
+	// a split pot splits the bounty, and the odd chip goes to the first eliminator
+	let awards = knockout.knock_out(ids[1], &[ids[2], ids[3]]);
+	assert_eq!(awards, vec![
+	    BountyAward { id: ids[2], amount: 38, bounty_increase: 38 },
+	    BountyAward { id: ids[3], amount: 38, bounty_increase: 37 },
+	]);
+	assert_eq!(knockout.bounty(ids[2]), 139);
+	assert_eq!(knockout.bounty(ids[3]), 138);
+	// the busted player would start over after a rebuy
+	assert_eq!(knockout.bounty(ids[1]), 101);
+    }
+}
@@ -7,8 +7,27 @@ mod bot;
 pub mod player;
 pub mod deck;
 pub mod table;
+pub mod variant;
+pub mod betting_structure;
+pub mod straddle;
+pub mod rotation;
+pub mod wild_cards;
+pub mod tournament;
+pub mod freezeout;
+pub mod knockout;
+pub mod icm;
 
-pub use table::Table;
+pub use table::{Table, TableOptions};
+pub use card::Card;
 pub use player::PlayerAction;
 pub use player::PlayerConfig;
 pub use player::PLAYER_TIMEOUT;
+pub use variant::Variant;
+pub use betting_structure::BettingStructure;
+pub use straddle::Straddle;
+pub use rotation::{GameRotation, RotationSchedule};
+pub use wild_cards::WildCards;
+pub use freezeout::Freezeout;
+pub use knockout::Knockout;
+pub use icm::DealMethod;
+pub use tournament::{balance_tables, seat_players, BlindLevel, BlindSchedule, Reseat, Tournament};
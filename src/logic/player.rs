@@ -1,5 +1,5 @@
 use super::card::{Card, Suit};
-use super::hand_analysis::{DrawType, DrawAnalysis, HandResult};
+use super::hand_analysis::{combinations, DrawType, DrawAnalysis, HandResult, LowHandResult};
 use super::game_hand::GameHand;
 use crate::messages::WsMessage;
 use actix::prelude::Recipient;
@@ -16,15 +16,23 @@ use std::fmt;
 /// before we remove them from any game AND the hub.
 pub const PLAYER_TIMEOUT: Duration = Duration::from_secs(1800);
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PlayerAction {
     PostSmallBlind(u32),
     PostBigBlind(u32),
+    PostStraddle(u32), // a voluntary blind of twice the big blind
+    PostAnte(u32),
+    PostBigBlindAnte(u32), // the big blind pays one ante for the whole table
+    PostBombPot(u32), // everyone puts in the same amount, and the hand starts on the flop
+    PostBringIn(u32), // in stud, the lowest upcard is forced to open the betting
     Fold,
     SitOut,    
     Check,
     Bet(u32),
     Call,
+    Draw(Vec<Card>), // in a draw game, throw away these cards and get new ones (none to stand pat)
+    Discard(Card), // in Pineapple, throw away one hole card without getting a new one
+    RunIt(u8), // once everyone is all-in, vote for how many times to run the rest of the board
     //Raise(u32), // i guess a raise is just a bet really?
 }
 impl fmt::Display for PlayerAction {
@@ -33,11 +41,21 @@ impl fmt::Display for PlayerAction {
 	let output = match self {
 	    Self::PostSmallBlind(amount) => format!("small_blind:{}", amount),
 	    Self::PostBigBlind(amount) => format!("big_blind:{}", amount),
+	    Self::PostStraddle(amount) => format!("straddle:{}", amount),
+	    Self::PostAnte(amount) => format!("ante:{}", amount),
+	    Self::PostBigBlindAnte(amount) => format!("big_blind_ante:{}", amount),
+	    Self::PostBombPot(amount) => format!("bomb_pot:{}", amount),
+	    Self::PostBringIn(amount) => format!("bring_in:{}", amount),
 	    Self::Fold => "fold".to_owned(),
 	    Self::SitOut => "sit out".to_owned(),	    
 	    Self::Check => "check".to_owned(),
 	    Self::Bet(amount) => format!("bet:{}", amount),
 	    Self::Call => "call".to_owned(),
+	    // everyone can see how many cards were drawn, but not which ones
+	    Self::Draw(cards) => format!("draw:{}", cards.len()),
+	    // the discarded card is not shown to anyone else
+	    Self::Discard(_) => "discard".to_owned(),
+	    Self::RunIt(times) => format!("run_it:{}", times),
 	};
         write!(f, "{}", output)
     }
@@ -141,7 +159,9 @@ pub struct Player {
     pub is_active: bool,      // is still playing the current hand
     pub is_sitting_out: bool, // if sitting out, then they are not active for any future hand
     pub hole_cards: Vec<Card>,
+    pub up_cards: Vec<Card>, // in stud, the cards dealt face up for everyone to see
     pub last_action: Option<PlayerAction>, // the last thing they did (or None)
+    pub wants_to_straddle: bool, // if the table allows it, straddle when in the straddle position
 }
 
 impl Player {
@@ -154,7 +174,9 @@ impl Player {
             is_active: false, // a branch new player is not active in a hand
             is_sitting_out: false,
             hole_cards: Vec::<Card>::with_capacity(2),
+            up_cards: Vec::<Card>::new(),
 	    last_action: None,
+	    wants_to_straddle: false,
         }
     }
 
@@ -178,6 +200,21 @@ impl Player {
         self.is_active && self.money == 0
     }
 
+    /// the hole cards concatenated into a single string for the front end, e.g. "AsKd"
+    pub fn hole_cards_string(&self) -> String {
+	self.hole_cards.iter().map(|card| card.to_string()).collect()
+    }
+
+    /// the up cards concatenated into a single string for the front end, e.g. "Qh7c"
+    pub fn up_cards_string(&self) -> String {
+	self.up_cards.iter().map(|card| card.to_string()).collect()
+    }
+
+    /// all of the player's own cards, both face down and face up
+    pub fn cards(&self) -> Vec<Card> {
+	self.hole_cards.iter().chain(self.up_cards.iter()).copied().collect()
+    }
+
     /// Given a gamehand,
     /// we need to determine which 5 cards make the best hand for this player
     /// If the player is not active or it is preflop, return None as the optional best hand.
@@ -190,6 +227,39 @@ impl Player {
 	    // there is no "best hand" if we didn't even make it to the flop
 	    return None;
 	}
+	if gamehand.variant.is_stud() {
+	    // in stud there is no board, so the best hand comes from the player's own cards
+	    let cards = self.cards();
+	    if cards.len() < 5 {
+		return Some(gamehand.analyze_partial_hand(cards));
+	    }
+	    return combinations(&cards, 5)
+		.into_iter()
+		.map(|possible_hand| gamehand.analyze_hand(possible_hand))
+		.max();
+	}
+	if let Some(num_from_hole) = gamehand.variant.required_hole_cards() {
+	    // e.g. in Omaha the hand must be made from exactly two hole cards and three board cards
+	    return Player::best_hand_with_exact_hole_cards(
+		&self.hole_cards,
+		&gamehand.board_cards(),
+		num_from_hole,
+		gamehand,
+	    );
+	}
+	if gamehand.variant.is_draw() {
+	    // a draw game has no board, so the best hand is just the player's five cards
+	    return Some(gamehand.analyze_hand(self.hole_cards.clone()));
+	}
+	if self.hole_cards.len() > 2 {
+	    // e.g. in Crazy Pineapple the players still hold three hole cards on the flop,
+	    // so look at every five card hand from the hole cards and the board
+	    let cards: Vec<Card> = self.hole_cards.iter().chain(gamehand.board_cards().iter()).copied().collect();
+	    return combinations(&cards, 5)
+		.into_iter()
+		.map(|possible_hand| gamehand.analyze_hand(possible_hand))
+		.max();
+	}
 	// we look at all possible 7 choose 5 (21) hands from the hole cards, flop, turn, river
 	let mut best_result: Option<HandResult> = None;
 	let mut hand_count = 0;	
@@ -216,7 +286,7 @@ impl Player {
 		}
 		hand_count += 1;		
 		// we have built a hand of five cards, now evaluate it
-		let current_result = HandResult::analyze_hand(possible_hand);
+		let current_result = gamehand.analyze_hand(possible_hand);
 		match best_result {
 		    None => best_result = Some(current_result),
 		    Some(result) if current_result > result => {
@@ -231,6 +301,82 @@ impl Player {
 	best_result
     }
 
+    /// find the best five card hand that uses exactly num_from_hole of the hole cards,
+    /// and the rest from the board
+    fn best_hand_with_exact_hole_cards(
+	hole_cards: &[Card],
+	board: &[Card],
+	num_from_hole: usize,
+	gamehand: &GameHand,
+    ) -> Option<HandResult> {
+	let mut best_result: Option<HandResult> = None;
+	for hole_combo in combinations(hole_cards, num_from_hole) {
+	    for board_combo in combinations(board, 5 - num_from_hole) {
+		let possible_hand: Vec<Card> = hole_combo.iter().chain(board_combo.iter()).copied().collect();
+		let current_result = gamehand.analyze_hand(possible_hand);
+		match best_result {
+		    None => best_result = Some(current_result),
+		    Some(ref result) if current_result > *result => {
+			best_result = Some(current_result)
+		    }
+		    _ => (),
+		}
+	    }
+	}
+	best_result
+    }
+
+    /// Given a gamehand of a hi-lo variant (e.g. Omaha Hi-Lo) or a lowball variant (e.g. Razz),
+    /// we need to determine which 5 cards make the best low hand for this player.
+    /// Returns None if the player is not active, or if in a hi-lo game they cannot make a low that is eight or better
+    pub fn determine_best_low_hand(&self, gamehand: &GameHand) -> Option<LowHandResult> {
+	if !self.is_active || gamehand.is_preflop() {
+	    return None;
+	}
+	if gamehand.variant.is_badugi() {
+	    // a badugi is made from the player's four cards, and it only uses the ones that count
+	    return Some(LowHandResult::analyze_badugi(self.hole_cards.clone()));
+	}
+	if gamehand.variant.is_draw() {
+	    // a draw game has no board, so the low hand is just the player's five cards
+	    return Some(LowHandResult::analyze_deuce_to_seven(self.hole_cards.clone()));
+	}
+	if gamehand.variant.is_stud() {
+	    // there is no board in stud, and there is no qualifier in Razz
+	    let cards = self.cards();
+	    let best_result = if cards.len() <= 5 {
+		Some(LowHandResult::analyze_ace_to_five(cards))
+	    } else {
+		combinations(&cards, 5)
+		    .into_iter()
+		    .map(LowHandResult::analyze_ace_to_five)
+		    .max()
+	    };
+	    // but in stud hi-lo the low must still be eight or better
+	    return best_result.filter(|result| !gamehand.variant.is_hi_lo() || result.is_eight_or_better());
+	}
+	let num_from_hole = gamehand.variant.required_hole_cards().unwrap_or(2);
+	let board = gamehand.board_cards();
+	let mut best_result: Option<LowHandResult> = None;
+	for hole_combo in combinations(&self.hole_cards, num_from_hole) {
+	    for board_combo in combinations(&board, 5 - num_from_hole) {
+		let possible_hand: Vec<Card> = hole_combo.iter().chain(board_combo.iter()).copied().collect();
+		let current_result = LowHandResult::analyze_ace_to_five(possible_hand);
+		if !current_result.is_eight_or_better() {
+		    continue;
+		}
+		match best_result {
+		    None => best_result = Some(current_result),
+		    Some(ref result) if current_result > *result => {
+			best_result = Some(current_result)
+		    }
+		    _ => (),
+		}
+	    }
+	}
+	best_result
+    }
+
 
     /*
     pub fn analyze_for_best_hand_and_draw_analysis(&self, gamehand: &GameHand)
@@ -269,6 +415,18 @@ impl Player {
 	    // no draws by definition at preflop
 	    return DrawAnalysis::from_draws(my_draws, board_draws);	    
 	}
+	if gamehand.variant.required_hole_cards() == Some(2) && self.hole_cards.len() > 2 {
+	    // e.g. in Omaha, we can only draw with two of our hole cards at a time,
+	    // so collect the draws that each pair would have on its own
+	    for pair in combinations(&self.hole_cards, 2) {
+		let mut pair_player = self.clone();
+		pair_player.hole_cards = pair;
+		let pair_analysis = pair_player.determine_draw_analysis(gamehand);
+		my_draws.extend(pair_analysis.my_draws);
+		board_draws.extend(pair_analysis.board_draws);
+	    }
+	    return DrawAnalysis::from_draws(my_draws, board_draws);
+	}
 
 	let top_rank = gamehand.highest_rank().unwrap();
 	if self.hole_cards[0].rank >= top_rank && self.hole_cards[1].rank >= top_rank {
@@ -298,7 +456,8 @@ impl Player {
 		    continue;
 		}
 		// we have built a hand of five cards, now evaluate it
-		let current_draws = HandResult::determine_draw_types(possible_hand);
+		let current_draws = HandResult::determine_draw_types(
+		    possible_hand, gamehand.variant.hand_ranking_rules());
 		for draw in current_draws {
                     // dont add flush draws if neither of our hole cards have this suit
                     // (could be smarter and see how many are ours, but this is something at least)
@@ -349,6 +508,8 @@ mod tests {
     use super::*;
     use crate::logic::card::{Card, Rank, Suit};
     use crate::logic::game_hand::Street;
+    use crate::logic::hand_analysis::HandRanking;
+    use crate::logic::variant::Variant;
 
     #[test]
     fn flop_four_flush_draw() {
@@ -1016,5 +1177,43 @@ mod tests {
 	    }
 	);
     }
+
+    /// in Omaha the best hand must use exactly two hole cards,
+    /// so four spades on the board with one spade in the hole is not a flush,
+    /// and neither is four hearts in the hole with one on the board
+    #[test]
+    fn omaha_must_use_two_hole_cards() {
+        let mut bot0 = Player::new_bot(200);
+	bot0.is_active = true;
+	bot0.index = Some(0);
+	bot0.hole_cards = vec![
+	    Card { rank: Rank::Ace, suit: Suit::Spade },
+	    Card { rank: Rank::King, suit: Suit::Heart },
+	    Card { rank: Rank::Queen, suit: Suit::Heart },
+	    Card { rank: Rank::Eight, suit: Suit::Heart },
+	];
+
+        let mut gamehand = GameHand::with_variant(Variant::Omaha, 2, &[Some(bot0.clone())]);
+	gamehand.street = Street::River;
+	gamehand.flop = Some(vec![
+	    Card { rank: Rank::Two, suit: Suit::Spade },
+	    Card { rank: Rank::Five, suit: Suit::Spade },
+	    Card { rank: Rank::Nine, suit: Suit::Spade },
+	]);
+	gamehand.turn = Some(Card { rank: Rank::Jack, suit: Suit::Spade });
+	gamehand.river = Some(Card { rank: Rank::Three, suit: Suit::Heart });
+
+	let best_hand = bot0.determine_best_hand(&gamehand).unwrap();
+	assert_eq!(best_hand.hand_ranking, HandRanking::HighCard);
+	// the best we can do is A-K with three board cards
+	assert_eq!(best_hand.constituent_cards[0].rank, Rank::Ace);
+	assert_eq!(best_hand.kickers[3].rank, Rank::King);
+
+	// in Hold'em, the Ace alone would make a flush
+	gamehand.variant = Variant::Holdem;
+	bot0.hole_cards.truncate(2);
+	let best_hand = bot0.determine_best_hand(&gamehand).unwrap();
+	assert_eq!(best_hand.hand_ranking, HandRanking::Flush);
+    }
     
 }
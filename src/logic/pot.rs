@@ -5,7 +5,7 @@ use uuid::Uuid;
 
 /// A pot keeps track of the total money, and which player (indices) contributed
 /// A game hand can have multiple pots, when players go all-in, and betting continues
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Pot {
     pub money: u32,                        // total amount in this pot
     contributions: HashMap<Uuid, u32>, // which players have contributed to the pot, and how much
@@ -33,11 +33,16 @@ impl Pot {
     pub fn is_elligible(&self, id: &Uuid) -> bool {
 	self.contributions.contains_key(id)
     }
+
+    /// how many players put money into the pot
+    pub fn num_contributors(&self) -> usize {
+	self.contributions.len()
+    }
 }
 
 /// The pot manager keeps track of how many pots there are and which players
 /// how contributed how much to each.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PotManager {
     pots: Vec<Pot>,
 }
@@ -69,6 +74,27 @@ impl PotManager {
         self.pots.iter().map(|x| x.money).sum()
     }
     
+    /// dead money (e.g. a big blind ante) goes into the main pot, but does not count as any
+    /// player's contribution, so it doesn't change how much anyone needs to put in to be elligible for a pot
+    pub fn add_dead_money(&mut self, amount: u32) {
+        self.pots[0].money += amount;
+    }
+
+    /// when there is more than one board (e.g. the board is run multiple times),
+    /// each board is played for an even share of every pot.
+    /// This keeps only the share for the given board, where the first board gets any odd chips
+    pub fn split_for_board(&mut self, board_index: usize, num_boards: usize) {
+	let num_boards = num_boards as u32;
+	for pot in self.pots.iter_mut() {
+	    let share = pot.money / num_boards;
+	    pot.money = if board_index == 0 {
+		share + pot.money % num_boards
+	    } else {
+		share
+	    };
+	}
+    }
+
     /// given a player id and an amount they need to contribute to the pot
     /// and whether this is putting them all-in), this method puts the proper
     /// amount into the proper pot(s), and possibly create and redistribute into a new side pot
@@ -0,0 +1,183 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::variant::Variant;
+
+/// When a mixed game moves on to the next variant in its rotation
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationSchedule {
+    EveryOrbit, // one hand for each player at the table, so everyone gets the button once
+    EveryNHands(u32),
+    DealersChoice, // every orbit, the player on the button picks the next game
+}
+
+/// A mixed game is usually switched every orbit
+impl Default for RotationSchedule {
+    fn default() -> Self {
+	RotationSchedule::EveryOrbit
+    }
+}
+
+impl fmt::Display for RotationSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+	let output = match self {
+	    RotationSchedule::EveryOrbit => "every_orbit".to_owned(),
+	    RotationSchedule::EveryNHands(num_hands) => format!("every_{}_hands", num_hands),
+	    RotationSchedule::DealersChoice => "dealers_choice".to_owned(),
+	};
+	write!(f, "{}", output)
+    }
+}
+
+/// A mixed game (e.g. HORSE) rotates through a list of variants, dealing each one
+/// for a number of hands before switching to the next
+#[derive(Debug)]
+pub struct GameRotation {
+    variants: Vec<Variant>,
+    schedule: RotationSchedule,
+    current_idx: usize, // the index of the variant currently being dealt
+    hands_left: Option<u32>, // how many more hands of the current variant, None until the first hand is dealt
+    awaiting_choice: bool, // in dealer's choice, the button still needs to pick the next game
+}
+
+impl GameRotation {
+    pub fn new(variants: Vec<Variant>, schedule: RotationSchedule) -> Self {
+	assert!(!variants.is_empty(), "a rotation needs at least one variant");
+	Self {
+	    variants,
+	    schedule,
+	    current_idx: 0,
+	    hands_left: None,
+	    awaiting_choice: false,
+	}
+    }
+
+    pub fn variants(&self) -> &[Variant] {
+	&self.variants
+    }
+
+    pub fn schedule(&self) -> RotationSchedule {
+	self.schedule
+    }
+
+    pub fn current_variant(&self) -> Variant {
+	self.variants[self.current_idx]
+    }
+
+    /// the variant that comes after the current one in the list.
+    /// In dealer's choice, this is what gets dealt if the button doesn't pick
+    pub fn next_variant(&self) -> Variant {
+	self.variants[(self.current_idx + 1) % self.variants.len()]
+    }
+
+    /// the countdown of hands until the next switch (including the current hand)
+    pub fn hands_until_switch(&self) -> Option<u32> {
+	self.hands_left
+    }
+
+    pub fn is_awaiting_choice(&self) -> bool {
+	self.awaiting_choice
+    }
+
+    /// called before each hand is dealt. If the current variant just started,
+    /// then the countdown starts, where an orbit is one hand per player at the table
+    pub fn start_hand(&mut self, num_players: u32) {
+	if self.hands_left.is_none() {
+	    let num_hands = match self.schedule {
+		RotationSchedule::EveryNHands(num_hands) => num_hands,
+		RotationSchedule::EveryOrbit | RotationSchedule::DealersChoice => num_players,
+	    };
+	    self.hands_left = Some(num_hands.max(1));
+	}
+    }
+
+    /// called after each hand is played. Once the countdown runs out, we move to the next
+    /// variant, or in dealer's choice, we wait for the button to pick one
+    pub fn finish_hand(&mut self) {
+	let hands_left = self.hands_left.unwrap_or(1).saturating_sub(1);
+	if hands_left > 0 {
+	    self.hands_left = Some(hands_left);
+	    return;
+	}
+	self.hands_left = None;
+	match self.schedule {
+	    RotationSchedule::DealersChoice => self.awaiting_choice = true,
+	    _ => self.current_idx = (self.current_idx + 1) % self.variants.len(),
+	}
+    }
+
+    /// in dealer's choice, set the next variant to be dealt.
+    /// Returns false if we aren't waiting on a choice, or the variant isn't part of the rotation
+    pub fn choose(&mut self, variant: Variant) -> bool {
+	if !self.awaiting_choice {
+	    return false;
+	}
+	if let Some(idx) = self.variants.iter().position(|v| *v == variant) {
+	    self.current_idx = idx;
+	    self.awaiting_choice = false;
+	    true
+	} else {
+	    false
+	}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn horse() -> Vec<Variant> {
+	vec![
+	    Variant::Holdem,
+	    Variant::OmahaHiLo,
+	    Variant::Razz,
+	    Variant::SevenCardStud,
+	    Variant::SevenCardStudHiLo,
+	]
+    }
+
+    #[test]
+    fn switch_every_n_hands() {
+	let mut rotation = GameRotation::new(horse(), RotationSchedule::EveryNHands(2));
+	rotation.start_hand(6);
+	assert_eq!(rotation.current_variant(), Variant::Holdem);
+	assert_eq!(rotation.hands_until_switch(), Some(2));
+	rotation.finish_hand();
+	rotation.start_hand(6);
+	assert_eq!(rotation.current_variant(), Variant::Holdem);
+	assert_eq!(rotation.hands_until_switch(), Some(1));
+	rotation.finish_hand();
+	rotation.start_hand(6);
+	assert_eq!(rotation.current_variant(), Variant::OmahaHiLo);
+	assert_eq!(rotation.hands_until_switch(), Some(2));
+    }
+
+    #[test]
+    fn switch_every_orbit_and_wrap_around() {
+	let mut rotation = GameRotation::new(vec![Variant::Razz, Variant::SevenCardStud], RotationSchedule::EveryOrbit);
+	for variant in [Variant::Razz, Variant::SevenCardStud, Variant::Razz] {
+	    for _ in 0..3 {
+		rotation.start_hand(3);
+		assert_eq!(rotation.current_variant(), variant);
+		rotation.finish_hand();
+	    }
+	}
+    }
+
+    #[test]
+    fn dealers_choice() {
+	let mut rotation = GameRotation::new(horse(), RotationSchedule::DealersChoice);
+	// nobody gets to choose in the middle of an orbit
+	assert!(!rotation.choose(Variant::Razz));
+	rotation.start_hand(1);
+	rotation.finish_hand();
+	assert!(rotation.is_awaiting_choice());
+	// the button can only pick a game from the rotation
+	assert!(!rotation.choose(Variant::ShortDeck));
+	assert!(rotation.choose(Variant::Razz));
+	assert!(!rotation.is_awaiting_choice());
+	assert_eq!(rotation.current_variant(), Variant::Razz);
+    }
+}
@@ -0,0 +1,29 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A table can let a player opt into a voluntary straddle of twice the big blind,
+/// which acts like a third blind and pushes back the preflop action
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Straddle {
+    UnderTheGun, // the player after the big blind straddles, and acts last preflop
+    Button, // the button straddles (Mississippi), action starts under the gun and the button acts last
+}
+
+impl fmt::Display for Straddle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+	let output = match self {
+	    Straddle::UnderTheGun => "under_the_gun",
+	    Straddle::Button => "button",
+	};
+	write!(f, "{}", output)
+    }
+}
+
+impl Straddle {
+    /// the straddle is always twice the big blind
+    pub fn amount(&self, big_blind: u32) -> u32 {
+	2 * big_blind
+    }
+}
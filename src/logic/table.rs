@@ -3,15 +3,27 @@ use json::object;
 use std::collections::{HashMap, VecDeque};
 use std::sync::Mutex;
 
-use super::card::Card;
+use super::card::{Card, Rank};
 use super::deck::{Deck, StandardDeck};
 use super::game_hand::{GameHand, Street, HandStatus};
+use super::hand_analysis::{HandResult, LowHandResult};
 use super::bot;
 
 use super::player::{Player, PlayerAction, PlayerConfig};
+use super::variant::Variant;
+use super::betting_structure::BettingStructure;
+use super::straddle::Straddle;
+use super::rotation::{GameRotation, RotationSchedule};
+use super::tournament::{BlindLevel, Tournament};
+use super::freezeout::Freezeout;
+use super::knockout::Knockout;
+use super::icm::{payout_structure, Deal, DealMethod};
+use super::wild_cards::WildCards;
 use crate::hub::TableHub;
 
-use crate::messages::{AdminCommand, GameOver, JoinTableError, MetaAction, Returned, ReturnedReason, WsMessage};
+use crate::messages::{
+    AdminCommand, GameOver, JoinTableError, MetaAction, Reseated, Returned, ReturnedReason, TableReport, WsMessage,
+};
 
 use std::{cmp, sync::Arc, thread, time};
 
@@ -20,16 +32,82 @@ use uuid::Uuid;
 // any game that runs for too long without a human will end, rather than looping indefinitely
 const NON_HUMAN_HANDS_LIMIT: u32 = 3;
 
+/// The optional rules that a table is created with, besides its variant, blinds, and buy in.
+/// Anything that is left out is the same as in a plain cash game
+#[derive(Debug)]
+pub struct TableOptions {
+    pub rotation: Option<GameRotation>, // a mixed game rotates through several variants
+    pub tournament: Option<Tournament>, // a sit-and-go tournament instead of a cash game
+    pub tournament_name: Option<String>, // the multi-table tournament the table is part of, which the hub directs
+    pub freezeout: Option<Freezeout>, // players with no chips left are eliminated, after any rebuys
+    pub knockout: Option<Knockout>, // every player carries a bounty for whoever busts them
+    pub betting_structure: Option<BettingStructure>, // None for the variant's usual betting structure
+    pub deck: Option<Box<dyn Deck>>, // a custom deck, e.g. for tests, rather than the variant's deck
+    pub ante: u32,
+    pub big_blind_ante: bool, // the big blind pays the ante once for the whole table
+    pub straddle: Option<Straddle>, // which player (if any) may opt into straddling
+    pub bomb_pot_every: u32, // every Nth hand is a bomb pot (0 for never)
+    pub max_runouts: u8, // once everyone is all-in, the players can vote to run the board up to this many times
+    pub rabbit_hunting: bool,
+    pub wild_cards: WildCards,
+    pub seven_deuce_bounty: u32, // 0 for no seven-deuce bounty
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        Self {
+            rotation: None,
+            tournament: None,
+            tournament_name: None,
+            freezeout: None,
+            knockout: None,
+            betting_structure: None,
+            deck: None,
+            ante: 0,
+            big_blind_ante: false,
+            straddle: None,
+            bomb_pot_every: 0,
+            max_runouts: 1, // the board is run once
+            rabbit_hunting: false,
+            wild_cards: WildCards::default(),
+            seven_deuce_bounty: 0,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Table {
     hub_addr: Option<Addr<TableHub>>, // needs to be able to communicate back to the hub sometimes
     pub name: String,
+    variant: Variant, // which game of poker we are dealing (this hand, in a mixed game)
+    rotation: Option<GameRotation>, // a mixed game rotates through several variants
+    tournament: Option<Tournament>, // a sit-and-go tournament instead of a cash game
+    tournament_name: Option<String>, // the multi-table tournament this table is part of, which the hub directs
+    tournament_over: bool, // the multi-table tournament has a winner, so the table closes
+    freezeout: Option<Freezeout>, // players with no chips left are eliminated, after any rebuys
+    knockout: Option<Knockout>, // every player carries a bounty for whoever busts them
+    betting_structure: BettingStructure, // e.g. no-limit or fixed-limit
+    betting_structure_configured: bool, // if not, each variant of a mixed game is dealt with its usual betting structure
     deck: Box<dyn Deck>,
     players: [Option<Player>; 9], // 9 spots where players can sit
     player_ids_to_configs: HashMap<Uuid, PlayerConfig>,
     max_players: u8, // how many will we let in the game
-    small_blind: u32,
-    big_blind: u32,
+    small_blind: u32, // in stud games this is the bring-in
+    big_blind: u32, // in stud games this is a full bet
+    ante: u32, // every player antes at the start of each hand
+    big_blind_ante: bool, // if true, the big blind pays the ante once for the whole table instead
+    straddle: Option<Straddle>, // which player (if any) may opt into straddling
+    bomb_pot_every: u32, // every Nth hand is a bomb pot (0 for never)
+    bomb_pot_amount: u32, // how much everyone puts in for a bomb pot
+    bomb_pot_next_hand: bool, // the admin asked for a bomb pot on demand
+    max_runouts: u8, // once everyone is all-in, the players can vote to run the board up to this many times
+    rabbit_hunting: bool, // can players see the rest of the board after a hand ends early
+    rabbit_board: Option<Vec<Card>>, // the board of the last hand, if it ended early and can be rabbit hunted
+    wild_cards: WildCards, // any jokers in the deck, and which ranks are wild
+    seven_deuce_bounty: u32, // whoever wins a hand with 7-2 and shows it collects this from everyone else (0 for never)
+    seven_deuce_winners: Vec<Uuid>, // winners of the last hand with 7-2, who collect the bounty once they show
+    hand_over: bool, // the last hand is over, and its players can still show their cards
+    deal: Option<Deal>, // a proposed deal to end the game, which the players are deciding on
     buy_in: u32,
     player_action_timeout: u32, // how long to wait for a single action
     password: Option<String>,
@@ -44,12 +122,35 @@ impl Default for Table {
         Self {
             hub_addr: None,
             name: "Table".to_owned(),
+            variant: Variant::Holdem,
+            rotation: None,
+            tournament: None,
+            tournament_name: None,
+            tournament_over: false,
+            freezeout: None,
+            knockout: None,
+            betting_structure: BettingStructure::NoLimit,
+            betting_structure_configured: false,
             deck: Box::new(StandardDeck::new()),
             players: Default::default(),
             player_ids_to_configs: HashMap::<Uuid, PlayerConfig>::new(),
             max_players: 9,
             small_blind: 4,
             big_blind: 8,
+            ante: 0,
+            big_blind_ante: false,
+            straddle: None,
+            bomb_pot_every: 0,
+            bomb_pot_amount: 16,
+            bomb_pot_next_hand: false,
+            max_runouts: 1,
+            rabbit_hunting: false,
+            rabbit_board: None,
+            wild_cards: WildCards::default(),
+            seven_deuce_bounty: 0,
+            seven_deuce_winners: vec![],
+            hand_over: false,
+            deal: None,
             buy_in: 1000,
 	    player_action_timeout: 45,
             password: None,
@@ -62,32 +163,73 @@ impl Default for Table {
 
 impl Table {
     /// the address of the TableHub is optional so that unit tests need not worry about it
-    /// We can pass in a custom Deck object, but if not, we will just construct a StandardDeck
+    /// The options can have a custom Deck object, but if not, we will just construct the variant's deck
     pub fn new(
         hub_addr: Addr<TableHub>,
         name: String,
-        deck_opt: Option<Box<dyn Deck>>,
+        variant: Variant,
         max_players: u8, // how many will we let in the game
         small_blind: u32,
         big_blind: u32,
         buy_in: u32,
         password: Option<String>,
 	admin_id: Uuid,
+	options: TableOptions,
     ) -> Self {
+	let TableOptions {
+	    rotation,
+	    tournament,
+	    tournament_name,
+	    freezeout,
+	    knockout,
+	    betting_structure,
+	    deck: deck_opt,
+	    ante,
+	    big_blind_ante,
+	    straddle,
+	    bomb_pot_every,
+	    max_runouts,
+	    rabbit_hunting,
+	    wild_cards,
+	    seven_deuce_bounty,
+	} = options;
         let deck = if let Some(deck) = deck_opt {
 	    deck
         } else {
-            Box::new(StandardDeck::new())
+            variant.new_deck(wild_cards.num_jokers)
         };
         Table {
             hub_addr: Some(hub_addr),
             name,
+            variant,
+            rotation,
+            tournament,
+            tournament_name,
+            tournament_over: false,
+            freezeout,
+            knockout,
+            betting_structure: betting_structure.unwrap_or_else(|| variant.default_betting_structure()),
+            betting_structure_configured: betting_structure.is_some(),
             deck,
             players: Default::default(),
             player_ids_to_configs: HashMap::<Uuid, PlayerConfig>::new(),
             max_players,
             small_blind,
             big_blind,
+            ante,
+            big_blind_ante,
+            straddle,
+            bomb_pot_every,
+            bomb_pot_amount: 2 * big_blind, // until the admin changes it
+            bomb_pot_next_hand: false,
+            max_runouts,
+            rabbit_hunting,
+            rabbit_board: None,
+            wild_cards,
+            seven_deuce_bounty,
+            seven_deuce_winners: vec![],
+            hand_over: false,
+            deal: None,
             buy_in,
 	    player_action_timeout: 45,
             password,
@@ -133,11 +275,8 @@ impl Table {
         for (i, player_spot) in self.players.iter().enumerate() {
             if let Some(player) = player_spot {
 		game_state["your_index"] = i.into();
-		if player.hole_cards.len() == 2 {
-		    game_state["hole_cards"] = format!("{}{}",
-							  player.hole_cards[0],
-							  player.hole_cards[1])
-			.into();
+		if !player.hole_cards.is_empty() {
+		    game_state["hole_cards"] = player.hole_cards_string().into();
 		} else {
 		    game_state["hole_cards"] = json::Null;
 		}
@@ -164,9 +303,21 @@ impl Table {
         let mut state_message = object! {
             msg_type: "game_state".to_owned(),
             name: self.name.to_owned(),
+            variant: self.variant.to_string(),
+            betting_structure: self.betting_structure.to_string(),
             max_players: self.max_players,
             small_blind: self.small_blind,
             big_blind: self.big_blind,
+            ante: self.ante,
+            big_blind_ante: self.big_blind_ante,
+            straddle: self.straddle.map(|straddle| straddle.to_string()),
+            bomb_pot_every: self.bomb_pot_every,
+            bomb_pot_amount: self.bomb_pot_amount,
+            max_runouts: self.max_runouts,
+            rabbit_hunting: self.rabbit_hunting,
+            jokers: self.wild_cards.num_jokers,
+            wild_ranks: self.wild_cards.ranks_string(),
+            seven_deuce_bounty: self.seven_deuce_bounty,
             buy_in: self.buy_in,
             password: self.password.to_owned(),	    
             button_idx: self.button_idx,
@@ -176,6 +327,56 @@ impl Table {
 	    all_in_situation: all_in_situation,
 	};
 
+	if let Some(rotation) = &self.rotation {
+	    state_message["rotation"] = rotation.variants().iter().map(|v| v.to_string()).collect::<Vec<_>>().into();
+	    state_message["rotation_schedule"] = rotation.schedule().to_string().into();
+	    if let Some(hands_until_switch) = rotation.hands_until_switch() {
+		state_message["hands_until_switch"] = hands_until_switch.into();
+	    }
+	    if rotation.is_awaiting_choice() {
+		state_message["choosing_variant"] = true.into();
+	    } else if !matches!(rotation.schedule(), RotationSchedule::DealersChoice) {
+		state_message["next_variant"] = rotation.next_variant().to_string().into();
+	    }
+	}
+
+	if let Some(tournament_name) = &self.tournament_name {
+	    state_message["tournament_name"] = tournament_name.clone().into();
+	}
+	if let Some(knockout) = &self.knockout {
+	    let bounties: Vec<json::JsonValue> = self.players
+		.iter()
+		.flatten()
+		.map(|player| object! {
+		    index: player.index,
+		    bounty: knockout.bounty(player.id),
+		    collected: knockout.collected(player.id),
+		})
+		.collect();
+	    state_message["knockout"] = object! {
+		progressive: knockout.is_progressive(),
+		bounties: bounties,
+	    };
+	}
+	if let Some(freezeout) = &self.freezeout {
+	    state_message["freezeout"] = object! {
+		rebuys_open: freezeout.rebuys_open(self.hand_num),
+		add_on_open: freezeout.add_on_open(self.hand_num),
+		late_entry_open: freezeout.late_entry_open(self.hand_num),
+		prize_pool: freezeout.prize_pool(),
+	    };
+	}
+	if let Some(deal) = self.deal_message() {
+	    state_message["deal"] = deal;
+	}
+	if let Some(tournament) = &self.tournament {
+	    state_message["tournament"] = object! {
+		started: tournament.has_started(),
+		level: tournament.level(),
+		blind_schedule: tournament.schedule().to_string(),
+	    };
+	}
+
 	if let Some(mut extra_fields) = extra_fields {
 	    // extra fields were provided, so add to the state
 	    for (k, v) in extra_fields.entries_mut() {
@@ -203,20 +404,24 @@ impl Table {
 		if player.is_sitting_out {
                     player_info["is_sitting_out"] = true.into();		    
 		}
+		if player.wants_to_straddle {
+                    player_info["wants_to_straddle"] = true.into();
+		}
 		if player.is_all_in() {
                     player_info["is_all_in"] = true.into();
 		}
-		if let Some(last_action) = player.last_action {
+		if let Some(last_action) = &player.last_action {
                     player_info["last_action"] = last_action.to_string().into();
 		}
+		if !player.up_cards.is_empty() {
+		    // up cards are face up, so everyone gets to see them
+		    player_info["up_cards"] = player.up_cards_string().into();
+		}
 		if all_in_situation && player.is_active {
 		    // everyone left is all_in, so show all the cards
-		    // (check for length 2 to be safe, but should not be an issue)
-		    if player.hole_cards.len() == 2 {
-			player_info["hole_cards"] = format!("{}{}",
-							    player.hole_cards[0],
-							    player.hole_cards[1])
-			    .into();
+		    // (check for empty to be safe, but should not be an issue)
+		    if !player.hole_cards.is_empty() {
+			player_info["hole_cards"] = player.hole_cards_string().into();
 		    } else {
 			player_info["hole_cards"] = json::Null;
 		    }
@@ -229,6 +434,7 @@ impl Table {
 			    Street::Turn => {player_info["turn_cont"] = contributions[i].into()}
 			    Street::River => {player_info["river_cont"] = contributions[i].into()}
 			    Street::ShowDown => (),
+			    _ => {player_info[format!("{}_cont", street)] = contributions[i].into()}
 			}
 		    }
 		}
@@ -241,8 +447,13 @@ impl Table {
 
 	if let Some(gamehand) = gamehand_opt {
 	    state_message["street"] = gamehand.street.to_string().into();
+	    // stud and draw games never get a flop, turn, or river
+	    state_message["has_board"] = gamehand.variant.has_board().into();
 	    state_message["current_bet"] = gamehand.current_bet.into();
 	    state_message["min_raise"] = gamehand.min_raise.into();	    
+	    if let Some(straddle_idx) = gamehand.straddle_idx {
+		state_message["straddle_idx"] = straddle_idx.into();
+	    }
 	    
 	    if let Some(flop) = &gamehand.flop {
 		state_message["flop"] = format!(
@@ -260,9 +471,25 @@ impl Table {
             state_message["river"] = format!("{}", river).into();
             }
             state_message["pots"] = gamehand.pot_repr().into();
+	    if !gamehand.extra_boards.is_empty() {
+		// the first board is the usual flop, turn, and river
+		state_message["boards"] = gamehand.boards()
+		    .iter()
+		    .map(|board| board.iter().map(|card| card.to_string()).collect::<String>())
+		    .collect::<Vec<_>>()
+		    .into();
+	    }
 
 	    if let Some(index_to_act) = gamehand.index_to_act {
 		state_message["index_to_act"] = index_to_act.into();
+		if let Some(max_bet) = gamehand.betting_structure.max_bet(gamehand, index_to_act) {
+		    state_message["max_bet"] = max_bet.into();
+		}
+		if gamehand.betting_structure.is_fixed_limit() {
+		    // the front end doesn't need to ask how much to bet, since it is always the same
+		    state_message["fixed_bet"] = gamehand.fixed_limit_bet().into();
+		    state_message["is_capped"] = gamehand.is_capped().into();
+		}
 	    }
 	}
 
@@ -295,6 +522,10 @@ impl Table {
         player_config: PlayerConfig,
         password: Option<String>,
     ) -> Result<usize, JoinTableError> {
+        if self.tournament_name.is_some() {
+            // the hub seats the players of a multi-table tournament itself
+            return Err(JoinTableError::TournamentStarted);
+        }
         if let Some(game_password) = &self.password {
             if let Some(given_password) = password {
                 if game_password.ne(&given_password) {
@@ -342,8 +573,21 @@ impl Table {
             return Err(JoinTableError::GameIsFull);
         }
 
+        if self.tournament.as_ref().is_some_and(|tournament| tournament.has_started()) {
+            // nobody can join a tournament once the cards are in the air
+            return Err(JoinTableError::TournamentStarted);
+        }
+
+        if self.freezeout.as_ref().is_some_and(|freezeout| !freezeout.can_enter(player.id, self.hand_num)) {
+            return Err(JoinTableError::LateEntryClosed);
+        }
+
         for (i, player_spot) in self.players.iter_mut().enumerate() {
             if player_spot.is_none() {
+		if let Some(freezeout) = self.freezeout.as_mut() {
+		    // the buy in goes into the prize pool
+		    freezeout.buy_in(player.id, player_config.name.clone(), player.money);
+		}
 		player.index = Some(i); // assign the index at the table for the player
                 *player_spot = Some(player);
                 self.player_ids_to_configs
@@ -398,11 +642,28 @@ impl Table {
                 if let Some(player) = player_spot {
                     if !self.player_ids_to_configs.contains_key(&player.id) {
                         println!("player is no longer in the config");
+			if let Some(tournament) = self.tournament.as_mut() {
+			    // leaving a tournament is the same as busting out of it
+			    tournament.eliminate(player.id);
+			}
                         *player_spot = None;
 			
                     }
 		}
 	    }
+
+	    // a deal can't go through once anyone in it has left, or if they take too long to decide
+	    self.call_off_stale_deal();
+	    if self.deal.as_ref().is_some_and(|deal| deal.is_unanimous()) {
+		self.record_deal();
+		self.send_tournament_results();
+		break;
+	    }
+	    if self.deal.is_some() {
+		// no hands are dealt while the players decide on a deal
+		self.sleep_loop(1.0, incoming_meta_actions, between_hands, None);
+		continue;
+	    }
  	    
             if let Some(limit) = hand_limit {
                 if self.hand_num > limit {
@@ -433,8 +694,48 @@ impl Table {
                 break;
             }
 
+	    // in a freezeout, a player who is out of chips (and rebuys) is out of the game
+	    self.eliminate_broke_players();
+	    if self.freezeout_is_over() {
+		println!("the freezeout is over");
+		break;
+	    }
+
+	    // once the director crowns the winner of a multi-table tournament, the final table closes
+	    if self.tournament_over {
+		println!("the multi-table tournament is over");
+		self.return_tournament_players();
+		break;
+	    }
+
+	    // players can leave a tournament between hands, which might leave only the winner
+	    self.crown_tournament_winner();
+	    if self.tournament.as_ref().is_some_and(|tournament| tournament.is_over()) {
+		self.send_tournament_results();
+		break;
+	    }
+	    if !self.start_tournament_if_full() {
+		// a sit-and-go doesn't deal any hands until every seat is filled
+		self.sleep_loop(1.0, incoming_meta_actions, between_hands, None);
+		continue;
+	    }
+
+	    // in a mixed game, the variant can change from hand to hand
+	    self.set_up_variant(incoming_meta_actions);
+	    let stacks_before: Vec<u32> = self.players.iter().map(|spot| spot.as_ref().map_or(0, |player| player.money)).collect();
 	    let was_played = self.play_one_hand(&incoming_actions, &incoming_meta_actions);
 	    if was_played {
+		if let Some(rotation) = self.rotation.as_mut() {
+		    rotation.finish_hand();
+		}
+		self.finish_multi_table_hand(&stacks_before);
+		if self.tournament.is_some() {
+		    self.finish_tournament_hand(&stacks_before);
+		    if self.tournament.as_ref().is_some_and(|tournament| tournament.is_over()) {
+			self.send_tournament_results();
+			break;
+		    }
+		}
 		// only increment the hand num and find a new button if we indeed played a hand.
 		// if there are not enough players and/or active players, a hand is not dealt/played
 		self.hand_num += 1;
@@ -450,16 +751,583 @@ impl Table {
 	    self.sleep_loop(1.0, &incoming_meta_actions, between_hands, None);    	    
 	    
         }
+        self.send_prize_pool();
         println!("about to send the gameover signal to the hub");
         // the game is ending, so tell that to the hub
         if let Some(hub_addr) = &self.hub_addr {
             // tell the hub that we left
             hub_addr.do_send(GameOver {
                 table_name: self.name.clone(),
+                prizes: self.deal_prizes(),
             });
         }
     }
 
+    /// a sit-and-go starts once every seat is filled, with everyone on the same starting stack (the buy in).
+    /// Returns false if the table is still waiting for the tournament to start
+    fn start_tournament_if_full(&mut self) -> bool {
+	if self.tournament.as_ref().is_none_or(|tournament| tournament.has_started()) {
+	    return true; // a cash game, or a tournament that is already going
+	}
+	let num_seated = self.players.iter().flatten().count();
+	if num_seated < self.max_players.into() {
+	    return false;
+	}
+	let entrants = self.players
+	    .iter()
+	    .flatten()
+	    .map(|player| {
+		let name = self.player_ids_to_configs.get(&player.id).and_then(|config| config.name.clone());
+		(player.id, name.unwrap_or_default())
+	    })
+	    .collect();
+	let tournament = self.tournament.as_mut().unwrap();
+	tournament.start(entrants);
+	let level = tournament.current_level();
+	for player in self.players.iter_mut().flatten() {
+	    player.money = self.buy_in;
+	}
+	self.small_blind = level.small_blind;
+	self.big_blind = level.big_blind;
+	self.ante = level.ante;
+	let message = object! {
+	    msg_type: "tournament_started".to_owned(),
+	    num_players: num_seated,
+	    starting_stack: self.buy_in,
+	};
+	PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+	true
+    }
+
+    /// after a tournament hand, anyone who busted is out of the tournament and gives up their seat.
+    /// Whoever had the smaller stack going into the hand finishes lower. Once only one player is left,
+    /// they win the tournament. Then the blinds go up if the level is over
+    fn finish_tournament_hand(&mut self, stacks_before: &[u32]) {
+	let busted = self.busted_seats(stacks_before);
+	let tournament = match self.tournament.as_mut() {
+	    Some(tournament) => tournament,
+	    None => return,
+	};
+	for i in busted {
+	    let player = self.players[i].take().unwrap();
+	    let position = tournament.eliminate(player.id);
+	    println!("player {} busted out of the tournament in position {:?}", player.id, position);
+	    let message = object! {
+		msg_type: "busted".to_owned(),
+		index: i,
+		player_name: self.player_ids_to_configs.get(&player.id).and_then(|config| config.name.clone()),
+		position: position,
+	    };
+	    PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+	    if !player.human_controlled {
+		self.player_ids_to_configs.remove(&player.id);
+	    }
+	    // a busted human keeps their connection to the table, so they still see the results
+	}
+	if self.crown_tournament_winner() {
+	    return;
+	}
+	let tournament = self.tournament.as_mut().unwrap();
+	if tournament.finish_hand() {
+	    let (level_num, level) = (tournament.level(), tournament.current_level());
+	    self.set_blind_level(level_num, level);
+	}
+    }
+
+    /// the seats of everyone who busted in the last hand, starting with whoever had the smallest stack going into it
+    fn busted_seats(&self, stacks_before: &[u32]) -> Vec<usize> {
+	let mut busted: Vec<usize> = (0..self.players.len())
+	    .filter(|i| self.players[*i].as_ref().is_some_and(|player| player.money == 0))
+	    .collect();
+	busted.sort_by_key(|i| stacks_before[*i]);
+	busted
+    }
+
+    /// the blinds went up in a tournament, starting with the next hand
+    fn set_blind_level(&mut self, level_num: usize, level: BlindLevel) {
+	self.small_blind = level.small_blind;
+	self.big_blind = level.big_blind;
+	self.ante = level.ante;
+	let message = object! {
+	    msg_type: "blinds_up".to_owned(),
+	    level: level_num,
+	    small_blind: level.small_blind,
+	    big_blind: level.big_blind,
+	    ante: level.ante,
+	};
+	PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+    }
+
+    /// after a hand at one table of a multi-table tournament, the busted players go back to the lobby,
+    /// and the hub is told how the hand went, so the director can balance the tables
+    fn finish_multi_table_hand(&mut self, stacks_before: &[u32]) {
+	let tournament_name = match &self.tournament_name {
+	    Some(tournament_name) => tournament_name.clone(),
+	    None => return,
+	};
+	let mut busted = vec![];
+	for i in self.busted_seats(stacks_before) {
+	    let player = self.players[i].take().unwrap();
+	    let message = object! {
+		msg_type: "busted".to_owned(),
+		index: i,
+		player_name: self.player_ids_to_configs.get(&player.id).and_then(|config| config.name.clone()),
+	    };
+	    PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+	    if let Some(config) = self.player_ids_to_configs.remove(&player.id) {
+		if let (Some(hub_addr), true) = (&self.hub_addr, player.human_controlled) {
+		    hub_addr.do_send(Returned {
+			config,
+			reason: ReturnedReason::Busted,
+		    });
+		}
+	    }
+	    busted.push(player.id);
+	}
+	if let Some(hub_addr) = &self.hub_addr {
+	    hub_addr.do_send(TableReport {
+		tournament_name,
+		table_name: self.name.clone(),
+		busted,
+		hand_played: true,
+	    });
+	}
+    }
+
+    /// once a multi-table tournament is over, whoever is still seated (i.e. the winner) goes back to the lobby
+    fn return_tournament_players(&mut self) {
+	for player_spot in self.players.iter_mut() {
+	    let player = match player_spot.take() {
+		Some(player) => player,
+		None => continue,
+	    };
+	    if let Some(config) = self.player_ids_to_configs.remove(&player.id) {
+		if let (Some(hub_addr), true) = (&self.hub_addr, player.human_controlled) {
+		    hub_addr.do_send(Returned {
+			config,
+			reason: ReturnedReason::TournamentOver,
+		    });
+		}
+	    }
+	}
+    }
+
+    /// in a freezeout, anyone with an empty stack who can't rebuy is eliminated, and goes back to the lobby.
+    /// They see the prize pool on the way out, since they won't be around when the freezeout ends
+    fn eliminate_broke_players(&mut self) {
+	let hand_num = self.hand_num;
+	let freezeout = match self.freezeout.as_mut() {
+	    Some(freezeout) => freezeout,
+	    None => return,
+	};
+	let mut busted = vec![];
+	for (i, player_spot) in self.players.iter_mut().enumerate() {
+	    let broke = player_spot
+		.as_ref()
+		.is_some_and(|player| player.money == 0 && !freezeout.can_rebuy(player.id, hand_num));
+	    if !broke {
+		continue;
+	    }
+	    let player = player_spot.take().unwrap();
+	    freezeout.eliminate(player.id);
+	    busted.push((i, player));
+	}
+	for (i, player) in busted {
+	    let message = object! {
+		msg_type: "eliminated".to_owned(),
+		index: i,
+		player_name: self.player_ids_to_configs.get(&player.id).and_then(|config| config.name.clone()),
+	    };
+	    PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+	    if let Some(prize_pool_message) = self.prize_pool_message() {
+		PlayerConfig::send_specific_message(&prize_pool_message.dump(), player.id, &self.player_ids_to_configs);
+	    }
+	    if let Some(config) = self.player_ids_to_configs.remove(&player.id) {
+		if let (Some(hub_addr), true) = (&self.hub_addr, player.human_controlled) {
+		    hub_addr.do_send(Returned {
+			config,
+			reason: ReturnedReason::Busted,
+		    });
+		}
+	    }
+	}
+    }
+
+    /// a freezeout is over once a single player has all the chips, and nobody can rebuy or join late
+    fn freezeout_is_over(&self) -> bool {
+	let freezeout = match &self.freezeout {
+	    Some(freezeout) => freezeout,
+	    None => return false,
+	};
+	let num_with_chips = self.players.iter().flatten().filter(|player| player.money > 0).count();
+	num_with_chips <= 1
+	    && !freezeout.late_entry_open(self.hand_num)
+	    && self.players.iter().flatten().all(|player| !freezeout.can_rebuy(player.id, self.hand_num))
+    }
+
+    /// a player who is out of chips buys back in for the starting stack.
+    /// Returns the message for the table, or None if they can't rebuy
+    fn rebuy(&mut self, id: Uuid) -> Option<json::JsonValue> {
+	let freezeout = self.freezeout.as_mut()?;
+	let player = self.players.iter_mut().flatten().find(|player| player.id == id)?;
+	if player.money > 0 || !freezeout.can_rebuy(id, self.hand_num) {
+	    return None;
+	}
+	player.money = self.buy_in;
+	freezeout.rebuy(id, self.buy_in);
+	Some(object! {
+	    msg_type: "rebuy".to_owned(),
+	    index: player.index,
+	    player_name: self.player_ids_to_configs.get(&id).and_then(|config| config.name.clone()),
+	    amount: self.buy_in,
+	    prize_pool: freezeout.prize_pool(),
+	})
+    }
+
+    /// a player takes the one-time add-on at the end of the rebuy window.
+    /// Returns the message for the table, or None if they can't take it
+    fn add_on(&mut self, id: Uuid) -> Option<json::JsonValue> {
+	let freezeout = self.freezeout.as_mut()?;
+	let player = self.players.iter_mut().flatten().find(|player| player.id == id)?;
+	let amount = freezeout.take_add_on(id, self.hand_num)?;
+	player.money += amount;
+	Some(object! {
+	    msg_type: "add_on".to_owned(),
+	    index: player.index,
+	    player_name: self.player_ids_to_configs.get(&id).and_then(|config| config.name.clone()),
+	    amount: amount,
+	    prize_pool: freezeout.prize_pool(),
+	})
+    }
+
+    /// who paid what into a freezeout's prize pool (and what they won), or None if this isn't a freezeout
+    fn prize_pool_message(&self) -> Option<json::JsonValue> {
+	let freezeout = self.freezeout.as_ref()?;
+	let ledger: Vec<json::JsonValue> = freezeout
+	    .ledger()
+	    .into_iter()
+	    .map(|purchases| object! {
+		player_name: purchases.player_name,
+		buy_ins: purchases.buy_ins,
+		rebuys: purchases.rebuys,
+		add_on: purchases.add_on,
+		total: purchases.total,
+		prize: purchases.prize,
+	    })
+	    .collect();
+	Some(object! {
+	    msg_type: "prize_pool".to_owned(),
+	    prize_pool: freezeout.prize_pool(),
+	    ledger: ledger,
+	})
+    }
+
+    /// when a freezeout ends, everyone still around sees who paid what into the prize pool
+    fn send_prize_pool(&self) {
+	if let Some(message) = self.prize_pool_message() {
+	    PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+	}
+    }
+
+    /// the payouts (for first place, second place, etc.) out of a freezeout's or sit-and-go's prize pool,
+    /// or None for a cash game, where the players decide the payouts themselves
+    fn prize_pool_payouts(&self) -> Option<Vec<u32>> {
+	if let Some(freezeout) = &self.freezeout {
+	    return Some(payout_structure(freezeout.prize_pool(), freezeout.num_entrants()));
+	}
+	let tournament = self.tournament.as_ref()?;
+	let num_entrants = tournament.num_entrants();
+	Some(payout_structure(self.buy_in * num_entrants as u32, num_entrants))
+    }
+
+    /// a player proposes to end the game by splitting the payouts (for first place, second place, etc.)
+    /// between everyone with chips left. Only a cash game takes the payouts from the player,
+    /// since a freezeout or sit-and-go pays out its own prize pool.
+    /// Players with no chips aren't part of the deal, and since bots can't accept one,
+    /// no deal can be made while a bot still has chips.
+    /// Returns the message for the table, or why the deal can't be made
+    fn propose_deal(&mut self, id: Uuid, method: DealMethod, payouts: Vec<u32>) -> Result<json::JsonValue, &'static str> {
+	if self.tournament_name.is_some() {
+	    return Err("A deal can't be made at one table of a multi-table tournament.");
+	}
+	if self.deal.is_some() {
+	    return Err("There is already a deal on the table.");
+	}
+	if self.tournament.as_ref().is_some_and(|tournament| !tournament.has_started()) {
+	    return Err("The tournament hasn't started yet.");
+	}
+	if self.freezeout.as_ref().is_some_and(|freezeout| freezeout.rebuys_open(self.hand_num)) {
+	    // a busted player could still buy back in, so everyone in the game might not be in the deal
+	    return Err("A deal can't be made until the rebuys are over.");
+	}
+	let players: Vec<&Player> = self.players.iter().flatten().filter(|player| player.money > 0).collect();
+	if !players.iter().any(|player| player.id == id) {
+	    return Err("Only a player with chips left can propose a deal.");
+	}
+	if players.len() < 2 {
+	    return Err("A deal needs at least two players.");
+	}
+	if players.iter().any(|player| !player.human_controlled) {
+	    return Err("A deal can't be made while a bot has chips left.");
+	}
+	let payouts = match self.prize_pool_payouts() {
+	    Some(_) if !payouts.is_empty() => {
+		return Err("The payouts at this table come from its prize pool.");
+	    }
+	    // only the places that the players left can finish in are part of the deal
+	    Some(prize_pool_payouts) => prize_pool_payouts.into_iter().take(players.len()).collect(),
+	    None if payouts.is_empty() || payouts.len() > players.len() => {
+		return Err("There must be a payout for at least first place, and no more payouts than players.");
+	    }
+	    None if payouts.windows(2).any(|pair| pair[0] < pair[1]) => {
+		return Err("The payouts can't go up from one place to the next.");
+	    }
+	    None if payouts.iter().try_fold(0u32, |total, payout| total.checked_add(*payout)).is_none() => {
+		// the splits are worked out in u32 chips, so the whole prize pool has to fit
+		return Err("The payouts add up to too much.");
+	    }
+	    None => payouts,
+	};
+	let stacks: Vec<(Uuid, u32)> = players.iter().map(|player| (player.id, player.money)).collect();
+	let deal = Deal::new(id, method, payouts, &stacks);
+	println!("player {} proposed a deal: {:?}", id, deal);
+	self.deal = Some(deal);
+	let mut message = self.deal_message().unwrap();
+	message["msg_type"] = "deal_proposed".into();
+	Ok(message)
+    }
+
+    /// the deal on the table, with both the ICM and chip chop splits for each player so that they can compare them
+    fn deal_message(&self) -> Option<json::JsonValue> {
+	let deal = self.deal.as_ref()?;
+	let name = |id: &Uuid| self.player_ids_to_configs.get(id).and_then(|config| config.name.clone());
+	let index = |id: &Uuid| self.players.iter().flatten().find(|player| player.id == *id).and_then(|player| player.index);
+	let players: Vec<json::JsonValue> = deal
+	    .ids
+	    .iter()
+	    .enumerate()
+	    .map(|(i, id)| object! {
+		index: index(id),
+		player_name: name(id),
+		money: deal.stacks[i],
+		icm: deal.icm[i],
+		chip_chop: deal.chip_chop[i],
+		accepted: deal.has_accepted(*id),
+	    })
+	    .collect();
+	Some(object! {
+	    proposer_name: name(&deal.proposer),
+	    method: deal.method.to_string(),
+	    payouts: deal.payouts.clone(),
+	    players: players,
+	})
+    }
+
+    /// a player in the deal accepts or rejects it. A single rejection takes the deal off the table.
+    /// Returns the message for the table, or None if there is no deal for them to respond to
+    fn respond_to_deal(&mut self, id: Uuid, accept: bool) -> Option<json::JsonValue> {
+	let deal = self.deal.as_mut()?;
+	if !deal.is_in_deal(id) {
+	    return None;
+	}
+	let player_name = self.player_ids_to_configs.get(&id).and_then(|config| config.name.clone());
+	if !accept {
+	    self.deal = None;
+	    return Some(object! {
+		msg_type: "deal_rejected".to_owned(),
+		player_name: player_name,
+	    });
+	}
+	deal.accept(id);
+	Some(object! {
+	    msg_type: "deal_accepted".to_owned(),
+	    player_name: player_name,
+	    unanimous: deal.is_unanimous(),
+	})
+    }
+
+    /// a deal is called off if any player in it is no longer at the table.
+    /// Anyone who hasn't answered within the action timeout is taken to have rejected it
+    fn call_off_stale_deal(&mut self) {
+	let deal = match &self.deal {
+	    Some(deal) => deal,
+	    None => return,
+	};
+	let players = &self.players;
+	let reason = if deal.ids.iter().any(|id| !players.iter().flatten().any(|player| player.id == *id)) {
+	    "A player in the deal left the table."
+	} else if !deal.is_unanimous() && deal.is_expired(time::Duration::from_secs(self.player_action_timeout.into())) {
+	    "Not everyone accepted the deal in time."
+	} else {
+	    return;
+	};
+	self.deal = None;
+	let message = object! {
+	    msg_type: "deal_cancelled".to_owned(),
+	    reason: reason.to_owned(),
+	};
+	PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+    }
+
+    /// what each player won in the deal that ended the game (if there was one)
+    fn deal_prizes(&self) -> HashMap<Uuid, u32> {
+	match &self.deal {
+	    Some(deal) if deal.is_unanimous() => deal.ids.iter().cloned().zip(deal.prizes().iter().cloned()).collect(),
+	    _ => HashMap::new(),
+	}
+    }
+
+    /// once everyone accepts the deal, the game is over. The prizes go in the freezeout's ledger (if there is one),
+    /// a sit-and-go's players finish in order of their prizes, and everyone sees what each player was paid
+    fn record_deal(&mut self) {
+	let prizes = self.deal_prizes();
+	if let Some(freezeout) = self.freezeout.as_mut() {
+	    for (id, prize) in prizes.iter() {
+		freezeout.award_prize(*id, *prize);
+	    }
+	}
+	if let (Some(tournament), Some(deal)) = (self.tournament.as_mut(), &self.deal) {
+	    // the smallest prize finishes lowest (or the smaller stack, for the same prize)
+	    let mut finishers: Vec<(u32, u32, Uuid)> = deal
+		.ids
+		.iter()
+		.zip(deal.stacks.iter())
+		.map(|(id, stack)| (prizes[id], *stack, *id))
+		.collect();
+	    finishers.sort();
+	    for (_, _, id) in finishers {
+		tournament.eliminate(id);
+	    }
+	}
+	if let Some(deal) = &self.deal {
+	    let name = |id: &Uuid| self.player_ids_to_configs.get(id).and_then(|config| config.name.clone());
+	    let results: Vec<json::JsonValue> = deal
+		.ids
+		.iter()
+		.zip(deal.prizes())
+		.enumerate()
+		.map(|(i, (id, prize))| object! {
+		    player_name: name(id),
+		    money: deal.stacks[i],
+		    prize: *prize,
+		})
+		.collect();
+	    println!("the deal was made: {:?}", results);
+	    let message = object! {
+		msg_type: "deal_made".to_owned(),
+		method: deal.method.to_string(),
+		results: results,
+	    };
+	    PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+	}
+    }
+
+    /// move a player to another table of a multi-table tournament, by handing them back to the hub with their stack
+    fn reseat_player(&mut self, id: Uuid, table_name: String) {
+	let seat = self.players.iter_mut().find(|spot| spot.as_ref().is_some_and(|player| player.id == id));
+	let player = match seat {
+	    Some(spot) => spot.take().unwrap(),
+	    None => return, // they already left or busted
+	};
+	if let Some(config) = self.player_ids_to_configs.remove(&id) {
+	    let message = object! {
+		msg_type: "player_reseated".to_owned(),
+		player_name: config.name.clone(),
+		table_name: table_name.clone(),
+	    };
+	    PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+	    if let Some(hub_addr) = &self.hub_addr {
+		hub_addr.do_send(Reseated {
+		    config,
+		    money: player.money,
+		    table_name,
+		});
+	    }
+	}
+    }
+
+    /// once a started tournament is down to one player, that player wins it.
+    /// Returns true if the tournament is over
+    fn crown_tournament_winner(&mut self) -> bool {
+	let remaining: Vec<Uuid> = self.players.iter().flatten().map(|player| player.id).collect();
+	match self.tournament.as_mut() {
+	    Some(tournament) if tournament.has_started() => {
+		if remaining.len() <= 1 {
+		    for id in remaining {
+			tournament.eliminate(id);
+		    }
+		}
+		tournament.is_over()
+	    }
+	    _ => false,
+	}
+    }
+
+    /// once a tournament is over, tell everyone where each player finished
+    fn send_tournament_results(&self) {
+	if let Some(tournament) = &self.tournament {
+	    let results: Vec<json::JsonValue> = tournament
+		.results()
+		.into_iter()
+		.map(|(position, player_name)| object! { position: position, player_name: player_name })
+		.collect();
+	    let message = object! {
+		msg_type: "tournament_results".to_owned(),
+		results: results,
+	    };
+	    PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+	}
+    }
+
+    /// in a mixed game, find which variant the next hand is dealt in,
+    /// first letting the button pick it in dealer's choice
+    fn set_up_variant(&mut self, incoming_meta_actions: &Arc<Mutex<VecDeque<MetaAction>>>) {
+	if self.rotation.as_ref().is_some_and(|rotation| rotation.is_awaiting_choice()) {
+	    self.get_dealers_choice(incoming_meta_actions);
+	}
+	let num_players = self.players.iter().flatten()
+	    .filter(|player| player.money > 0 && !player.is_sitting_out)
+	    .count() as u32;
+	if let Some(rotation) = self.rotation.as_mut() {
+	    rotation.start_hand(num_players);
+	    let variant = rotation.current_variant();
+	    if variant != self.variant {
+		println!("switching the game from {} to {}", self.variant, variant);
+		self.variant = variant;
+		self.deck = variant.new_deck(self.wild_cards.num_jokers);
+		if !self.betting_structure_configured {
+		    self.betting_structure = variant.default_betting_structure();
+		}
+	    }
+	}
+    }
+
+    /// in dealer's choice, the (human) player on the button picks the next game.
+    /// If they don't pick in time, or the button is a bot, we just move on to the next game in the list
+    fn get_dealers_choice(&mut self, incoming_meta_actions: &Arc<Mutex<VecDeque<MetaAction>>>) {
+	let between_hands = true;
+	if let Some(player) = self.players[self.button_idx].as_ref().filter(|player| player.human_controlled) {
+	    let variants = self.rotation.as_ref().unwrap().variants().iter()
+		.map(|v| v.to_string())
+		.collect::<Vec<_>>();
+	    let message = object! {
+		msg_type: "choose_variant".to_owned(),
+		variants: variants,
+	    };
+	    PlayerConfig::send_specific_message(&message.dump(), player.id, &self.player_ids_to_configs);
+	    self.send_game_state(None, None);
+	    for _ in 0..self.player_action_timeout {
+		self.sleep_loop(1.0, incoming_meta_actions, between_hands, None);
+		if !self.rotation.as_ref().unwrap().is_awaiting_choice() {
+		    return;
+		}
+	    }
+	}
+	let rotation = self.rotation.as_mut().unwrap();
+	let next_variant = rotation.next_variant();
+	rotation.choose(next_variant);
+    }
+
     /// move the button to the next Player who is not sitting out
     /// if non can be found, then return false
     fn find_next_button(&mut self) -> Result<usize, &'static str> {
@@ -588,8 +1456,24 @@ impl Table {
 		    let message = object! {
 			"msg_type": "table_info".to_owned(),
 			"table_name": self.name.to_owned(),
+			"variant": self.variant.to_string(),
+			"betting_structure": self.betting_structure.to_string(),
 			"small_blind": self.small_blind,
 			"big_blind": self.big_blind,
+			"ante": self.ante,
+			"big_blind_ante": self.big_blind_ante,
+			"straddle": self.straddle.map(|straddle| straddle.to_string()),
+			"max_runouts": self.max_runouts,
+			"rabbit_hunting": self.rabbit_hunting,
+			"tournament": self.tournament.is_some(),
+			"tournament_name": self.tournament_name.clone(),
+			"freezeout": self.freezeout.is_some(),
+			"knockout": self.knockout.is_some(),
+			"jokers": self.wild_cards.num_jokers,
+			"wild_ranks": self.wild_cards.ranks_string(),
+			"seven_deuce_bounty": self.seven_deuce_bounty,
+			"rotation": self.rotation.as_ref().map(
+			    |rotation| rotation.variants().iter().map(|v| v.to_string()).collect::<Vec<_>>()),
 			"buy_in": self.buy_in,
 			"max_players": self.max_players,
 			"num_humans": self.players.iter().flatten().filter(|p| p.human_controlled).count(),
@@ -609,6 +1493,64 @@ impl Table {
 		    }
 		    self.send_game_state(gamehand, None);		    		    
                 }
+                MetaAction::Straddle(id, wants_to_straddle) => {
+                    for player in self.players.iter_mut().flatten() {
+                        if player.id == id {
+                            println!("player {} being set to wants_to_straddle = {}", id, wants_to_straddle);
+                            player.wants_to_straddle = wants_to_straddle;
+                        }
+                    }
+		    self.send_game_state(gamehand, None);
+                }
+                MetaAction::ChooseVariant(id, variant) => {
+		    let is_button = matches!(&self.players[self.button_idx], Some(player) if player.id == id);
+		    if let Some(rotation) = self.rotation.as_mut() {
+			if is_button && rotation.choose(variant) {
+			    println!("player {} chose {} for the next game", id, variant);
+			    self.send_game_state(gamehand, None);
+			}
+		    }
+                }
+                MetaAction::RabbitHunt(id) => {
+		    let message = match self.rabbit_hunt() {
+			Some(board) => {
+			    let player_name = self.player_ids_to_configs.get(&id).and_then(|config| config.name.clone());
+			    object! {
+				msg_type: "rabbit_hunt".to_owned(),
+				player_name: player_name,
+				board: board.iter().map(|card| card.to_string()).collect::<String>(),
+			    }
+			}
+			None => {
+			    let message = object! {
+				msg_type: "error".to_owned(),
+				error: "unable_to_rabbit_hunt".to_owned(),
+				reason: "There is no board to rabbit hunt.".to_owned(),
+			    };
+			    PlayerConfig::send_specific_message(&message.dump(), id, &self.player_ids_to_configs);
+			    continue;
+			}
+		    };
+		    // everyone gets to see the rabbit
+		    PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+                }
+                MetaAction::ShowCards(id) => {
+		    match self.show_cards(id) {
+			Some(message) => {
+			    PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+			    // a bounty may have changed the stacks
+			    self.send_game_state(gamehand, Some(object! { hand_over: true }));
+			}
+			None => {
+			    let message = object! {
+				msg_type: "error".to_owned(),
+				error: "unable_to_show_cards".to_owned(),
+				reason: "You can only show your cards once the hand is over.".to_owned(),
+			    };
+			    PlayerConfig::send_specific_message(&message.dump(), id, &self.player_ids_to_configs);
+			}
+		    }
+                }
                 MetaAction::SitOut(id) => {
                     for player in self.players.iter_mut().flatten() {
                         if player.id == id {
@@ -618,6 +1560,113 @@ impl Table {
                     }
 		    self.send_game_state(gamehand, None);		    		    
                 }
+		MetaAction::Reseat(id, table_name) => {
+		    if !between_hands {
+			// nobody moves in the middle of a hand
+			meta_actions.push_back(MetaAction::Reseat(id, table_name));
+		    } else {
+			self.reseat_player(id, table_name);
+		    }
+		}
+		MetaAction::TournamentSeat(player_config, money) => {
+		    let id = player_config.id;
+		    let cloned_config = player_config.clone(); // clone in case we need to send back
+		    match self.add_player(player_config, Player::new(id, true, money)) {
+			Ok(index) => {
+			    let message = object! {
+				msg_type: "reseated".to_owned(),
+				table_name: self.name.clone(),
+				index: index,
+			    };
+			    PlayerConfig::send_specific_message(&message.dump(), id, &self.player_ids_to_configs);
+			    self.send_game_state(gamehand, None);
+			}
+			Err(err) => {
+			    println!("unable to seat the tournament player: {:?}", err);
+			    if let Some(hub_addr) = &self.hub_addr {
+				hub_addr.do_send(Returned {
+				    config: cloned_config,
+				    reason: ReturnedReason::FailureToJoin(err),
+				});
+			    }
+			}
+		    }
+		}
+		MetaAction::Rebuy(id) => {
+		    if !between_hands {
+			// a player who is all-in still has chips in the pot, so wait until the hand is over
+			meta_actions.push_back(MetaAction::Rebuy(id));
+		    } else if let Some(message) = self.rebuy(id) {
+			PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+			self.send_game_state(gamehand, None);
+		    } else {
+			let message = object! {
+			    msg_type: "error".to_owned(),
+			    error: "unable_to_rebuy".to_owned(),
+			    reason: "You can only rebuy once you are out of chips, during the rebuy window, if you have rebuys left.".to_owned(),
+			};
+			PlayerConfig::send_specific_message(&message.dump(), id, &self.player_ids_to_configs);
+		    }
+		}
+		MetaAction::AddOn(id) => {
+		    if !between_hands {
+			// stacks don't change in the middle of a hand
+			meta_actions.push_back(MetaAction::AddOn(id));
+		    } else if let Some(message) = self.add_on(id) {
+			PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+			self.send_game_state(gamehand, None);
+		    } else {
+			let message = object! {
+			    msg_type: "error".to_owned(),
+			    error: "unable_to_add_on".to_owned(),
+			    reason: "You can only take the add-on once, at the end of the rebuy window.".to_owned(),
+			};
+			PlayerConfig::send_specific_message(&message.dump(), id, &self.player_ids_to_configs);
+		    }
+		}
+		MetaAction::ProposeDeal(id, method, payouts) => {
+		    if !between_hands {
+			// the stacks have to settle before a deal can be worked out
+			meta_actions.push_back(MetaAction::ProposeDeal(id, method, payouts));
+		    } else {
+			match self.propose_deal(id, method, payouts) {
+			    Ok(message) => {
+				PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+				self.send_game_state(gamehand, None);
+			    }
+			    Err(reason) => {
+				let message = object! {
+				    msg_type: "error".to_owned(),
+				    error: "unable_to_propose_deal".to_owned(),
+				    reason: reason.to_owned(),
+				};
+				PlayerConfig::send_specific_message(&message.dump(), id, &self.player_ids_to_configs);
+			    }
+			}
+		    }
+		}
+		MetaAction::RespondToDeal(id, accept) => {
+		    if let Some(message) = self.respond_to_deal(id, accept) {
+			PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
+			self.send_game_state(gamehand, None);
+		    } else {
+			let message = object! {
+			    msg_type: "error".to_owned(),
+			    error: "unable_to_respond_to_deal".to_owned(),
+			    reason: "There is no deal on the table for you to accept or reject.".to_owned(),
+			};
+			PlayerConfig::send_specific_message(&message.dump(), id, &self.player_ids_to_configs);
+		    }
+		}
+		MetaAction::TournamentLevel(level_num, level) => {
+		    self.set_blind_level(level_num, level);
+		}
+		MetaAction::TournamentOver => {
+		    self.tournament_over = true;
+		}
+		MetaAction::SendMessage(id, message) => {
+		    PlayerConfig::send_specific_message(&message, id, &self.player_ids_to_configs);
+		}
 		MetaAction::Admin(id, admin_command) => {
 		    if !between_hands {
 			// put it back on the meta actions queue to be handled only between hands
@@ -664,8 +1713,17 @@ impl Table {
 	}
 	
 	let message = match admin_command {
-	    AdminCommand::SmallBlind(new) => {
-		self.small_blind = new;
+	    AdminCommand::SmallBlind(_) | AdminCommand::BigBlind(_) | AdminCommand::BuyIn(_)
+		if self.tournament.is_some() || self.freezeout.is_some() => {
+		// the blind levels and the buy in (which rebuys pay into the prize pool) are set for the whole event
+		object! {
+		    msg_type: "error".to_owned(),
+		    error: "unable_to_update".to_owned(),
+		    reason: "The blinds and buy in cannot be changed during a tournament or freezeout.".to_owned(),
+		}
+	    }
+	    AdminCommand::SmallBlind(new) => {
+		self.small_blind = new;
 		object! {
 		    msg_type: "admin_success".to_owned(),
 		    updated: "small_blind".to_owned(),
@@ -755,9 +1813,45 @@ impl Table {
 		    }
 		}
 	    }
+	    AdminCommand::BombPot => {
+		self.bomb_pot_next_hand = true;
+		object! {
+		    msg_type: "admin_success".to_owned(),
+		    updated: "bomb_pot".to_owned(),
+                    text: "The next hand will be a bomb pot.".to_owned(),
+		}
+	    }
+	    AdminCommand::BombPotEvery(new) => {
+		self.bomb_pot_every = new;
+		object! {
+		    msg_type: "admin_success".to_owned(),
+		    updated: "bomb_pot_every".to_owned(),
+                    text: if new == 0 {
+			"There will be no more scheduled bomb pots".to_owned()
+		    } else {
+			format!("Every {} hands will be a bomb pot", new)
+		    },
+		}
+	    }
+	    AdminCommand::BombPotAmount(new) => {
+		self.bomb_pot_amount = new;
+		object! {
+		    msg_type: "admin_success".to_owned(),
+		    updated: "bomb_pot_amount".to_owned(),
+                    text: format!("The bomb pot amount has been changed to {}", new),
+		}
+	    }
+	    AdminCommand::Restart if self.tournament.is_some() || self.freezeout.is_some() => {
+		object! {
+		    msg_type: "error".to_owned(),
+		    error: "unable_to_restart".to_owned(),
+		    reason: "A tournament or freezeout cannot be restarted.".to_owned(),
+		}
+	    }
 	    AdminCommand::Restart => {
 		// set every player to have the buy_in amount of money
 		println!("inside restart");
+		self.deal = None; // any deal was for the old stacks
 		for player_spot in self.players.iter_mut() {
 		    if let Some(player) = player_spot {
 			player.money = self.buy_in;
@@ -784,6 +1878,7 @@ impl Table {
             Street::Preflop => {
                 gamehand.street = Street::Flop;
                 self.deal_flop(gamehand);
+                self.deal_extra_boards(gamehand);
                 println!(
                     "\n===========================\nFlop = {:?}\n===========================",
                     gamehand.flop
@@ -792,6 +1887,7 @@ impl Table {
             Street::Flop => {
                 gamehand.street = Street::Turn;
                 self.deal_turn(gamehand);
+                self.deal_extra_boards(gamehand);
                 println!(
                     "\n==========================\nTurn = {:?}\n==========================",
                     gamehand.turn
@@ -800,6 +1896,7 @@ impl Table {
             Street::Turn => {
                 gamehand.street = Street::River;
                 self.deal_river(gamehand);
+                self.deal_extra_boards(gamehand);
                 println!(
                     "\n==========================\nRiver = {:?}\n==========================",
                     gamehand.river
@@ -812,6 +1909,53 @@ impl Table {
                 );
             }
             Street::ShowDown => (), // we are already in the end street (from players folding during the street)
+            Street::Third => {
+                gamehand.street = Street::Fourth;
+                self.deal_up_cards();
+            }
+            Street::Fourth => {
+                gamehand.street = Street::Fifth;
+                self.deal_up_cards();
+            }
+            Street::Fifth => {
+                gamehand.street = Street::Sixth;
+                self.deal_up_cards();
+            }
+            Street::Sixth => {
+                gamehand.street = Street::Seventh;
+                // the last card in stud is dealt face down
+                self.deal_down_cards();
+            }
+            Street::Seventh => {
+                gamehand.street = Street::ShowDown;
+                println!(
+                    "\n==========================\nShowDown!\n================================"
+                );
+            }
+            // in a draw game, no cards are dealt between the streets.
+            // Instead, the players draw at the start of the next street (see play_draw_round)
+            street if street == gamehand.variant.last_street() => {
+                // e.g. five card draw is over after the first draw
+                gamehand.street = Street::ShowDown;
+                println!(
+                    "\n==========================\nShowDown!\n================================"
+                );
+            }
+            Street::PreDraw => {
+                gamehand.street = Street::FirstDraw;
+            }
+            Street::FirstDraw => {
+                gamehand.street = Street::SecondDraw;
+            }
+            Street::SecondDraw => {
+                gamehand.street = Street::ThirdDraw;
+            }
+            Street::ThirdDraw => {
+                gamehand.street = Street::ShowDown;
+                println!(
+                    "\n==========================\nShowDown!\n================================"
+                );
+            }
         }
 	self.send_game_state(Some(gamehand), None);	
     }
@@ -819,7 +1963,7 @@ impl Table {
     fn deal_hands(&mut self) {
         for player in self.players.iter_mut().flatten() {
             if player.is_active {
-                for _ in 0..2 {
+                for _ in 0..self.variant.num_hole_cards() {
                     if let Some(card) = self.deck.draw_card() {
                         player.hole_cards.push(card)
                     } else {
@@ -828,6 +1972,88 @@ impl Table {
                 }
             }
         }
+        if self.variant.is_stud() {
+            // third street in stud is two down cards and one up card
+            self.deal_up_cards();
+        }
+    }
+
+    /// in stud, deal one face up card to each player still in the hand
+    fn deal_up_cards(&mut self) {
+        for player in self.players.iter_mut().flatten() {
+            if player.is_active {
+                if let Some(card) = self.deck.draw_card() {
+                    player.up_cards.push(card)
+                } else {
+                    panic!("The deck is out of cards somehow?");
+                }
+            }
+        }
+    }
+
+    /// in stud, deal one face down card to each player still in the hand
+    fn deal_down_cards(&mut self) {
+        for player in self.players.iter_mut().flatten() {
+            if player.is_active {
+                if let Some(card) = self.deck.draw_card() {
+                    player.hole_cards.push(card)
+                } else {
+                    panic!("The deck is out of cards somehow?");
+                }
+            }
+        }
+    }
+
+    /// is the hand about to be dealt a bomb pot? Either the admin asked for one,
+    /// or it is the scheduled Nth hand. Only games with a flop can have a bomb pot
+    fn take_bomb_pot(&mut self) -> bool {
+        if self.variant.first_street() != Street::Preflop {
+            // keep an on-demand bomb pot for the next flop game in a rotation
+            return false;
+        }
+        let is_scheduled = self.bomb_pot_every > 0 && self.hand_num.is_multiple_of(self.bomb_pot_every);
+        let is_bomb_pot = self.bomb_pot_next_hand || is_scheduled;
+        self.bomb_pot_next_hand = false;
+        is_bomb_pot
+    }
+
+    /// in a bomb pot, every player with money puts in the same amount instead of the antes and blinds.
+    /// Then there is no preflop betting, so we go straight to the flop
+    fn post_bomb_pot(&mut self, gamehand: &mut GameHand) {
+        for i in 0..self.players.len() {
+            let amount = match &self.players[i] {
+                Some(player) if player.is_active && player.money > 0 => cmp::min(self.bomb_pot_amount, player.money),
+                _ => continue,
+            };
+            gamehand.enact_player_action(i, PlayerAction::PostBombPot(amount), &mut self.players);
+        }
+    }
+
+    /// every player with money puts in the ante before the cards are dealt,
+    /// or with a big blind ante, the big blind puts in one ante for everyone
+    fn post_antes(&mut self, gamehand: &mut GameHand) {
+        if self.ante == 0 {
+            return;
+        }
+        if self.big_blind_ante && !self.variant.is_stud() {
+            if let Some(i) = self.get_big_blind_idx() {
+                let player = self.players[i].as_ref().unwrap();
+                // the big blind takes priority over the ante if they can't afford both
+                let amount = cmp::min(self.ante, player.money.saturating_sub(self.big_blind));
+                if amount > 0 {
+                    gamehand.enact_player_action(i, PlayerAction::PostBigBlindAnte(amount), &mut self.players);
+                }
+            }
+            return;
+        }
+        for i in 0..self.players.len() {
+            if let Some(player) = &self.players[i] {
+                if player.is_active && player.money > 0 {
+                    let amount = cmp::min(self.ante, player.money);
+                    gamehand.enact_player_action(i, PlayerAction::PostAnte(amount), &mut self.players);
+                }
+            }
+        }
     }
 
     fn deal_flop(&mut self, gamehand: &mut GameHand) {
@@ -850,16 +2076,106 @@ impl Table {
         gamehand.river = self.deck.draw_card();
     }
 
+    /// can the players vote to run the rest of the board more than once?
+    /// The table must allow it, everyone must be all-in, and there must be board cards left to come
+    fn can_run_it_multiple_times(&self, gamehand: &GameHand) -> bool {
+        self.max_runouts > 1
+            && gamehand.variant.has_board()
+            && gamehand.variant.num_boards() == 1
+            && gamehand.board_cards().len() < 5
+            && self.is_all_in_situation()
+    }
+
+    /// the players still in the hand vote on how many times to run the rest of the board.
+    /// Everyone has to agree, so the fewest times voted for wins,
+    /// and if anyone doesn't vote in time, the board is only run once.
+    /// Bots are happy to run it as many times as the table allows
+    fn get_runout_vote(
+        &mut self,
+        incoming_actions: &Arc<Mutex<HashMap<Uuid, PlayerAction>>>,
+        incoming_meta_actions: &Arc<Mutex<VecDeque<MetaAction>>>,
+        gamehand: &GameHand,
+    ) -> u8 {
+        let mut votes: HashMap<Uuid, u8> = self.players.iter().flatten()
+            .filter(|player| player.is_active && !player.human_controlled)
+            .map(|player| (player.id, self.max_runouts))
+            .collect();
+        let human_ids: Vec<Uuid> = self.players.iter().flatten()
+            .filter(|player| player.is_active && player.human_controlled)
+            .map(|player| player.id)
+            .collect();
+        let num_voters = votes.len() + human_ids.len();
+        self.send_game_state(Some(gamehand), Some(object! { run_it_vote: self.max_runouts }));
+        let mut attempts = 0;
+        while attempts < self.player_action_timeout {
+            attempts += 1;
+            {
+                let mut actions = incoming_actions.lock().unwrap();
+                for id in human_ids.iter() {
+                    if let Some(PlayerAction::RunIt(times)) = actions.remove(id) {
+                        votes.insert(*id, times.clamp(1, self.max_runouts));
+                    }
+                }
+            }
+            if votes.len() == num_voters {
+                break;
+            }
+            self.sleep_loop(1.0, incoming_meta_actions, false, Some(gamehand));
+        }
+        if votes.len() < num_voters {
+            println!("not everyone voted to run it more than once");
+            return 1;
+        }
+        let num_runouts = votes.values().copied().min().unwrap_or(1);
+        println!("running the board {} times", num_runouts);
+        num_runouts
+    }
+
+    /// after a hand ended early, the rest of the board is drawn from the same deck,
+    /// so players can see what would have come. It doesn't change the result of the hand,
+    /// and later requests see the same cards
+    fn rabbit_hunt(&mut self) -> Option<Vec<Card>> {
+        let board = self.rabbit_board.as_mut()?;
+        while board.len() < 5 {
+            board.push(self.deck.draw_card().expect("we exhausted the deck somehow"));
+        }
+        Some(board.clone())
+    }
+
+    /// after the flop, turn, or river is dealt, deal the same street to any other boards
+    /// (e.g. the second board in double-board Hold'em, or the extra runouts when running it more than once)
+    fn deal_extra_boards(&mut self, gamehand: &mut GameHand) {
+        let num_cards = gamehand.board_cards().len();
+        for board in gamehand.extra_boards.iter_mut() {
+            while board.len() < num_cards {
+                board.push(self.deck.draw_card().expect("we exhausted the deck somehow"));
+            }
+        }
+    }
+
     fn finish_hand(&mut self,
 		   gamehand: &mut GameHand,
+		   stacks_before: &[u32], // everyone's stack before the blinds and antes went in
 		   incoming_meta_actions: &Arc<Mutex<VecDeque<MetaAction>>>,		   
     ) {
         if self.player_ids_to_configs.is_empty() {
             // the game is currently empty, so there is nothing to finish
             return;
         }
-	let starting_idx = self.get_starting_idx();
-	let settlements = gamehand.divvy_pots(&mut self.players, &self.player_ids_to_configs, starting_idx);
+	let starting_idx = self.get_starting_idx(gamehand);
+	let mut settlements = gamehand.divvy_pots(&mut self.players, &self.player_ids_to_configs, starting_idx);
+	let knockouts = self.collect_knockout_bounties(gamehand, stacks_before, &settlements);
+	settlements.extend(knockouts);
+	self.seven_deuce_winners = self.find_seven_deuce_winners(gamehand, &settlements);
+	for id in self.seven_deuce_winners.clone() {
+	    let is_bot = self.players.iter().flatten().any(|player| player.id == id && !player.human_controlled);
+	    if gamehand.is_showdown() || is_bot {
+		// the winner's cards were already shown at showdown, and a bot always shows its 7-2
+		if let Some(bounty_settlement) = self.collect_seven_deuce_bounty(id) {
+		    settlements.push(bounty_settlement);
+		}
+	    }
+	}
 	let num_in_showdown = self.players.iter().flatten().filter(|player| player.is_active).count();
         let wait_time = 3.0 * num_in_showdown as f32 + 1.5; // 2 bonus seconds at the very end 
 	let extra_fields = object! {
@@ -867,13 +2183,163 @@ impl Table {
 	    settlements: settlements.to_owned(),
 	};
 	self.send_game_state(Some(&gamehand), Some(extra_fields));
+	self.hand_over = true; // the players can show their cards until they are taken
 	self.sleep_loop(wait_time, &incoming_meta_actions, false, Some(gamehand));    	    	
+	self.hand_over = false;
+	self.seven_deuce_winners.clear(); // too late to show for the bounty now
         // take the players' cards
         for player in self.players.iter_mut().flatten() {
             player.hole_cards.drain(..);
+            player.up_cards.drain(..);
         }
     }
 
+    /// in a knockout game, anyone who just busted pays their bounty to whoever won the pot that their last chips went into.
+    /// If that pot was split (or won on different boards), so is the bounty.
+    /// Returns a settlement message for each award
+    fn collect_knockout_bounties(
+	&mut self,
+	gamehand: &GameHand,
+	stacks_before: &[u32],
+	settlements: &[json::JsonValue],
+    ) -> Vec<json::JsonValue> {
+	let (players, configs) = (&self.players, &self.player_ids_to_configs);
+	let knockout = match self.knockout.as_mut() {
+	    Some(knockout) => knockout,
+	    None => return vec![],
+	};
+	let mut knockout_settlements = vec![];
+	for (busted_idx, busted) in players.iter().enumerate() {
+	    let busted = match busted {
+		Some(player) if player.money == 0 && stacks_before[busted_idx] > 0 => player,
+		_ => continue,
+	    };
+	    let pot_idx = match gamehand.last_pot_index(busted.id) {
+		Some(pot_idx) => pot_idx,
+		None => continue,
+	    };
+	    // everyone who won some of the busting pot, in seat order
+	    let mut eliminator_idxs: Vec<usize> = settlements
+		.iter()
+		.filter(|settlement| settlement["pot_index"] == pot_idx && settlement["payout"].as_u32().unwrap_or(0) > 0)
+		.filter_map(|settlement| settlement["index"].as_usize())
+		.filter(|i| *i != busted_idx)
+		.collect();
+	    eliminator_idxs.sort();
+	    eliminator_idxs.dedup();
+	    let eliminator_ids: Vec<Uuid> = eliminator_idxs
+		.iter()
+		.filter_map(|i| players[*i].as_ref().map(|player| player.id))
+		.collect();
+	    let busted_name = configs.get(&busted.id).and_then(|config| config.name.clone());
+	    for (award, index) in knockout.knock_out(busted.id, &eliminator_ids).into_iter().zip(eliminator_idxs) {
+		println!("player {} collected a bounty of {} for busting {}", award.id, award.amount, busted.id);
+		knockout_settlements.push(object! {
+		    index: index,
+		    player_name: configs.get(&award.id).and_then(|config| config.name.clone()),
+		    knockout: true,
+		    busted_index: busted_idx,
+		    busted_player_name: busted_name.clone(),
+		    bounty: award.amount,
+		    bounty_increase: award.bounty_increase,
+		});
+	    }
+	}
+	knockout_settlements
+    }
+
+    /// the players who won (some of) the last hand holding exactly 7-2 in the hole,
+    /// who are owed the seven-deuce bounty if the table plays it
+    fn find_seven_deuce_winners(&self, gamehand: &GameHand, settlements: &[json::JsonValue]) -> Vec<Uuid> {
+	if self.seven_deuce_bounty == 0 || !gamehand.variant.has_board() {
+	    return vec![];
+	}
+	// getting back a bet that nobody called doesn't count as winning
+	let mut winner_idxs: Vec<usize> = settlements
+	    .iter()
+	    .filter(|settlement| settlement["winner"] == true)
+	    .filter(|settlement| settlement["pot_index"].as_usize().is_some_and(|pot_idx| gamehand.is_contested(pot_idx)))
+	    .filter_map(|settlement| settlement["index"].as_usize())
+	    .collect();
+	winner_idxs.sort();
+	winner_idxs.dedup();
+	winner_idxs
+	    .into_iter()
+	    .filter_map(|i| self.players[i].as_ref())
+	    .filter(|player| {
+		let mut ranks: Vec<Rank> = player.hole_cards.iter().map(|card| card.rank).collect();
+		ranks.sort();
+		ranks == [Rank::Two, Rank::Seven]
+	    })
+	    .map(|player| player.id)
+	    .collect()
+    }
+
+    /// every other player at the table pays the bounty (or whatever they have left)
+    /// to the given winner of the seven-deuce bounty. Where running out of chips knocks a player out
+    /// (a tournament, freezeout, or knockout game), the bounty never takes anyone's last chip,
+    /// since only losing a pot should bust them.
+    /// Returns a settlement message recording who paid how much, or None if the player isn't owed a bounty
+    fn collect_seven_deuce_bounty(&mut self, winner_id: Uuid) -> Option<json::JsonValue> {
+	let winner_pos = self.seven_deuce_winners.iter().position(|id| *id == winner_id)?;
+	self.seven_deuce_winners.remove(winner_pos);
+	let keeps_last_chip = self.tournament.is_some()
+	    || self.tournament_name.is_some()
+	    || self.freezeout.is_some()
+	    || self.knockout.is_some();
+	let mut payers = json::JsonValue::new_array();
+	let mut total = 0;
+	for (i, player) in self.players.iter_mut().enumerate() {
+	    if let Some(player) = player.as_mut().filter(|player| player.id != winner_id && player.money > 0) {
+		let can_pay = if keeps_last_chip { player.money - 1 } else { player.money };
+		let amount = cmp::min(self.seven_deuce_bounty, can_pay);
+		if amount == 0 {
+		    continue;
+		}
+		player.money -= amount;
+		total += amount;
+		let player_name = self.player_ids_to_configs.get(&player.id).and_then(|config| config.name.clone());
+		payers.push(object! { index: i, player_name: player_name, amount: amount }).unwrap();
+	    }
+	}
+	let (winner_idx, winner) = self.players.iter_mut().enumerate()
+	    .find_map(|(i, spot)| spot.as_mut().filter(|player| player.id == winner_id).map(|player| (i, player)))?;
+	winner.pay(total);
+	println!("player {} collected a seven-deuce bounty of {}", winner_id, total);
+	Some(object! {
+	    index: winner_idx,
+	    player_name: self.player_ids_to_configs.get(&winner_id).and_then(|config| config.name.clone()),
+	    seven_deuce_bounty: true,
+	    hole_cards: winner.hole_cards_string(),
+	    payout: total,
+	    paid_by: payers,
+	})
+    }
+
+    /// once the hand is over, a player can show their hole cards to the whole table.
+    /// If they won holding 7-2, showing it collects the seven-deuce bounty.
+    /// Returns the message to show everyone, or None if there is nothing to show
+    fn show_cards(&mut self, id: Uuid) -> Option<json::JsonValue> {
+	if !self.hand_over {
+	    return None;
+	}
+	let (index, player) = self.players.iter().enumerate()
+	    .find_map(|(i, spot)| spot.as_ref().filter(|player| player.id == id).map(|player| (i, player)))?;
+	if player.hole_cards.is_empty() {
+	    return None;
+	}
+	let mut message = object! {
+	    msg_type: "show_cards".to_owned(),
+	    index: index,
+	    player_name: self.player_ids_to_configs.get(&id).and_then(|config| config.name.clone()),
+	    hole_cards: player.hole_cards_string(),
+	};
+	if let Some(bounty_settlement) = self.collect_seven_deuce_bounty(id) {
+	    message["settlements"] = json::array![bounty_settlement];
+	}
+	Some(message)
+    }
+
     /// play a single hand of poker
     /// returns a bool indicating if the hand was "actually" played.
     /// because if there are < 2 active players, there is nothing to play
@@ -883,7 +2349,11 @@ impl Table {
         incoming_meta_actions: &Arc<Mutex<VecDeque<MetaAction>>>,
     ) -> bool {
         println!("inside of play(). button_idx = {:?}", self.button_idx);
-        let mut gamehand = GameHand::new(self.big_blind, &self.players);
+        let mut gamehand = GameHand::with_variant(self.variant, self.big_blind, &self.players);
+        gamehand.betting_structure = self.betting_structure;
+        gamehand.wild_cards = self.wild_cards.clone();
+	// the stacks before anyone puts money in, to see who busted once the hand is over
+	let stacks_before: Vec<u32> = self.players.iter().map(|spot| spot.as_ref().map_or(0, |player| player.money)).collect();
 	let mut num_active = 0;
         for player in self.players.iter_mut().flatten() {
             if player.money == 0 {
@@ -906,10 +2376,12 @@ impl Table {
             return false;
         }
 
+	let is_bomb_pot = self.take_bomb_pot();
 	let message = object! {
 	    msg_type: "new_hand".to_owned(),
 	    hand_num: self.hand_num,
 	    button_index: self.button_idx,
+	    bomb_pot: is_bomb_pot,
         };
 	PlayerConfig::send_group_message(&message.dump(), &self.player_ids_to_configs);
 	
@@ -919,20 +2391,49 @@ impl Table {
 	std::mem::drop(actions); // give back the lock
 	
 	self.send_game_state(Some(&gamehand), None);	
+        self.rabbit_board = None; // the last hand's deck is about to be shuffled away
         self.deck.shuffle();
         self.deal_hands();
+        if is_bomb_pot {
+            self.post_bomb_pot(&mut gamehand);
+            // nobody bets before the flop in a bomb pot, but in Pineapple the players still discard
+            self.next_street(incoming_actions, incoming_meta_actions, &mut gamehand);
+        } else {
+            if !self.variant.is_stud() {
+                // the blinds belong to seats, so decide them before an ante can put someone all-in
+                gamehand.small_blind_idx = self.get_nth_idx_after_button(0);
+                gamehand.big_blind_idx = self.get_big_blind_idx();
+            }
+            self.post_antes(&mut gamehand);
+            gamehand.straddle_idx = self.get_straddle_idx();
+        }
 
         println!("players = {:?}", self.players);
 
+        let mut runouts_decided = false;
         while gamehand.street != Street::ShowDown {
+            if gamehand.street.follows_draw() {
+                self.play_draw_round(incoming_actions, incoming_meta_actions, &mut gamehand);
+            }
 	    // before each street, set the player's last action to None
             for player in self.players.iter_mut().flatten() {
 		player.last_action = None;
             }
 	    // at the start of each stree, the min raise is just the big blind
-	    gamehand.min_raise = self.big_blind; 
+	    // (or the size of the fixed bet in fixed-limit)
+	    gamehand.min_raise = if self.betting_structure.is_fixed_limit() {
+		gamehand.fixed_limit_bet_size()
+	    } else {
+		self.big_blind
+	    };
             let finished =
                 self.play_street(incoming_actions, incoming_meta_actions, &mut gamehand);
+	    if !finished && !runouts_decided && self.can_run_it_multiple_times(&gamehand) {
+		// the rest of the board might be run more than once, but only the first time everyone is all-in
+		runouts_decided = true;
+		let num_runouts = self.get_runout_vote(incoming_actions, incoming_meta_actions, &gamehand);
+		gamehand.extra_boards = vec![gamehand.board_cards(); num_runouts as usize - 1];
+	    }
 	    self.sleep_loop(2.0, &incoming_meta_actions, false, Some(&gamehand));    	    		    
             if finished {
                 // if the game is over from players folding
@@ -940,19 +2441,32 @@ impl Table {
                 break;
             } else {
                 // otherwise we move to the next street
-                self.transition(&mut gamehand)
+                self.next_street(incoming_actions, incoming_meta_actions, &mut gamehand);
             }
         }
+        if !gamehand.is_showdown() && self.rabbit_hunting && gamehand.variant.has_board() {
+            // the hand ended early, so the rest of the board can be rabbit hunted
+            self.rabbit_board = Some(gamehand.board_cards());
+        }
         // now we finish up and pay the pot to the winner
-        self.finish_hand(&mut gamehand, incoming_meta_actions);
+        self.finish_hand(&mut gamehand, &stacks_before, incoming_meta_actions);
 	true // the hand was indeed played
     }
 
-    fn get_starting_idx(&self) -> usize {
+    fn get_starting_idx(&self, gamehand: &GameHand) -> usize {
+        if gamehand.variant.is_stud() {
+            return self.get_stud_starting_idx(gamehand);
+        }
         // the starting index is either the person one more from the button on most streets,
         // or 3 down on the preflop (since the blinds already had to buy in)
         // TODO: this needs to be smarter in small games
 	// is that ACTUALLY a todo anymore? March 26, 2023
+        if gamehand.street == gamehand.variant.first_street()
+	    && self.straddle == Some(Straddle::Button)
+	    && gamehand.straddle_idx == Some(self.button_idx) {
+	    // the button posts their straddle before the blinds
+	    return self.button_idx;
+	}
         let mut starting_idx = self.button_idx + 1;
         if starting_idx >= self.players.len() {
             starting_idx = 0;
@@ -960,6 +2474,97 @@ impl Table {
         starting_idx
     }
 
+    /// the index of the nth player after the button who can act this hand,
+    /// e.g. n = 0 is the small blind, and n = 1 is the big blind
+    fn get_nth_idx_after_button(&self, n: usize) -> Option<usize> {
+        let first_idx = (self.button_idx + 1) % self.players.len();
+        (first_idx..9).chain(0..first_idx)
+            .filter(|i| matches!(&self.players[*i], Some(player) if player.is_active && player.money > 0))
+            .nth(n)
+    }
+
+    /// the index of the player who will post the big blind this hand
+    fn get_big_blind_idx(&self) -> Option<usize> {
+        self.get_nth_idx_after_button(1)
+    }
+
+    /// the index of the player who will straddle this hand, if the table allows it and
+    /// the player in the straddle position opted in and can afford it.
+    /// There must be at least three players, so that the straddle isn't one of the blinds
+    fn get_straddle_idx(&self) -> Option<usize> {
+        let straddle = self.straddle?;
+        if self.variant.is_stud() || self.get_nth_idx_after_button(2).is_none() {
+            return None;
+        }
+        let straddle_idx = match straddle {
+            Straddle::UnderTheGun => self.get_nth_idx_after_button(2)?,
+            Straddle::Button => self.button_idx,
+        };
+        match &self.players[straddle_idx] {
+            Some(player) if player.is_active
+                && !player.is_sitting_out
+                && player.wants_to_straddle
+                && player.money > straddle.amount(self.big_blind) => Some(straddle_idx),
+            _ => None,
+        }
+    }
+
+    /// the straddler gets to act last preflop, so once they posted their straddle,
+    /// they are skipped while the blinds still have to act
+    fn straddler_acts_later(&self, gamehand: &GameHand, index: usize) -> bool {
+        if gamehand.straddle_idx != Some(index) {
+            return false;
+        }
+        let straddler = self.players[index].as_ref().unwrap();
+        matches!(straddler.last_action, Some(PlayerAction::PostStraddle(_)))
+            && self.players.iter().flatten().any(|player| {
+                player.is_active
+                    && !player.is_all_in()
+                    && matches!(player.last_action, Some(PlayerAction::PostSmallBlind(_) | PlayerAction::PostBigBlind(_)))
+            })
+    }
+
+    /// in stud, the lowest up card must bring it in on third street (suits break ties),
+    /// then the best hand showing acts first on every later street.
+    /// In Razz it is the opposite, since the best hand is the lowest
+    fn get_stud_starting_idx(&self, gamehand: &GameHand) -> usize {
+        let first_idx = (self.button_idx + 1) % self.players.len();
+        let active_players = (first_idx..9).chain(0..first_idx)
+            .filter_map(|i| self.players[i].as_ref().map(|player| (i, player)))
+            .filter(|(_, player)| player.is_active && !player.up_cards.is_empty());
+        let starting_idx = if gamehand.street == Street::Third && gamehand.variant.is_lowball() {
+            // in Razz, the highest up card brings it in instead (Aces are low)
+            active_players
+                .max_by_key(|(_, player)| (LowHandResult::ace_low_rank(&player.up_cards[0]), player.up_cards[0].suit))
+                .map(|(i, _)| i)
+        } else if gamehand.street == Street::Third {
+            active_players
+                .min_by_key(|(_, player)| (player.up_cards[0].rank, player.up_cards[0].suit))
+                .map(|(i, _)| i)
+        } else if gamehand.variant.is_lowball() {
+            // the best low hand showing acts first
+            let mut best: Option<(usize, LowHandResult)> = None;
+            for (i, player) in active_players {
+                let showing = LowHandResult::analyze_ace_to_five(player.up_cards.clone());
+                if best.is_none() || showing > best.as_ref().unwrap().1 {
+                    best = Some((i, showing));
+                }
+            }
+            best.map(|(i, _)| i)
+        } else {
+            // on ties, the player closest to the left of the button acts first
+            let mut best: Option<(usize, HandResult)> = None;
+            for (i, player) in active_players {
+                let showing = gamehand.analyze_partial_hand(player.up_cards.clone());
+                if best.is_none() || showing > best.as_ref().unwrap().1 {
+                    best = Some((i, showing));
+                }
+            }
+            best.map(|(i, _)| i)
+        };
+        starting_idx.unwrap_or(first_idx)
+    }
+
     /// this method returns a bool indicating whether the hand is over or not
     fn play_street(
         &mut self,
@@ -989,7 +2594,7 @@ impl Table {
 	let between_hands = false;			
 	let mut hand_over = false;
 
-        let starting_idx = self.get_starting_idx(); // which player starts the betting	
+        let starting_idx = self.get_starting_idx(gamehand); // which player starts the betting	
         // iterate over the players in a cycle, from the starting index
         for i in (starting_idx..9).chain(0..starting_idx).cycle() {
 	    // handle meta actions once right at the beginning to be responsive to sitout messages for example
@@ -1027,6 +2632,9 @@ impl Table {
 		    // if the player is not active with money, they can't do anything.
                     continue;
 		}
+		if self.straddler_acts_later(gamehand, i) {
+		    continue;
+		}
 	    } else {
                 // no one sitting in this spot
                 continue;
@@ -1048,6 +2656,219 @@ impl Table {
 	hand_over
     }
     
+    /// in a draw game, each player still in the hand (starting from the left of the button)
+    /// throws away the cards they don't want, and is dealt the same number of new cards
+    fn play_draw_round(
+        &mut self,
+        incoming_actions: &Arc<Mutex<HashMap<Uuid, PlayerAction>>>,
+        incoming_meta_actions: &Arc<Mutex<VecDeque<MetaAction>>>,
+        gamehand: &mut GameHand,
+    ) {
+        let starting_idx = self.get_starting_idx(gamehand);
+        for i in (starting_idx..9).chain(0..starting_idx) {
+            self.handle_meta_actions(incoming_meta_actions, false, Some(gamehand));
+            match &self.players[i] {
+                Some(player) if player.is_active && self.player_ids_to_configs.contains_key(&player.id) => (),
+                _ => continue,
+            }
+            gamehand.index_to_act = Some(i);
+            self.send_game_state(Some(gamehand), Some(object! { drawing: true }));
+
+            let discards = self.get_and_validate_draw(incoming_actions, incoming_meta_actions, gamehand, i);
+            let player = self.players[i].as_mut().unwrap();
+            player.hole_cards.retain(|card| !discards.iter().any(|discard| discard.is_identical(card)));
+            for _ in 0..discards.len() {
+                // the new cards are dealt before the discards go in the discard pile,
+                // so a player can never draw their own discards
+                if let Some(card) = self.deck.draw_card() {
+                    player.hole_cards.push(card);
+                } else {
+                    panic!("The deck is out of cards somehow?");
+                }
+            }
+            for card in discards.iter() {
+                self.deck.discard(*card);
+            }
+            gamehand.enact_player_action(i, PlayerAction::Draw(discards), &mut self.players);
+        }
+        gamehand.index_to_act = None;
+    }
+
+    /// once the betting on a street is done, move on to the next street.
+    /// In Pineapple, everyone throws away their extra hole card before the next street is dealt
+    fn next_street(
+        &mut self,
+        incoming_actions: &Arc<Mutex<HashMap<Uuid, PlayerAction>>>,
+        incoming_meta_actions: &Arc<Mutex<VecDeque<MetaAction>>>,
+        gamehand: &mut GameHand,
+    ) {
+        if gamehand.variant.discard_street() == Some(gamehand.street) {
+            self.play_discard_round(incoming_actions, incoming_meta_actions, gamehand);
+        }
+        self.transition(gamehand);
+    }
+
+    /// in Pineapple, each player still in the hand throws away one of their three hole cards.
+    /// Unlike a draw, everyone chooses at the same time instead of in turn order,
+    /// so we keep checking on everyone until they have all discarded
+    fn play_discard_round(
+        &mut self,
+        incoming_actions: &Arc<Mutex<HashMap<Uuid, PlayerAction>>>,
+        incoming_meta_actions: &Arc<Mutex<VecDeque<MetaAction>>>,
+        gamehand: &mut GameHand,
+    ) {
+        gamehand.index_to_act = None;
+        self.send_game_state(Some(gamehand), Some(object! { discarding: true }));
+        let mut attempts = 0;
+        loop {
+            self.handle_meta_actions(incoming_meta_actions, false, Some(gamehand));
+            let timed_out = attempts >= self.player_action_timeout;
+            let mut num_waiting = 0;
+            for i in 0..self.players.len() {
+                match &self.players[i] {
+                    Some(player) if player.is_active && player.hole_cards.len() > 2 => (),
+                    _ => continue,
+                }
+                let discard = match self.get_and_validate_discard(incoming_actions, incoming_meta_actions, gamehand, i, timed_out) {
+                    Some(discard) => discard,
+                    None => {
+                        num_waiting += 1;
+                        continue;
+                    }
+                };
+                let player = self.players[i].as_mut().unwrap();
+                player.hole_cards.retain(|card| !card.is_identical(&discard));
+                self.deck.discard(discard);
+                gamehand.enact_player_action(i, PlayerAction::Discard(discard), &mut self.players);
+            }
+            if num_waiting == 0 {
+                break;
+            }
+            // we give the users a second to choose their cards
+            attempts += 1;
+            self.sleep_loop(1.0, incoming_meta_actions, false, Some(gamehand));
+        }
+        self.send_game_state(Some(gamehand), None);
+    }
+
+    /// get the card that the player at the given index wants to throw away in Pineapple,
+    /// or None if they haven't chosen yet.
+    /// A player who has left (or does not choose in time) throws away what a bot would
+    fn get_and_validate_discard(
+        &mut self,
+        incoming_actions: &Arc<Mutex<HashMap<Uuid, PlayerAction>>>,
+        incoming_meta_actions: &Arc<Mutex<VecDeque<MetaAction>>>,
+        gamehand: &GameHand,
+        index: usize,
+        timed_out: bool,
+    ) -> Option<Card> {
+        let player = self.players[index].as_ref().unwrap();
+        let player_id = player.id;
+        if !player.human_controlled || player.is_sitting_out || !self.player_ids_to_configs.contains_key(&player_id) {
+            return Some(bot::get_bot_discard(player, gamehand));
+        }
+        let reason = match self.get_action_from_player(incoming_actions, player, gamehand) {
+            None if timed_out => {
+                // we timed out, so the player discards like a bot and sits out
+                incoming_meta_actions
+                    .lock()
+                    .unwrap()
+                    .push_back(MetaAction::SitOut(player_id));
+                return Some(bot::get_bot_discard(player, gamehand));
+            }
+            None => return None,
+            Some(PlayerAction::Discard(discard)) => {
+                if let Some(card) = player.hole_cards.iter().find(|card| card.is_identical(&discard)) {
+                    let card = *card;
+                    if let Some(player_config) = self.player_ids_to_configs.get_mut(&player_id) {
+                        // the fact that we received an action tells us to update the active heartbeat
+                        player_config.heart_beat = time::Instant::now();
+                    }
+                    return Some(card);
+                }
+                "You can only throw away a card that is in your hand!"
+            }
+            Some(_) => "You need to choose a card to throw away!",
+        };
+        let message = json::object! {
+            msg_type: "error".to_owned(),
+            error: "invalid_action".to_owned(),
+            reason: reason.to_owned(),
+        };
+        PlayerConfig::send_specific_message(
+            &message.dump(),
+            player_id,
+            &self.player_ids_to_configs,
+        );
+        None
+    }
+
+    /// get the cards that the player at the given index wants to throw away in a draw game.
+    /// A player who has left (or does not choose in time) keeps their cards
+    fn get_and_validate_draw(
+        &mut self,
+        incoming_actions: &Arc<Mutex<HashMap<Uuid, PlayerAction>>>,
+        incoming_meta_actions: &Arc<Mutex<VecDeque<MetaAction>>>,
+        gamehand: &GameHand,
+        index: usize
+    ) -> Vec<Card> {
+        // we sleep a little bit each time so that the output doesnt flood the user at one moment
+        self.sleep_loop(1.0, incoming_meta_actions, false, Some(gamehand));
+        let player_id = self.players[index].as_ref().unwrap().id;
+        let mut attempts = 0;
+        let retry_duration = 1.0; // how long to wait between trying again
+        while attempts < self.player_action_timeout {
+            self.handle_meta_actions(incoming_meta_actions, false, Some(gamehand));
+            attempts += 1;
+            let player = self.players[index].as_ref().unwrap();
+            if player.is_sitting_out || !self.player_ids_to_configs.contains_key(&player.id) {
+                println!("player is sitting out or has left, so they stand pat");
+                return vec![];
+            }
+            if !player.human_controlled {
+                return bot::get_bot_discards(player, gamehand);
+            }
+            let reason = match self.get_action_from_player(incoming_actions, player, gamehand) {
+                None => {
+                    // we give the user a second to choose their cards
+                    self.sleep_loop(retry_duration, incoming_meta_actions, false, Some(gamehand));
+                    continue;
+                }
+                Some(PlayerAction::Draw(discards)) => {
+                    let all_held = discards.iter()
+                        .all(|discard| player.hole_cards.iter().any(|card| card.is_identical(discard)));
+                    let no_repeats = discards.iter().enumerate()
+                        .all(|(i, discard)| !discards[..i].iter().any(|other| other.is_identical(discard)));
+                    if all_held && no_repeats {
+                        if let Some(player_config) = self.player_ids_to_configs.get_mut(&player_id) {
+                            // the fact that we received an action tells us to update the active heartbeat
+                            player_config.heart_beat = time::Instant::now();
+                        }
+                        return discards;
+                    }
+                    "You can only throw away cards that are in your hand!"
+                }
+                Some(_) => "You need to choose which cards to draw (or none to stand pat)!",
+            };
+            let message = json::object! {
+                msg_type: "error".to_owned(),
+                error: "invalid_action".to_owned(),
+                reason: reason.to_owned(),
+            };
+            PlayerConfig::send_specific_message(
+                &message.dump(),
+                player_id,
+                &self.player_ids_to_configs,
+            );
+        }
+        // we timed out, so the player keeps their cards and sits out
+        incoming_meta_actions
+            .lock()
+            .unwrap()
+            .push_back(MetaAction::SitOut(player_id));
+        vec![]
+    }
+
     /// if the player is a human, then we look for their action in the incoming_actions hashmap
     /// this value is set by the table hub when handling a message from a player client
     fn get_action_from_player(
@@ -1058,13 +2879,8 @@ impl Table {
     ) -> Option<PlayerAction> {
         if player.human_controlled {
             let mut actions = incoming_actions.lock().unwrap();
-            if let Some(action) = actions.get_mut(&player.id) {
-                let value = *action;
-                actions.remove(&player.id); // wipe this action so we don't repeat it next time
-                Some(value)
-            } else {
-                None
-            }
+            // taking the action out wipes it so we don't repeat it next time
+            actions.remove(&player.id)
         } else {
 	    Some(bot::get_bot_action(player, gamehand, &self.players))
         }
@@ -1092,14 +2908,25 @@ impl Table {
 	// I don't know if this is somewhat common, or if I have coded myself into a corner...
 	let player_id = {
 	    let player = self.players[index].as_ref().unwrap();
-	    if let Some(action) = gamehand.last_action {
-		if matches!(action, PlayerAction::PostSmallBlind(_)) {
-		    // the last action was the small blind, so now need the big blind
+	    if gamehand.variant.is_stud() {
+		if gamehand.street == Street::Third
+		    && matches!(gamehand.last_action, None | Some(PlayerAction::PostAnte(_))) {
+		    // nobody has acted yet, so the lowest up card (who acts first) brings it in
+		    return PlayerAction::PostBringIn(cmp::min(self.small_blind, player.money));
+		}
+	    } else if gamehand.street == gamehand.variant.first_street()
+		&& gamehand.straddle_idx == Some(index)
+		&& player.last_action.is_none() {
+		// the player opted into the straddle before the hand (draw games straddle before the first draw)
+		return PlayerAction::PostStraddle(self.straddle.unwrap().amount(self.big_blind));
+	    } else if gamehand.street == gamehand.variant.first_street() && player.last_action.is_none() {
+		// the blind seats post their blinds when they first act.
+		// A seat that went all-in on the ante posts nothing, and the blind doesn't move on to the next player
+		if gamehand.small_blind_idx == Some(index) {
+		    return PlayerAction::PostSmallBlind(cmp::min(self.small_blind, player.money));
+		} else if gamehand.big_blind_idx == Some(index) {
 		    return PlayerAction::PostBigBlind(cmp::min(self.big_blind, player.money));
 		}
-	    } else {
-		// there was no action yet, so post small blind to begin
-		return PlayerAction::PostSmallBlind(cmp::min(self.small_blind, player.money));		
 	    }
 	    player.id
 	};
@@ -1236,20 +3063,24 @@ impl Table {
 			    );
 			    continue;
 			}
-			if (new_bet <= gamehand.current_bet) ||
-			    (new_bet < gamehand.current_bet + gamehand.min_raise &&
-			     (player_cumulative + player.money != new_bet)) {
-				// The new bet MUST be larger than the current bet.
-				// Also, the new bet must meet the min raise,
-				// UNLESS it puts them all-in, then it is fine
-				println!("new bet must be at least the minimum raise!");
-				println!("new_bet = {0}, current = {1}, min raise {2}",
-					 new_bet, gamehand.current_bet, gamehand.min_raise);
-				let min_bet = gamehand.current_bet + gamehand.min_raise;
+			if gamehand.betting_structure.is_fixed_limit() {
+			    // in fixed-limit, the bet must be exactly one more bet (unless it puts them all-in for less),
+			    // and there can only be so many bets per street
+			    let fixed_bet = gamehand.fixed_limit_bet();
+			    let all_in_for_less = new_bet == player_cumulative + player.money && new_bet < fixed_bet;
+			    let reason = if gamehand.is_capped() {
+				Some(format!("the betting is capped at {} bets this street", BettingStructure::FIXED_LIMIT_MAX_BETS))
+			    } else if new_bet != fixed_bet && !all_in_for_less {
+				Some(format!("this is fixed-limit, so the bet must be exactly {fixed_bet}"))
+			    } else {
+				None
+			    };
+			    if let Some(reason) = reason {
+				println!("invalid fixed-limit bet of {new_bet}: {reason}");
 				let message = json::object! {
 				    msg_type: "error".to_owned(),
 				    error: "invalid_action".to_owned(),
-				    reason: format!("the new bet must be at least the minumum: {min_bet}"),
+				    reason: reason,
 				};
 				PlayerConfig::send_specific_message(
 				    &message.dump(),
@@ -1258,12 +3089,65 @@ impl Table {
 				);
 				continue;
 			    }
-			action = Some(PlayerAction::Bet(new_bet));
-		    }
-		    other => {
-			action = other;
-		    }
-		}
+			}
+			if let Some(max_bet) = gamehand.betting_structure.max_bet(gamehand, index)
+			    .filter(|max_bet| new_bet > *max_bet) {
+			    // e.g. in a pot-limit game, the bet can be at most the size of the pot after calling
+			    println!("new bet {new_bet} is larger than the {} limit {max_bet}", gamehand.betting_structure);
+			    let message = json::object! {
+				msg_type: "error".to_owned(),
+				error: "invalid_action".to_owned(),
+				reason: format!("the new bet can be at most {max_bet}"),
+			    };
+			    PlayerConfig::send_specific_message(
+				&message.dump(),
+				player.id,
+				&self.player_ids_to_configs,
+			    );
+			    continue;
+			}
+			if (new_bet <= gamehand.current_bet) ||
+			    (new_bet < gamehand.current_bet + gamehand.min_raise &&
+			     (player_cumulative + player.money != new_bet)) {
+				// The new bet MUST be larger than the current bet.
+				// Also, the new bet must meet the min raise,
+				// UNLESS it puts them all-in, then it is fine
+				println!("new bet must be at least the minimum raise!");
+				println!("new_bet = {0}, current = {1}, min raise {2}",
+					 new_bet, gamehand.current_bet, gamehand.min_raise);
+				let min_bet = gamehand.current_bet + gamehand.min_raise;
+				let message = json::object! {
+				    msg_type: "error".to_owned(),
+				    error: "invalid_action".to_owned(),
+				    reason: format!("the new bet must be at least the minumum: {min_bet}"),
+				};
+				PlayerConfig::send_specific_message(
+				    &message.dump(),
+				    player.id,
+				    &self.player_ids_to_configs,
+				);
+				continue;
+			    }
+			action = Some(PlayerAction::Bet(new_bet));
+		    }
+		    Some(PlayerAction::Draw(_)) | Some(PlayerAction::Discard(_)) | Some(PlayerAction::RunIt(_)) => {
+			// these only count outside of the betting, so wait for a real action
+			let message = json::object! {
+			    msg_type: "error".to_owned(),
+			    error: "invalid_action".to_owned(),
+			    reason: "You need to bet, call, check, or fold!".to_owned(),
+			};
+			PlayerConfig::send_specific_message(
+			    &message.dump(),
+			    player.id,
+			    &self.player_ids_to_configs,
+			);
+			continue;
+		    }
+		    other => {
+			action = other;
+		    }
+		}
 	    }
         }
         // if we got a valid action, then we can return it,
@@ -3236,6 +5120,36 @@ mod tests {
 	assert_eq!(table.players[1].as_mut().unwrap().money, new_buy_in);	
     }
 
+    /// the admin can't change the blinds or the buy in during a tournament or freezeout
+    #[test]
+    fn admin_no_blinds_or_buy_in_during_event() {
+	for is_tournament in [true, false] {
+            let mut table = Table::default();
+            let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+	    if is_tournament {
+		let levels = vec![crate::logic::BlindLevel { small_blind: 5, big_blind: 10, ante: 1 }];
+		table.tournament = Some(Tournament::new(levels, crate::logic::BlindSchedule::EveryNHands(1)));
+	    } else {
+		table.freezeout = Some(Freezeout::new(2, 2, 500, 2));
+	    }
+            let id = uuid::Uuid::new_v4();
+	    table.admin_id = id;
+	    table.password = Some("arbitrary".to_string());
+	    let (small_blind, big_blind, buy_in) = (table.small_blind, table.big_blind, table.buy_in);
+
+	    for admin_command in [AdminCommand::SmallBlind(100), AdminCommand::BigBlind(200), AdminCommand::BuyIn(5000)] {
+		incoming_meta_actions
+		    .lock()
+		    .unwrap()
+		    .push_back(MetaAction::Admin(id, admin_command));
+	    }
+            table.handle_meta_actions(&incoming_meta_actions, true, None);
+	    assert_eq!(table.small_blind, small_blind);
+	    assert_eq!(table.big_blind, big_blind);
+	    assert_eq!(table.buy_in, buy_in);
+	}
+    }
+
     /// even if a player is_sitting_out, they still are obliged to pay the blinds as
     /// they come around.
     #[test]
@@ -4144,5 +6058,1454 @@ mod tests {
 	assert_eq!(player_1_money, 62);
 	assert_eq!(player_2_money, 1008);	
     }
-    
+
+    /// in pot-limit Omaha, a bet larger than the pot is rejected
+    /// the small blind tries to bet 100 preflop, but the most they can bet is 24
+    /// (the current bet of 8, plus the pot of 12, plus their call of 4)
+    #[test]
+    fn omaha_pot_limit_bet_too_large() {
+        let mut table = Table::default();
+	table.variant = Variant::Omaha;
+	table.betting_structure = BettingStructure::PotLimit;
+	table.player_action_timeout = 10;
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let cloned_actions = incoming_actions.clone();
+        let cloned_meta_actions = incoming_meta_actions.clone();
+
+        // player1 will start as the button
+        let id1 = uuid::Uuid::new_v4();
+        let name1 = "Human1".to_string();
+        let settings1 = PlayerConfig::new(id1, Some(name1), None);
+        table.add_human(settings1, None).unwrap();
+
+        // player2 will start as the small blind
+        let id2 = uuid::Uuid::new_v4();
+        let name2 = "Human2".to_string();
+        let settings2 = PlayerConfig::new(id2, Some(name2), None);
+        table.add_human(settings2, None).unwrap();
+
+        let handler = std::thread::spawn(move || {
+            table.play_one_hand(&cloned_actions, &cloned_meta_actions);
+            table // return the table back
+        });
+
+	// sleep so we dont drain the actions accidentally right at the beginning of play_one_hand
+        thread::sleep(time::Duration::from_secs_f32(0.2));
+
+        // player2 tries to bet more than the pot
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id2, PlayerAction::Bet(100));
+
+        thread::sleep(time::Duration::from_secs_f32(4.5));
+
+        // player2 now bets the pot, and player1 folds
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id2, PlayerAction::Bet(24));
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id1, PlayerAction::Fold);
+
+        // get the game back from the thread
+        let table = handler.join().unwrap();
+
+	// player1 lost their BB
+	assert_eq!(table.players[0].as_ref().unwrap().money, 992);
+	assert_eq!(table.players[1].as_ref().unwrap().money, 1008);
+    }
+
+    /// in fixed-limit, a bet that is not exactly one more small bet preflop is rejected.
+    /// the small blind tries to raise to 20, but the only raise allowed is to 16
+    #[test]
+    fn fixed_limit_off_size_bet_rejected() {
+        let mut table = Table::default();
+	table.betting_structure = BettingStructure::FixedLimit;
+	table.player_action_timeout = 10;
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let cloned_actions = incoming_actions.clone();
+        let cloned_meta_actions = incoming_meta_actions.clone();
+
+        // player1 will start as the button
+        let id1 = uuid::Uuid::new_v4();
+        let name1 = "Human1".to_string();
+        let settings1 = PlayerConfig::new(id1, Some(name1), None);
+        table.add_human(settings1, None).unwrap();
+
+        // player2 will start as the small blind
+        let id2 = uuid::Uuid::new_v4();
+        let name2 = "Human2".to_string();
+        let settings2 = PlayerConfig::new(id2, Some(name2), None);
+        table.add_human(settings2, None).unwrap();
+
+        let handler = std::thread::spawn(move || {
+            table.play_one_hand(&cloned_actions, &cloned_meta_actions);
+            table // return the table back
+        });
+
+	// sleep so we dont drain the actions accidentally right at the beginning of play_one_hand
+        thread::sleep(time::Duration::from_secs_f32(0.2));
+
+        // player2 tries to raise by more than one small bet
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id2, PlayerAction::Bet(20));
+
+        thread::sleep(time::Duration::from_secs_f32(4.5));
+
+        // player2 now raises by exactly one small bet, and player1 folds
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id2, PlayerAction::Bet(16));
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id1, PlayerAction::Fold);
+
+        // get the game back from the thread
+        let table = handler.join().unwrap();
+
+	// player1 lost their BB
+	assert_eq!(table.players[0].as_ref().unwrap().money, 992);
+	assert_eq!(table.players[1].as_ref().unwrap().money, 1008);
+    }
+
+    /// in seven card stud, everyone antes and the lowest up card is forced to bring it in.
+    /// player1 completes the bring-in to a full bet, and player2 folds
+    #[test]
+    fn stud_ante_bring_in_and_complete() {
+        let mut deck = RiggedDeck::new();
+        // player1's down cards
+        deck.push(Card {
+            rank: Rank::Ace,
+            suit: Suit::Club,
+        });
+        deck.push(Card {
+            rank: Rank::Ace,
+            suit: Suit::Diamond,
+        });
+        // player2's down cards
+        deck.push(Card {
+            rank: Rank::Two,
+            suit: Suit::Club,
+        });
+        deck.push(Card {
+            rank: Rank::Three,
+            suit: Suit::Diamond,
+        });
+        // the up cards, player2 has the lowest
+        deck.push(Card {
+            rank: Rank::King,
+            suit: Suit::Club,
+        });
+        deck.push(Card {
+            rank: Rank::Two,
+            suit: Suit::Heart,
+        });
+
+        let mut table = Table::default();
+        table.deck = Box::new(deck);
+	table.variant = Variant::SevenCardStud;
+	table.ante = 1;
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let cloned_actions = incoming_actions.clone();
+        let cloned_meta_actions = incoming_meta_actions.clone();
+
+        let id1 = uuid::Uuid::new_v4();
+        let name1 = "Human1".to_string();
+        let settings1 = PlayerConfig::new(id1, Some(name1), None);
+        table.add_human(settings1, None).unwrap();
+
+        let id2 = uuid::Uuid::new_v4();
+        let name2 = "Human2".to_string();
+        let settings2 = PlayerConfig::new(id2, Some(name2), None);
+        table.add_human(settings2, None).unwrap();
+
+        let handler = std::thread::spawn(move || {
+            table.play_one_hand(&cloned_actions, &cloned_meta_actions);
+            table // return the table back
+        });
+
+	// sleep so we dont drain the actions accidentally right at the beginning of play_one_hand
+        thread::sleep(time::Duration::from_secs_f32(0.2));
+
+        // player2 brings it in for 4, which player1 completes to 8
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id1, PlayerAction::Bet(8));
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id2, PlayerAction::Fold);
+
+        // get the game back from the thread
+        let table = handler.join().unwrap();
+
+	// player1 wins both antes and the bring-in
+	assert_eq!(table.players[0].as_ref().unwrap().money, 1005);
+	assert_eq!(table.players[1].as_ref().unwrap().money, 995);
+	assert!(table.players[0].as_ref().unwrap().up_cards.is_empty());
+    }
+
+    /// with a big blind ante, the big blind pays one ante for the table before the blinds,
+    /// and still wins it back when the small blind folds
+    #[test]
+    fn big_blind_ante_small_blind_folds() {
+        let mut table = Table::default();
+	table.ante = 10;
+	table.big_blind_ante = true;
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let cloned_actions = incoming_actions.clone();
+        let cloned_meta_actions = incoming_meta_actions.clone();
+
+        let id1 = uuid::Uuid::new_v4();
+        let name1 = "Human1".to_string();
+        let settings1 = PlayerConfig::new(id1, Some(name1), None);
+        table.add_human(settings1, None).unwrap();
+
+        let id2 = uuid::Uuid::new_v4();
+        let name2 = "Human2".to_string();
+        let settings2 = PlayerConfig::new(id2, Some(name2), None);
+        table.add_human(settings2, None).unwrap();
+
+        let handler = std::thread::spawn(move || {
+            table.play_one_hand(&cloned_actions, &cloned_meta_actions);
+            table // return the table back
+        });
+
+	// sleep so we dont drain the actions accidentally right at the beginning of play_one_hand
+        thread::sleep(time::Duration::from_secs_f32(0.2));
+
+        // player2 is the small blind, and folds
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id2, PlayerAction::Fold);
+
+        // get the game back from the thread
+        let table = handler.join().unwrap();
+
+	// player1 paid the big blind and the ante, and wins them back plus the small blind
+	assert_eq!(table.players[0].as_ref().unwrap().money, 1004);
+	assert_eq!(table.players[1].as_ref().unwrap().money, 996);
+    }
+
+    /// a big blind who can't afford both the blind and the big blind ante posts the blind first,
+    /// so the player under the gun never has to post it for them
+    #[test]
+    fn big_blind_ante_short_stack() {
+        let mut table = Table::default();
+	table.ante = 10;
+	table.big_blind_ante = true;
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let cloned_actions = incoming_actions.clone();
+        let cloned_meta_actions = incoming_meta_actions.clone();
+
+	// player0 is the button (and under the gun), player1 the small blind, and player2 the big blind
+	let mut ids = vec![];
+	for i in 0..3 {
+            let id = uuid::Uuid::new_v4();
+            let settings = PlayerConfig::new(id, Some(format!("Human{}", i)), None);
+            table.add_human(settings, None).unwrap();
+	    ids.push(id);
+	}
+	// the big blind's stack is no bigger than the ante
+	table.players[2].as_mut().unwrap().money = 8;
+
+        let handler = std::thread::spawn(move || {
+            table.play(&cloned_actions, &cloned_meta_actions, Some(1));
+            table // return the table back
+        });
+
+	// sleep so we dont drain the actions accidentally right at the beginning of play_one_hand
+        thread::sleep(time::Duration::from_secs_f32(0.5));
+	for id in [ids[0], ids[1]] {
+            incoming_actions
+		.lock()
+		.unwrap()
+		.insert(id, PlayerAction::Fold);
+	}
+
+        // get the game back from the thread
+        let table = handler.join().unwrap();
+
+	// the big blind went all-in for the blind and no ante, and won the small blind
+	assert_eq!(table.players[0].as_ref().unwrap().money, 1000);
+	assert_eq!(table.players[1].as_ref().unwrap().money, 996);
+	assert_eq!(table.players[2].as_ref().unwrap().money, 12);
+    }
+
+    /// the small blind goes all-in on the ante, so only the big blind posts,
+    /// and the blinds don't move on to the player under the gun
+    #[test]
+    fn ante_all_in_small_blind_keeps_the_blinds() {
+        let mut table = Table::default();
+	table.ante = 10;
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let cloned_actions = incoming_actions.clone();
+        let cloned_meta_actions = incoming_meta_actions.clone();
+
+	// player0 is the button (and under the gun), player1 the small blind, and player2 the big blind
+	let mut ids = vec![];
+	for i in 0..3 {
+            let id = uuid::Uuid::new_v4();
+            let settings = PlayerConfig::new(id, Some(format!("Human{}", i)), None);
+            table.add_human(settings, None).unwrap();
+	    ids.push(id);
+	}
+	table.players[1].as_mut().unwrap().money = 10;
+
+        let handler = std::thread::spawn(move || {
+            table.play_one_hand(&cloned_actions, &cloned_meta_actions);
+            table // return the table back
+        });
+
+	// sleep so we dont drain the actions accidentally right at the beginning of play_one_hand
+        thread::sleep(time::Duration::from_secs_f32(0.2));
+	// player0 folds, and the big blind checks their option
+	for (id, action) in [(ids[0], PlayerAction::Fold), (ids[2], PlayerAction::Check)] {
+            incoming_actions
+		.lock()
+		.unwrap()
+		.insert(id, action);
+	}
+
+        // get the game back from the thread
+        let table = handler.join().unwrap();
+
+	// player0 only paid the ante, and the small blind and big blind played out the rest
+	assert_eq!(table.players[0].as_ref().unwrap().money, 990);
+	let blinds_money = table.players[1].as_ref().unwrap().money + table.players[2].as_ref().unwrap().money;
+	assert_eq!(blinds_money, 1020);
+    }
+
+    /// plays one hand of the given variant with four humans, where the given player opted into straddling,
+    /// and returns each player's money afterwards
+    fn play_straddle_hand(variant: Variant, straddle: Straddle, straddler: usize, actions: Vec<(usize, PlayerAction)>) -> Vec<u32> {
+        let mut table = Table::default();
+	table.variant = variant;
+	table.straddle = Some(straddle);
+	table.player_action_timeout = 3;
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let cloned_actions = incoming_actions.clone();
+        let cloned_meta_actions = incoming_meta_actions.clone();
+
+	let mut ids = vec![];
+	for i in 0..4 {
+            let id = uuid::Uuid::new_v4();
+            let settings = PlayerConfig::new(id, Some(format!("Human{}", i)), None);
+            table.add_human(settings, None).unwrap();
+	    ids.push(id);
+	}
+	table.players[straddler].as_mut().unwrap().wants_to_straddle = true;
+
+        let handler = std::thread::spawn(move || {
+            table.play_one_hand(&cloned_actions, &cloned_meta_actions);
+            table // return the table back
+        });
+
+	// sleep so we dont drain the actions accidentally right at the beginning of play_one_hand
+        thread::sleep(time::Duration::from_secs_f32(0.2));
+	for (i, action) in actions {
+            incoming_actions
+		.lock()
+		.unwrap()
+		.insert(ids[i], action);
+	}
+
+        // get the game back from the thread
+        let table = handler.join().unwrap();
+	table.players.iter().flatten().map(|player| player.money).collect()
+    }
+
+    /// the player under the gun straddles for twice the big blind, and wins the blinds when everyone folds
+    #[test]
+    fn under_the_gun_straddle_wins_blinds() {
+	let actions = vec![
+	    (0, PlayerAction::Fold),
+	    (1, PlayerAction::Fold),
+	    (2, PlayerAction::Fold),
+	];
+	let money = play_straddle_hand(Variant::Holdem, Straddle::UnderTheGun, 3, actions);
+	assert_eq!(money, vec![1000, 996, 992, 1012]);
+    }
+
+    /// draw games have blinds too, so the straddle is posted before the first draw
+    #[test]
+    fn draw_game_straddle() {
+	let actions = vec![
+	    (0, PlayerAction::Fold),
+	    (1, PlayerAction::Fold),
+	    (2, PlayerAction::Fold),
+	];
+	let money = play_straddle_hand(Variant::DeuceToSevenTripleDraw, Straddle::UnderTheGun, 3, actions);
+	assert_eq!(money, vec![1000, 996, 992, 1012]);
+    }
+
+    /// the button straddles before the blinds, a raise must be at least the size of the straddle,
+    /// and the button acts after the blinds
+    #[test]
+    fn button_straddle_acts_last() {
+	let actions = vec![
+	    // a raise to 24 is too small, so player3 times out and sits out instead.
+	    // The button has no action, so the blinds must fold before the button gets asked
+	    (3, PlayerAction::Bet(24)),
+	    (1, PlayerAction::Fold),
+	    (2, PlayerAction::Fold),
+	];
+	let money = play_straddle_hand(Variant::Holdem, Straddle::Button, 0, actions);
+	assert_eq!(money, vec![1012, 996, 992, 1000]);
+    }
+
+    /// the straddle position depends on the table's straddle, and nobody straddles heads-up
+    #[test]
+    fn straddle_position() {
+        let mut table = Table::default();
+	for i in 0..4 {
+	    table.add_bot(format!("Bot {}", i)).unwrap();
+	}
+	for player in table.players.iter_mut().flatten() {
+	    player.is_active = true;
+	    player.wants_to_straddle = true;
+	}
+	table.button_idx = 1;
+	assert_eq!(table.get_straddle_idx(), None);
+	table.straddle = Some(Straddle::UnderTheGun);
+	assert_eq!(table.get_straddle_idx(), Some(0));
+	table.straddle = Some(Straddle::Button);
+	assert_eq!(table.get_straddle_idx(), Some(1));
+	table.players[1].as_mut().unwrap().wants_to_straddle = false;
+	assert_eq!(table.get_straddle_idx(), None);
+	table.players[1].as_mut().unwrap().wants_to_straddle = true;
+	table.players[2] = None;
+	table.players[3] = None;
+	assert_eq!(table.get_straddle_idx(), None);
+    }
+
+    /// a mixed game deals the next variant in the rotation every N hands
+    #[test]
+    fn mixed_game_rotates_variants() {
+        let mut table = Table::default();
+	table.rotation = Some(GameRotation::new(
+	    vec![Variant::Holdem, Variant::Razz],
+	    RotationSchedule::EveryNHands(1),
+	));
+	for i in 0..3 {
+	    table.add_bot(format!("Bot {}", i)).unwrap();
+	}
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+	table.play(&incoming_actions, &incoming_meta_actions, Some(2));
+
+	// the second hand was Razz, and Hold'em is up next
+	assert_eq!(table.variant, Variant::Razz);
+	assert_eq!(table.rotation.as_ref().unwrap().current_variant(), Variant::Holdem);
+	let total: u32 = table.players.iter().flatten().map(|player| player.money).sum();
+	assert_eq!(total, 3000);
+    }
+
+    /// without a configured betting structure, each variant of a mixed game is dealt with its usual one
+    #[test]
+    fn mixed_game_uses_each_variants_betting_structure() {
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+	for configured in [false, true] {
+            let mut table = Table::default();
+	    table.rotation = Some(GameRotation::new(
+		vec![Variant::Holdem, Variant::Omaha, Variant::Holdem],
+		RotationSchedule::EveryNHands(1),
+	    ));
+	    table.betting_structure_configured = configured;
+	    let mut structures = vec![];
+	    for _ in 0..3 {
+		table.set_up_variant(&incoming_meta_actions);
+		structures.push(table.betting_structure);
+		table.rotation.as_mut().unwrap().finish_hand();
+	    }
+	    if configured {
+		assert_eq!(structures, vec![BettingStructure::NoLimit; 3]);
+	    } else {
+		assert_eq!(structures, vec![BettingStructure::NoLimit, BettingStructure::PotLimit, BettingStructure::NoLimit]);
+	    }
+	}
+    }
+
+    /// in a bomb pot, everyone puts in the bomb pot amount, there are no blinds,
+    /// and the betting starts on the flop
+    #[test]
+    fn bomb_pot_starts_on_flop() {
+        let mut table = Table::default();
+	table.bomb_pot_next_hand = true;
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let cloned_actions = incoming_actions.clone();
+        let cloned_meta_actions = incoming_meta_actions.clone();
+
+        let id1 = uuid::Uuid::new_v4();
+        let name1 = "Human1".to_string();
+        let settings1 = PlayerConfig::new(id1, Some(name1), None);
+        table.add_human(settings1, None).unwrap();
+
+        let id2 = uuid::Uuid::new_v4();
+        let name2 = "Human2".to_string();
+        let settings2 = PlayerConfig::new(id2, Some(name2), None);
+        table.add_human(settings2, None).unwrap();
+
+        let handler = std::thread::spawn(move || {
+            table.play_one_hand(&cloned_actions, &cloned_meta_actions);
+            table // return the table back
+        });
+
+	// sleep so we dont drain the actions accidentally right at the beginning of play_one_hand
+        thread::sleep(time::Duration::from_secs_f32(0.2));
+
+        // player2 acts first on the flop and bets, then player1 folds
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id2, PlayerAction::Bet(100));
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id1, PlayerAction::Fold);
+
+        // get the game back from the thread
+        let table = handler.join().unwrap();
+
+	// player2 wins both bomb pot contributions, and nobody paid a blind
+	assert_eq!(table.players[0].as_ref().unwrap().money, 984);
+	assert_eq!(table.players[1].as_ref().unwrap().money, 1016);
+	assert!(!table.bomb_pot_next_hand);
+    }
+
+    /// both all-in players vote to run the board twice, and each runout is played for half of each pot.
+    /// The main pot has an odd chip (from the button's ante), which goes to the first runout
+    #[test]
+    fn run_it_twice_at_the_table() {
+        let mut deck = RiggedDeck::new();
+	// the button's hole cards, then the small blind's aces, then the big blind's kings
+	for (rank, suit) in [
+	    (Rank::Two, Suit::Club),
+	    (Rank::Three, Suit::Diamond),
+	    (Rank::Ace, Suit::Club),
+	    (Rank::Ace, Suit::Diamond),
+	    (Rank::King, Suit::Club),
+	    (Rank::King, Suit::Diamond),
+	] {
+	    deck.push(Card { rank, suit });
+	}
+	// each street is dealt to the first runout, then to the second.
+	// The aces hold up on the first runout, and the kings make a set on the second
+	for (rank, suit) in [
+	    (Rank::Two, Suit::Heart),
+	    (Rank::Seven, Suit::Spade),
+	    (Rank::Nine, Suit::Heart),
+	    (Rank::Five, Suit::Heart),
+	    (Rank::Eight, Suit::Spade),
+	    (Rank::Ten, Suit::Heart),
+	    (Rank::Jack, Suit::Spade),
+	    (Rank::King, Suit::Heart),
+	    (Rank::Four, Suit::Club),
+	    (Rank::Three, Suit::Club),
+	] {
+	    deck.push(Card { rank, suit });
+	}
+        let mut table = Table::default();
+        table.deck = Box::new(deck);
+	table.max_runouts = 2;
+	table.ante = 1;
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let cloned_actions = incoming_actions.clone();
+        let cloned_meta_actions = incoming_meta_actions.clone();
+
+	// player0 is the button, and the blinds are all-in once they post
+	let mut ids = vec![];
+	for i in 0..3 {
+            let id = uuid::Uuid::new_v4();
+            let settings = PlayerConfig::new(id, Some(format!("Human{}", i)), None);
+            table.add_human(settings, None).unwrap();
+	    ids.push(id);
+	}
+	table.players[1].as_mut().unwrap().money = 5;
+	table.players[2].as_mut().unwrap().money = 9;
+
+        let handler = std::thread::spawn(move || {
+            table.play_one_hand(&cloned_actions, &cloned_meta_actions);
+            table // return the table back
+        });
+
+	// sleep so we dont drain the actions accidentally right at the beginning of play_one_hand
+        thread::sleep(time::Duration::from_secs_f32(0.2));
+	// the button folds, and the blinds agree to run it twice
+	for (id, action) in [(ids[0], PlayerAction::Fold), (ids[1], PlayerAction::RunIt(2)), (ids[2], PlayerAction::RunIt(2))] {
+            incoming_actions
+		.lock()
+		.unwrap()
+		.insert(id, action);
+	}
+
+        // get the game back from the thread
+        let table = handler.join().unwrap();
+
+	// the main pot of 11 is split 6/5 between the runouts,
+	// and the big blind's extra 4 chips are only contested by themselves
+	assert_eq!(table.players[0].as_ref().unwrap().money, 999);
+	assert_eq!(table.players[1].as_ref().unwrap().money, 6);
+	assert_eq!(table.players[2].as_ref().unwrap().money, 9);
+    }
+
+    /// whoever wins holding 7-2 collects the bounty from everyone else, but only once they show it
+    #[test]
+    fn seven_deuce_bounty_when_shown() {
+        let mut table = Table::default();
+	table.seven_deuce_bounty = 10;
+	let ids: Vec<Uuid> = (0..3).map(|_| uuid::Uuid::new_v4()).collect();
+	for (i, id) in ids.iter().enumerate() {
+	    let settings = PlayerConfig::new(*id, Some(format!("Human{}", i)), None);
+	    table.add_human(settings, None).unwrap();
+	}
+	for player in table.players.iter_mut().flatten() {
+	    player.hole_cards = vec![Card { rank: Rank::King, suit: Suit::Club }, Card { rank: Rank::Two, suit: Suit::Club }];
+	}
+	let winner = table.players.iter_mut().flatten().find(|player| player.id == ids[0]).unwrap();
+	winner.hole_cards = vec![Card { rank: Rank::Seven, suit: Suit::Club }, Card { rank: Rank::Two, suit: Suit::Diamond }];
+	// a bet that nobody calls isn't a win
+	let mut gamehand = GameHand::with_variant(Variant::Holdem, 8, &table.players);
+	for (i, player) in table.players.iter_mut().enumerate().filter_map(|(i, spot)| spot.as_mut().map(|player| (i, player))) {
+	    player.is_active = player.id == ids[0];
+	    if player.id == ids[0] {
+		gamehand.contribute(i, player.id, 30, false, false);
+	    }
+	}
+	let settlements = gamehand.divvy_pots(&mut table.players, &table.player_ids_to_configs, 0);
+	assert!(table.find_seven_deuce_winners(&gamehand, &settlements).is_empty());
+	table.players.iter_mut().flatten().find(|player| player.id == ids[0]).unwrap().money -= 30;
+
+	// the 7-2 wins the pot without a showdown
+	let mut gamehand = GameHand::with_variant(Variant::Holdem, 8, &table.players);
+	for (i, player) in table.players.iter().enumerate().filter_map(|(i, spot)| spot.as_ref().map(|player| (i, player))) {
+	    gamehand.contribute(i, player.id, 10, false, false);
+	}
+	let settlements = gamehand.divvy_pots(&mut table.players, &table.player_ids_to_configs, 0);
+	table.seven_deuce_winners = table.find_seven_deuce_winners(&gamehand, &settlements);
+	assert_eq!(table.seven_deuce_winners, vec![ids[0]]);
+
+	let money = |table: &Table, id: Uuid| table.players.iter().flatten().find(|player| player.id == id).unwrap().money;
+	// nobody can show their cards until the hand is over
+	assert!(table.show_cards(ids[0]).is_none());
+	table.hand_over = true;
+	// a loser can show their cards, but they collect nothing
+	let message = table.show_cards(ids[1]).unwrap();
+	assert!(message["settlements"].is_null());
+	assert_eq!(money(&table, ids[1]), 1000);
+
+	let message = table.show_cards(ids[0]).unwrap();
+	assert_eq!(message["hole_cards"], "7c2d");
+	assert_eq!(message["settlements"][0]["payout"], 20);
+	assert_eq!(message["settlements"][0]["paid_by"].len(), 2);
+	assert_eq!(money(&table, ids[0]), 1050);
+	assert_eq!(money(&table, ids[1]), 990);
+	assert_eq!(money(&table, ids[2]), 990);
+	// showing again doesn't collect the bounty twice
+	let message = table.show_cards(ids[0]).unwrap();
+	assert!(message["settlements"].is_null());
+	assert_eq!(money(&table, ids[0]), 1050);
+    }
+
+    /// in a knockout game, the seven-deuce bounty can't take anyone's last chip, so it never busts them
+    #[test]
+    fn seven_deuce_bounty_leaves_last_chip() {
+        let mut table = Table::default();
+	table.seven_deuce_bounty = 10;
+	table.knockout = Some(Knockout::new(100, false));
+	let ids: Vec<Uuid> = (0..3).map(|_| uuid::Uuid::new_v4()).collect();
+	for (i, id) in ids.iter().enumerate() {
+	    let settings = PlayerConfig::new(*id, Some(format!("Human{}", i)), None);
+	    table.add_human(settings, None).unwrap();
+	}
+	let money = |table: &Table, id: Uuid| table.players.iter().flatten().find(|player| player.id == id).unwrap().money;
+	for player in table.players.iter_mut().flatten() {
+	    if player.id == ids[0] {
+		player.hole_cards = vec![Card { rank: Rank::Seven, suit: Suit::Club }, Card { rank: Rank::Two, suit: Suit::Diamond }];
+	    } else if player.id == ids[1] {
+		player.money = 5;
+	    } else {
+		player.money = 1;
+	    }
+	}
+	table.seven_deuce_winners = vec![ids[0]];
+	table.hand_over = true;
+
+	let message = table.show_cards(ids[0]).unwrap();
+	// the short stack pays all but one chip, and the player with one chip pays nothing
+	assert_eq!(message["settlements"][0]["payout"], 4);
+	assert_eq!(message["settlements"][0]["paid_by"].len(), 1);
+	assert_eq!(money(&table, ids[0]), 1004);
+	assert_eq!(money(&table, ids[1]), 1);
+	assert_eq!(money(&table, ids[2]), 1);
+	let knockout = table.knockout.as_ref().unwrap();
+	assert_eq!(knockout.collected(ids[0]), 0);
+    }
+
+    /// a sit-and-go waits for every seat, then busted players get finishing positions until one is left
+    #[test]
+    fn sit_and_go_finishing_positions() {
+        let mut table = Table::default();
+	table.max_players = 3;
+	table.buy_in = 500;
+	let levels = vec![crate::logic::BlindLevel { small_blind: 5, big_blind: 10, ante: 1 }];
+	table.tournament = Some(Tournament::new(levels, crate::logic::BlindSchedule::EveryNHands(1)));
+	let ids: Vec<Uuid> = (0..3).map(|_| uuid::Uuid::new_v4()).collect();
+	for (i, id) in ids.iter().take(2).enumerate() {
+	    let settings = PlayerConfig::new(*id, Some(format!("Human{}", i)), None);
+	    table.add_human(settings, None).unwrap();
+	}
+	// not enough players yet
+	assert!(!table.start_tournament_if_full());
+	let settings = PlayerConfig::new(ids[2], Some("Human2".to_string()), None);
+	table.add_human(settings, None).unwrap();
+	assert!(table.start_tournament_if_full());
+	assert!(table.players.iter().flatten().all(|player| player.money == 500));
+	assert_eq!((table.small_blind, table.big_blind, table.ante), (5, 10, 1));
+	// nobody else can join once it has started
+	table.max_players = 4;
+	let late = PlayerConfig::new(uuid::Uuid::new_v4(), Some("Late".to_string()), None);
+	assert!(matches!(table.add_human(late, None), Err(JoinTableError::TournamentStarted)));
+
+	let set_money = |table: &mut Table, moneys: [u32; 3]| {
+	    for (player, money) in table.players.iter_mut().flatten().zip(moneys.iter()) {
+		player.money = *money;
+	    }
+	};
+	// Human1 busts first, and the blinds go up after the hand
+	set_money(&mut table, [700, 0, 800]);
+	table.finish_tournament_hand(&[500, 500, 500]);
+	assert!(table.players[1].is_none());
+	assert_eq!((table.small_blind, table.big_blind, table.ante), (10, 20, 2));
+	assert!(!table.tournament.as_ref().unwrap().is_over());
+	// Human0 and Human2 both bust to Human2, and Human0 started with less, so finishes lower
+	table.players[0].as_mut().unwrap().money = 0;
+	table.players[2].as_mut().unwrap().money = 1500;
+	table.finish_tournament_hand(&[700, 0, 800]);
+	let tournament = table.tournament.as_ref().unwrap();
+	assert!(tournament.is_over());
+	assert_eq!(tournament.results(), vec![
+	    (1, "Human2".to_string()),
+	    (2, "Human0".to_string()),
+	    (3, "Human1".to_string()),
+	]);
+    }
+
+    /// a deal ends a sit-and-go, with the players in it finishing in order of their prizes
+    #[test]
+    fn sit_and_go_deal_finishing_positions() {
+        let mut table = Table::default();
+	table.max_players = 3;
+	table.buy_in = 500;
+	let levels = vec![crate::logic::BlindLevel { small_blind: 5, big_blind: 10, ante: 0 }];
+	table.tournament = Some(Tournament::new(levels, crate::logic::BlindSchedule::EveryNHands(1)));
+	let ids: Vec<Uuid> = (0..3).map(|_| uuid::Uuid::new_v4()).collect();
+	for (i, id) in ids.iter().enumerate() {
+	    let settings = PlayerConfig::new(*id, Some(format!("Human{}", i)), None);
+	    table.add_human(settings, None).unwrap();
+	}
+	assert!(table.start_tournament_if_full());
+	for player in table.players.iter_mut().flatten() {
+	    player.money = match player.id {
+		id if id == ids[0] => 300,
+		id if id == ids[1] => 1000,
+		_ => 200,
+	    };
+	}
+	table.propose_deal(ids[0], DealMethod::ChipChop, vec![]).unwrap();
+	table.respond_to_deal(ids[1], true).unwrap();
+	table.respond_to_deal(ids[2], true).unwrap();
+	table.record_deal();
+
+	let tournament = table.tournament.as_ref().unwrap();
+	assert!(tournament.is_over());
+	assert_eq!(tournament.results(), vec![
+	    (1, "Human1".to_string()),
+	    (2, "Human0".to_string()),
+	    (3, "Human2".to_string()),
+	]);
+    }
+
+    /// a multi-table tournament's players are seated by the hub, with their stacks, and can be moved away between hands
+    #[test]
+    fn multi_table_seating_and_busting() {
+        let mut table = Table::default();
+	table.tournament_name = Some("ABCD".to_string());
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+	let ids: Vec<Uuid> = (0..3).map(|_| uuid::Uuid::new_v4()).collect();
+	for (i, id) in ids.iter().enumerate() {
+	    let settings = PlayerConfig::new(*id, Some(format!("Human{}", i)), None);
+	    incoming_meta_actions.lock().unwrap().push_back(MetaAction::TournamentSeat(settings, 300 + i as u32));
+	}
+	table.handle_meta_actions(&incoming_meta_actions, true, None);
+	let stacks: Vec<u32> = table.players.iter().flatten().map(|player| player.money).collect();
+	assert_eq!(stacks, vec![300, 301, 302]);
+	// nobody can join on their own
+	let settings = PlayerConfig::new(uuid::Uuid::new_v4(), Some("Late".to_string()), None);
+	assert!(matches!(table.add_human(settings, None), Err(JoinTableError::TournamentStarted)));
+
+	// a reseat waits until the hand is over
+	incoming_meta_actions.lock().unwrap().push_back(MetaAction::Reseat(ids[2], "ABCD-2".to_string()));
+	table.handle_meta_actions(&incoming_meta_actions, false, None);
+	assert_eq!(table.players.iter().flatten().count(), 3);
+	table.handle_meta_actions(&incoming_meta_actions, true, None);
+	assert_eq!(table.players.iter().flatten().count(), 2);
+	assert!(!table.player_ids_to_configs.contains_key(&ids[2]));
+
+	// the busted player gives up their seat
+	table.players[0].as_mut().unwrap().money = 0;
+	table.players[1].as_mut().unwrap().money = 601;
+	table.finish_multi_table_hand(&[300, 301, 0]);
+	assert!(table.players[0].is_none());
+	assert!(!table.player_ids_to_configs.contains_key(&ids[0]));
+	assert!(table.players[1].is_some());
+    }
+
+    /// once the hub says the multi-table tournament is over, the final table sends the winner back and stops
+    #[test]
+    fn multi_table_final_table_closes() {
+        let mut table = Table::default();
+	table.tournament_name = Some("ABCD".to_string());
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+	let id = uuid::Uuid::new_v4();
+	let settings = PlayerConfig::new(id, Some("Winner".to_string()), None);
+	incoming_meta_actions.lock().unwrap().push_back(MetaAction::TournamentSeat(settings, 900));
+	incoming_meta_actions.lock().unwrap().push_back(MetaAction::TournamentOver);
+        let handler = std::thread::spawn(move || {
+	    // the winner is alone, so without the tournament being over this would never return
+            table.play(&incoming_actions, &incoming_meta_actions, None);
+            table
+        });
+	for _ in 0..50 {
+	    if handler.is_finished() {
+		break;
+	    }
+	    thread::sleep(time::Duration::from_secs_f32(0.1));
+	}
+	assert!(handler.is_finished());
+        let table = handler.join().unwrap();
+	assert!(table.players.iter().all(|spot| spot.is_none()));
+	assert!(!table.player_ids_to_configs.contains_key(&id));
+    }
+
+    /// in a freezeout, a broke player can rebuy during the window, and is eliminated once it closes
+    #[test]
+    fn freezeout_rebuys_and_elimination() {
+        let mut table = Table::default();
+	table.freezeout = Some(Freezeout::new(2, 2, 500, 2));
+	let ids: Vec<Uuid> = (0..3).map(|_| uuid::Uuid::new_v4()).collect();
+	for (i, id) in ids.iter().take(2).enumerate() {
+	    let settings = PlayerConfig::new(*id, Some(format!("Human{}", i)), None);
+	    table.add_human(settings, None).unwrap();
+	}
+	let money = |table: &Table, id: Uuid| table.players.iter().flatten().find(|player| player.id == id).map(|player| player.money);
+
+	// Human0 goes broke, and rebuys once
+	table.players[0].as_mut().unwrap().money = 0;
+	table.players[1].as_mut().unwrap().money = 2000;
+	table.eliminate_broke_players();
+	assert_eq!(money(&table, ids[0]), Some(0));
+	assert!(table.add_on(ids[0]).is_none()); // no add-on until the end of the window
+	assert!(table.rebuy(ids[0]).is_some());
+	assert_eq!(money(&table, ids[0]), Some(1000));
+	assert!(table.rebuy(ids[0]).is_none()); // only broke players can rebuy
+	assert!(table.propose_deal(ids[1], DealMethod::ChipChop, vec![]).is_err()); // no deals until the rebuys are over
+	table.hand_num = 2;
+	assert!(table.add_on(ids[0]).is_some());
+	assert!(table.add_on(ids[0]).is_none()); // only once
+	assert_eq!(money(&table, ids[0]), Some(1500));
+
+	// late entry closes after hand 2
+	table.hand_num = 3;
+	let late = PlayerConfig::new(ids[2], Some("Late".to_string()), None);
+	assert!(matches!(table.add_human(late, None), Err(JoinTableError::LateEntryClosed)));
+
+	// the rebuy window is still open for the break after the last rebuy hand, then Human0 is out
+	table.players[0].as_mut().unwrap().money = 0;
+	table.eliminate_broke_players();
+	assert_eq!(money(&table, ids[0]), Some(0));
+	table.hand_num = 4;
+	table.eliminate_broke_players();
+	assert_eq!(money(&table, ids[0]), None);
+	assert!(!table.player_ids_to_configs.contains_key(&ids[0]));
+
+	let freezeout = table.freezeout.as_ref().unwrap();
+	assert_eq!(freezeout.prize_pool(), 3500);
+	assert_eq!(freezeout.ledger()[0].rebuys, 1);
+	assert!(freezeout.ledger()[0].add_on);
+    }
+
+    /// a player who is all-in in the middle of a hand can't rebuy until the hand is over
+    #[test]
+    fn freezeout_rebuy_waits_for_the_hand() {
+        let mut table = Table::default();
+	table.freezeout = Some(Freezeout::new(2, 2, 500, 2));
+	let ids: Vec<Uuid> = (0..2).map(|_| uuid::Uuid::new_v4()).collect();
+	for (i, id) in ids.iter().enumerate() {
+	    let settings = PlayerConfig::new(*id, Some(format!("Human{}", i)), None);
+	    table.add_human(settings, None).unwrap();
+	}
+	for player in table.players.iter_mut().flatten() {
+	    player.is_active = true;
+	}
+	let gamehand = GameHand::with_variant(Variant::Holdem, table.big_blind, &table.players);
+	// Human0 is all-in, but still in the hand
+	table.players[0].as_mut().unwrap().money = 0;
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+	incoming_meta_actions.lock().unwrap().push_back(MetaAction::Rebuy(ids[0]));
+	table.handle_meta_actions(&incoming_meta_actions, false, Some(&gamehand));
+	assert_eq!(table.players[0].as_ref().unwrap().money, 0);
+	assert_eq!(incoming_meta_actions.lock().unwrap().len(), 1);
+	assert_eq!(table.freezeout.as_ref().unwrap().prize_pool(), 2000);
+
+	// once the hand is over, the rebuy goes through
+	table.handle_meta_actions(&incoming_meta_actions, true, None);
+	assert_eq!(table.players[0].as_ref().unwrap().money, 1000);
+	assert!(incoming_meta_actions.lock().unwrap().is_empty());
+	assert_eq!(table.freezeout.as_ref().unwrap().prize_pool(), 3000);
+    }
+
+    /// once the rebuys and late entry are closed, the freezeout ends when one player has all the chips
+    #[test]
+    fn freezeout_plays_down_to_one_player() {
+        let mut deck = RiggedDeck::new();
+	// the button/big blind's aces, then the small blind's seven deuce, then the run out
+	for (rank, suit) in [
+	    (Rank::Ace, Suit::Club),
+	    (Rank::Ace, Suit::Diamond),
+	    (Rank::Seven, Suit::Heart),
+	    (Rank::Two, Suit::Spade),
+	    (Rank::King, Suit::Diamond),
+	    (Rank::Nine, Suit::Spade),
+	    (Rank::Four, Suit::Club),
+	    (Rank::Eight, Suit::Heart),
+	    (Rank::Queen, Suit::Spade),
+	] {
+	    deck.push(Card { rank, suit });
+	}
+        let mut table = Table::default();
+        table.deck = Box::new(deck);
+	table.freezeout = Some(Freezeout::new(0, 0, 0, 1));
+	let ids: Vec<Uuid> = (0..2).map(|_| uuid::Uuid::new_v4()).collect();
+	for (i, id) in ids.iter().enumerate() {
+	    let settings = PlayerConfig::new(*id, Some(format!("Human{}", i)), None);
+	    table.add_human(settings, None).unwrap();
+	}
+	// both players are all-in as soon as they post the blinds
+	for player in table.players.iter_mut().flatten() {
+	    player.money = 4;
+	}
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let handler = std::thread::spawn(move || {
+	    // with one player left, no more hands are dealt, so without the end check this would never return
+            table.play(&incoming_actions, &incoming_meta_actions, Some(10));
+            table
+        });
+	for _ in 0..300 {
+	    if handler.is_finished() {
+		break;
+	    }
+	    thread::sleep(time::Duration::from_secs_f32(0.1));
+	}
+	assert!(handler.is_finished());
+        let table = handler.join().unwrap();
+
+	assert_eq!(table.hand_num, 2);
+	assert_eq!(table.players.iter().flatten().count(), 1);
+	assert_eq!(table.players[0].as_ref().unwrap().money, 8);
+	assert!(!table.player_ids_to_configs.contains_key(&ids[1]));
+    }
+
+    /// the big blind is all-in for the blind and loses, so the small blind collects their bounty
+    #[test]
+    fn knockout_bounty_for_all_in_loser() {
+        let mut deck = RiggedDeck::new();
+	// the button/big blind's hole cards, then the small blind's, then the run out
+	for (rank, suit) in [
+	    (Rank::Two, Suit::Club),
+	    (Rank::Three, Suit::Diamond),
+	    (Rank::Ten, Suit::Club),
+	    (Rank::Ten, Suit::Heart),
+	    (Rank::Ten, Suit::Diamond),
+	    (Rank::Nine, Suit::Spade),
+	    (Rank::King, Suit::Club),
+	    (Rank::Eight, Suit::Heart),
+	    (Rank::Queen, Suit::Spade),
+	] {
+	    deck.push(Card { rank, suit });
+	}
+        let mut table = Table::default();
+        table.deck = Box::new(deck);
+	table.knockout = Some(Knockout::new(100, false));
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let cloned_actions = incoming_actions.clone();
+        let cloned_meta_actions = incoming_meta_actions.clone();
+
+        let id1 = uuid::Uuid::new_v4();
+        table.add_human(PlayerConfig::new(id1, Some("Human1".to_string()), None), None).unwrap();
+        table.players[0].as_mut().unwrap().money = 3; // all-in as soon as they post the big blind
+        let id2 = uuid::Uuid::new_v4();
+        table.add_human(PlayerConfig::new(id2, Some("Human2".to_string()), None), None).unwrap();
+
+        let handler = std::thread::spawn(move || {
+            table.play_one_hand(&cloned_actions, &cloned_meta_actions);
+            table // return the table back
+        });
+
+	// sleep so we dont drain the actions accidentally right at the beginning of play_one_hand
+        thread::sleep(time::Duration::from_secs_f32(0.5)); 
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id2, PlayerAction::Bet(22));
+        let table = handler.join().unwrap();
+
+	// the trip tens bust the big blind
+        assert_eq!(table.players[0].as_ref().unwrap().money, 0);
+        assert_eq!(table.players[1].as_ref().unwrap().money, 1003);
+	let knockout = table.knockout.as_ref().unwrap();
+	assert_eq!(knockout.collected(id2), 100);
+	assert_eq!(knockout.collected(id1), 0);
+    }
+
+    /// a split pot splits the busted player's bounty between both winners, with the odd chip going to the first one,
+    /// and in progressive mode half of each share goes on the eliminator's head
+    #[test]
+    fn knockout_bounty_split_pot() {
+        let mut table = Table::default();
+	table.knockout = Some(Knockout::new(101, true));
+	let ids: Vec<Uuid> = (0..3).map(|_| uuid::Uuid::new_v4()).collect();
+	for (i, id) in ids.iter().enumerate() {
+	    let settings = PlayerConfig::new(*id, Some(format!("Human{}", i)), None);
+	    table.add_human(settings, None).unwrap();
+	}
+	let hole_cards = [
+	    [Card { rank: Rank::Two, suit: Suit::Club }, Card { rank: Rank::Four, suit: Suit::Diamond }],
+	    [Card { rank: Rank::Jack, suit: Suit::Club }, Card { rank: Rank::Ten, suit: Suit::Diamond }],
+	    [Card { rank: Rank::Jack, suit: Suit::Heart }, Card { rank: Rank::Ten, suit: Suit::Spade }],
+	];
+	for (player, cards) in table.players.iter_mut().flatten().zip(hole_cards.iter()) {
+	    player.hole_cards = cards.to_vec();
+	    player.is_active = true;
+	    player.money = 100;
+	}
+	let stacks_before: Vec<u32> = table.players.iter().map(|spot| spot.as_ref().map_or(0, |player| player.money)).collect();
+	let mut gamehand = GameHand::with_variant(Variant::Holdem, 8, &table.players);
+	for (i, spot) in table.players.iter_mut().enumerate() {
+	    if let Some(player) = spot {
+		gamehand.contribute(i, player.id, 100, true, false);
+		player.money = 0;
+	    }
+	}
+	gamehand.flop = Some(vec![
+	    Card { rank: Rank::Ace, suit: Suit::Heart },
+	    Card { rank: Rank::King, suit: Suit::Diamond },
+	    Card { rank: Rank::Queen, suit: Suit::Club },
+	]);
+	gamehand.turn = Some(Card { rank: Rank::Seven, suit: Suit::Spade });
+	gamehand.river = Some(Card { rank: Rank::Three, suit: Suit::Heart });
+	gamehand.street = Street::ShowDown;
+	let settlements = gamehand.divvy_pots(&mut table.players, &table.player_ids_to_configs, 0);
+	let knockouts = table.collect_knockout_bounties(&gamehand, &stacks_before, &settlements);
+
+	// the two straights split the pot and bust Human0, and the first eliminator in seat order gets the odd chip
+	let seat = |id: Uuid| table.players.iter().position(|spot| spot.as_ref().is_some_and(|player| player.id == id)).unwrap();
+	let (first, second) = if seat(ids[1]) < seat(ids[2]) { (ids[1], ids[2]) } else { (ids[2], ids[1]) };
+	assert_eq!(knockouts.len(), 2);
+	assert_eq!(knockouts[0]["index"], seat(first));
+	assert_eq!(knockouts[0]["busted_index"], seat(ids[0]));
+	assert_eq!(knockouts[0]["bounty"], 26);
+	assert_eq!(knockouts[0]["bounty_increase"], 25);
+	assert_eq!(knockouts[1]["index"], seat(second));
+	assert_eq!(knockouts[1]["busted_index"], seat(ids[0]));
+	assert_eq!(knockouts[1]["bounty"], 25);
+	assert_eq!(knockouts[1]["bounty_increase"], 25);
+	let knockout = table.knockout.as_ref().unwrap();
+	assert_eq!(knockout.collected(first), 26);
+	assert_eq!(knockout.bounty(first), 126);
+	assert_eq!(knockout.collected(second), 25);
+	assert_eq!(knockout.bounty(second), 126);
+	assert_eq!(knockout.bounty(ids[0]), 101);
+    }
+
+    /// a deal shows both splits, is called off by any rejection, and goes through once everyone accepts
+    #[test]
+    fn deal_needs_everyone_to_accept() {
+        let mut table = Table::default();
+	let ids: Vec<Uuid> = (0..3).map(|_| uuid::Uuid::new_v4()).collect();
+	for (i, id) in ids.iter().enumerate() {
+	    let settings = PlayerConfig::new(*id, Some(format!("Human{}", i)), None);
+	    table.add_human(settings, None).unwrap();
+	}
+	for (player, money) in table.players.iter_mut().flatten().zip([5000, 3000, 2000]) {
+	    player.money = money;
+	}
+	let in_seat_order: Vec<Uuid> = table.players.iter().flatten().map(|player| player.id).collect();
+	assert!(table.propose_deal(in_seat_order[0], DealMethod::Icm, vec![50, 30, 20, 10]).is_err());
+	assert!(table.propose_deal(in_seat_order[0], DealMethod::Icm, vec![20, 30, 50]).is_err());
+	assert!(table.propose_deal(in_seat_order[0], DealMethod::ChipChop, vec![u32::MAX, u32::MAX]).is_err());
+	assert!(table.deal.is_none());
+
+	let message = table.propose_deal(in_seat_order[0], DealMethod::Icm, vec![50, 30, 20]).unwrap();
+	assert_eq!(message["players"][0]["icm"], 38);
+	assert_eq!(message["players"][0]["chip_chop"], 40);
+	assert!(table.propose_deal(in_seat_order[1], DealMethod::ChipChop, vec![100]).is_err()); // one at a time
+	let message = table.respond_to_deal(in_seat_order[1], false).unwrap();
+	assert_eq!(message["msg_type"], "deal_rejected");
+	assert!(table.deal.is_none());
+
+	table.propose_deal(in_seat_order[0], DealMethod::Icm, vec![50, 30, 20]).unwrap();
+	assert!(table.respond_to_deal(Uuid::new_v4(), true).is_none());
+	let message = table.respond_to_deal(in_seat_order[1], true).unwrap();
+	assert_eq!(message["unanimous"], false);
+	let message = table.respond_to_deal(in_seat_order[2], true).unwrap();
+	assert_eq!(message["unanimous"], true);
+	assert_eq!(table.deal.as_ref().unwrap().prizes(), &[38, 33, 29]);
+
+	// a player who doesn't answer in time rejects the deal
+	table.deal = None;
+	table.propose_deal(in_seat_order[0], DealMethod::Icm, vec![50, 30, 20]).unwrap();
+	table.call_off_stale_deal();
+	assert!(table.deal.is_some());
+	table.player_action_timeout = 0;
+	table.call_off_stale_deal();
+	assert!(table.deal.is_none());
+
+	// the deal is off if a player leaves before the game ends
+	table.player_action_timeout = 45;
+	table.propose_deal(in_seat_order[0], DealMethod::Icm, vec![50, 30, 20]).unwrap();
+	table.players[table.players.iter().position(|spot| spot.is_some()).unwrap()] = None;
+	table.call_off_stale_deal();
+	assert!(table.deal.is_none());
+    }
+
+    /// once a deal goes through at a freezeout, the prizes are in the ledger and go to the hub with the game over
+    #[test]
+    fn deal_prizes_are_recorded() {
+        let mut table = Table::default();
+	table.freezeout = Some(Freezeout::new(0, 0, 0, 1));
+	let ids: Vec<Uuid> = (0..2).map(|_| uuid::Uuid::new_v4()).collect();
+	for (i, id) in ids.iter().enumerate() {
+	    let settings = PlayerConfig::new(*id, Some(format!("Human{}", i)), None);
+	    table.add_human(settings, None).unwrap();
+	}
+	for player in table.players.iter_mut().flatten() {
+	    player.money = if player.id == ids[0] { 1500 } else { 500 };
+	}
+	// the payouts come from the prize pool, not the players
+	assert!(table.propose_deal(ids[0], DealMethod::ChipChop, vec![1400, 600]).is_err());
+	let message = table.propose_deal(ids[0], DealMethod::ChipChop, vec![]).unwrap();
+	assert_eq!(message["payouts"], json::array![2000]);
+	assert!(table.deal_prizes().is_empty()); // nothing is won until everyone accepts
+	table.respond_to_deal(ids[1], true).unwrap();
+	table.record_deal();
+
+	let prizes = table.deal_prizes();
+	assert_eq!(prizes[&ids[0]], 1500);
+	assert_eq!(prizes[&ids[1]], 500);
+	let ledger = table.freezeout.as_ref().unwrap().ledger();
+	let prize_for = |name: &str| ledger.iter().find(|purchases| purchases.player_name.as_deref() == Some(name)).unwrap().prize;
+	assert_eq!(prize_for("Human0"), 1500);
+	assert_eq!(prize_for("Human1"), 500);
+    }
+
+    /// after everyone folds preflop, the rest of the board can be rabbit hunted
+    #[test]
+    fn rabbit_hunt_after_fold() {
+        let mut table = Table::default();
+	table.rabbit_hunting = true;
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        let cloned_actions = incoming_actions.clone();
+        let cloned_meta_actions = incoming_meta_actions.clone();
+
+        let id1 = uuid::Uuid::new_v4();
+        let name1 = "Human1".to_string();
+        let settings1 = PlayerConfig::new(id1, Some(name1), None);
+        table.add_human(settings1, None).unwrap();
+
+        let id2 = uuid::Uuid::new_v4();
+        let name2 = "Human2".to_string();
+        let settings2 = PlayerConfig::new(id2, Some(name2), None);
+        table.add_human(settings2, None).unwrap();
+
+        let handler = std::thread::spawn(move || {
+            table.play_one_hand(&cloned_actions, &cloned_meta_actions);
+            table // return the table back
+        });
+
+	// sleep so we dont drain the actions accidentally right at the beginning of play_one_hand
+        thread::sleep(time::Duration::from_secs_f32(0.2));
+
+        // whoever acts first folds, and the hand is over before the flop
+        for id in [id1, id2] {
+            incoming_actions
+		.lock()
+		.unwrap()
+		.insert(id, PlayerAction::Fold);
+	}
+
+        // get the game back from the thread
+        let mut table = handler.join().unwrap();
+
+	let board = table.rabbit_hunt().unwrap();
+	assert_eq!(board.len(), 5);
+	// asking again shows the same cards
+	let again = table.rabbit_hunt().unwrap();
+	assert!(board.iter().zip(again.iter()).all(|(a, b)| a.is_identical(b)));
+	// the rabbit comes from the deck, so it can't be anyone's hole cards
+	for player in table.players.iter().flatten() {
+	    for hole_card in player.hole_cards.iter() {
+		assert!(!board.iter().any(|card| card.is_identical(hole_card)));
+	    }
+	}
+    }
+
+    /// after third street in stud, the best hand showing acts first
+    #[test]
+    fn stud_best_hand_showing_acts_first() {
+        let mut table = Table::default();
+	table.variant = Variant::SevenCardStud;
+	for i in 0..3 {
+	    table.add_bot(format!("Bot {}", i)).unwrap();
+	}
+	let up_cards = [
+	    vec![Card { rank: Rank::Ace, suit: Suit::Club }, Card { rank: Rank::King, suit: Suit::Club }],
+	    vec![Card { rank: Rank::Four, suit: Suit::Club }, Card { rank: Rank::Four, suit: Suit::Heart }],
+	    vec![Card { rank: Rank::Two, suit: Suit::Spade }, Card { rank: Rank::Queen, suit: Suit::Club }],
+	];
+	for (player, cards) in table.players.iter_mut().flatten().zip(up_cards) {
+	    player.is_active = true;
+	    player.up_cards = cards;
+	}
+	let mut gamehand = GameHand::with_variant(Variant::SevenCardStud, 8, &table.players);
+	// the lowest up card brought it in on third street
+	assert_eq!(table.get_starting_idx(&gamehand), 2);
+	gamehand.street = Street::Fourth;
+	// the pair of fours showing acts first on fourth street
+	assert_eq!(table.get_starting_idx(&gamehand), 1);
+    }
+
+    /// in Razz the highest up card brings it in, then the best low showing acts first
+    #[test]
+    fn razz_bring_in_and_best_low_showing_acts_first() {
+        let mut table = Table::default();
+	table.variant = Variant::Razz;
+	for i in 0..3 {
+	    table.add_bot(format!("Bot {}", i)).unwrap();
+	}
+	let up_cards = [
+	    vec![Card { rank: Rank::Ace, suit: Suit::Club }, Card { rank: Rank::Six, suit: Suit::Club }],
+	    vec![Card { rank: Rank::King, suit: Suit::Club }, Card { rank: Rank::Four, suit: Suit::Heart }],
+	    vec![Card { rank: Rank::Two, suit: Suit::Spade }, Card { rank: Rank::Three, suit: Suit::Club }],
+	];
+	for (player, cards) in table.players.iter_mut().flatten().zip(up_cards) {
+	    player.is_active = true;
+	    player.up_cards = cards;
+	}
+	let mut gamehand = GameHand::with_variant(Variant::Razz, 8, &table.players);
+	// the King brings it in on third street
+	assert_eq!(table.get_starting_idx(&gamehand), 1);
+	gamehand.street = Street::Fourth;
+	// the 3-2 showing acts first on fourth street
+	assert_eq!(table.get_starting_idx(&gamehand), 2);
+    }
+
+    /// in double-board Hold'em, each street is dealt to both boards
+    #[test]
+    fn double_board_deals_both_boards() {
+        let mut table = Table::default();
+	table.variant = Variant::DoubleBoardHoldem;
+	table.add_bot("Bot 0".to_string()).unwrap();
+	table.add_bot("Bot 1".to_string()).unwrap();
+	for player in table.players.iter_mut().flatten() {
+	    player.is_active = true;
+	}
+	table.deck.shuffle();
+	table.deal_hands();
+	let mut gamehand = GameHand::with_variant(Variant::DoubleBoardHoldem, 8, &table.players);
+	table.transition(&mut gamehand);
+	assert!(gamehand.boards().iter().all(|board| board.len() == 3));
+	table.transition(&mut gamehand);
+	table.transition(&mut gamehand);
+	let boards = gamehand.boards();
+	assert_eq!(boards.len(), 2);
+	assert!(boards.iter().all(|board| board.len() == 5));
+	// the boards are dealt from the same deck, so they share no cards
+	assert!(boards[0].iter().all(|card| !boards[1].iter().any(|other| other.is_identical(card))));
+    }
+
+    /// in Pineapple, everyone throws away one of their three hole cards at the same time
+    #[test]
+    fn pineapple_discard_round() {
+        let mut deck = RiggedDeck::new();
+        for (rank, suit) in [(Rank::Ace, Suit::Spade), (Rank::King, Suit::Spade), (Rank::Two, Suit::Club),
+                             (Rank::Queen, Suit::Heart), (Rank::Queen, Suit::Diamond), (Rank::Three, Suit::Club)] {
+            deck.push(Card { rank, suit });
+        }
+        // and then the flop
+        for (rank, suit) in [(Rank::Nine, Suit::Heart), (Rank::Eight, Suit::Diamond), (Rank::Four, Suit::Spade)] {
+            deck.push(Card { rank, suit });
+        }
+        let mut table = Table::default();
+        table.deck = Box::new(deck);
+	table.variant = Variant::Pineapple;
+	table.add_bot("Bot 0".to_string()).unwrap();
+        let id1 = uuid::Uuid::new_v4();
+        let settings1 = PlayerConfig::new(id1, Some("Human1".to_string()), None);
+        table.add_human(settings1, None).unwrap();
+	for player in table.players.iter_mut().flatten() {
+	    player.is_active = true;
+	}
+	table.deal_hands();
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+	// the human has already chosen, so nobody waits on anyone
+        incoming_actions
+            .lock()
+            .unwrap()
+            .insert(id1, PlayerAction::Discard(Card { rank: Rank::Three, suit: Suit::Club }));
+	let mut gamehand = GameHand::with_variant(Variant::Pineapple, 8, &table.players);
+	table.next_street(&incoming_actions, &incoming_meta_actions, &mut gamehand);
+
+	// the bot keeps its suited ace-king
+	let player0 = table.players[0].as_ref().unwrap();
+	assert_eq!(player0.hole_cards_string(), "AsKs");
+	assert_eq!(player0.last_action.as_ref().unwrap().to_string(), "discard");
+	let player1 = table.players[1].as_ref().unwrap();
+	assert_eq!(player1.hole_cards_string(), "QhQd");
+	// the discards happen before the flop is dealt
+	assert_eq!(gamehand.street, Street::Flop);
+	assert_eq!(gamehand.board_cards().iter().map(|card| card.to_string()).collect::<String>(), "9h8d4s");
+    }
+
+    /// in a draw game, the players throw away cards and get new ones.
+    /// When the deck runs out, the discards are reshuffled to make a new deck
+    #[test]
+    fn triple_draw_reshuffles_discards() {
+        let mut deck = RiggedDeck::new();
+        // player0 has a pair of kings to throw away
+        for (rank, suit) in [(Rank::King, Suit::Club), (Rank::King, Suit::Diamond), (Rank::Eight, Suit::Spade),
+                             (Rank::Six, Suit::Heart), (Rank::Two, Suit::Club)] {
+            deck.push(Card { rank, suit });
+        }
+        // player1 throws away the queen
+        for (rank, suit) in [(Rank::Seven, Suit::Heart), (Rank::Five, Suit::Spade), (Rank::Four, Suit::Club),
+                             (Rank::Three, Suit::Diamond), (Rank::Queen, Suit::Heart)] {
+            deck.push(Card { rank, suit });
+        }
+        // only two cards are left to draw from
+        deck.push(Card { rank: Rank::Two, suit: Suit::Diamond });
+        deck.push(Card { rank: Rank::Three, suit: Suit::Spade });
+
+        let mut table = Table::default();
+        table.deck = Box::new(deck);
+	table.variant = Variant::DeuceToSevenTripleDraw;
+	table.add_bot("Bot 0".to_string()).unwrap();
+	table.add_bot("Bot 1".to_string()).unwrap();
+	for player in table.players.iter_mut().flatten() {
+	    player.is_active = true;
+	}
+	table.deal_hands();
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+	let mut gamehand = GameHand::with_variant(Variant::DeuceToSevenTripleDraw, 8, &table.players);
+	gamehand.street = Street::FirstDraw;
+	table.play_draw_round(&incoming_actions, &incoming_meta_actions, &mut gamehand);
+
+	// player1 is left of the button, so draws first and gets the two of diamonds for the queen
+	let player1 = table.players[1].as_ref().unwrap();
+	assert_eq!(player1.hole_cards_string(), "7h5s4c3d2d");
+	assert_eq!(player1.last_action.as_ref().unwrap().to_string(), "draw:1");
+	// player0 gets the last card in the deck, then the queen from the reshuffled discards
+	let player0 = table.players[0].as_ref().unwrap();
+	assert_eq!(player0.hole_cards_string(), "8s6h2c3sQh");
+	assert_eq!(player0.last_action.as_ref().unwrap().to_string(), "draw:2");
+    }
+
+    /// five card draw has a single draw, where the players throw away cards and get new ones.
+    /// The deck runs out partway through, so the last player draws from the discard pile,
+    /// and the hand goes to the showdown after the draw
+    #[test]
+    fn five_card_draw_single_draw() {
+        let mut deck = RiggedDeck::new();
+        // player0 keeps a pair of sevens and the ace
+        for (rank, suit) in [(Rank::Seven, Suit::Club), (Rank::Seven, Suit::Diamond), (Rank::Ace, Suit::Spade),
+                             (Rank::Four, Suit::Heart), (Rank::Two, Suit::Spade)] {
+            deck.push(Card { rank, suit });
+        }
+        // player1 keeps a pair of queens
+        for (rank, suit) in [(Rank::Queen, Suit::Heart), (Rank::Queen, Suit::Spade), (Rank::Nine, Suit::Club),
+                             (Rank::Five, Suit::Diamond), (Rank::Three, Suit::Heart)] {
+            deck.push(Card { rank, suit });
+        }
+        // only three cards are left to draw from
+        for (rank, suit) in [(Rank::Queen, Suit::Diamond), (Rank::Jack, Suit::Club), (Rank::Ten, Suit::Diamond)] {
+            deck.push(Card { rank, suit });
+        }
+
+        let mut table = Table::default();
+        table.deck = Box::new(deck);
+	table.variant = Variant::FiveCardDraw;
+	let mut ids = vec![];
+	for i in 0..2 {
+            let id = uuid::Uuid::new_v4();
+            let settings = PlayerConfig::new(id, Some(format!("Human{}", i)), None);
+            table.add_human(settings, None).unwrap();
+	    ids.push(id);
+	}
+	for player in table.players.iter_mut().flatten() {
+	    player.is_active = true;
+	}
+	table.deal_hands();
+        let incoming_actions = Arc::new(Mutex::new(HashMap::<Uuid, PlayerAction>::new()));
+        let incoming_meta_actions = Arc::new(Mutex::new(VecDeque::<MetaAction>::new()));
+        incoming_actions.lock().unwrap().insert(ids[0], PlayerAction::Draw(vec![
+            Card { rank: Rank::Four, suit: Suit::Heart },
+            Card { rank: Rank::Two, suit: Suit::Spade },
+        ]));
+        incoming_actions.lock().unwrap().insert(ids[1], PlayerAction::Draw(vec![
+            Card { rank: Rank::Nine, suit: Suit::Club },
+            Card { rank: Rank::Five, suit: Suit::Diamond },
+            Card { rank: Rank::Three, suit: Suit::Heart },
+        ]));
+	let mut gamehand = GameHand::with_variant(Variant::FiveCardDraw, 8, &table.players);
+	table.transition(&mut gamehand);
+	assert_eq!(gamehand.street, Street::FirstDraw);
+	table.play_draw_round(&incoming_actions, &incoming_meta_actions, &mut gamehand);
+
+	// player1 is left of the button, so draws first and makes trip queens with the rest of the deck
+	let player1 = table.players[1].as_ref().unwrap();
+	assert_eq!(player1.hole_cards_string(), "QhQsQdJcTd");
+	assert_eq!(player1.last_action.as_ref().unwrap().to_string(), "draw:3");
+	// player0 draws from the discard pile, and gets player1's first two discards
+	let player0 = table.players[0].as_ref().unwrap();
+	assert_eq!(player0.hole_cards_string(), "7c7dAs9c5d");
+	assert_eq!(player0.last_action.as_ref().unwrap().to_string(), "draw:2");
+
+	// there is only the one draw, so the next street is the showdown
+	table.transition(&mut gamehand);
+	assert_eq!(gamehand.street, Street::ShowDown);
+    }
 }
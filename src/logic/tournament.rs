@@ -0,0 +1,281 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// When the blinds go up in a tournament
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlindSchedule {
+    EveryNHands(u32),
+    EveryNMinutes(u32),
+}
+
+/// A sit-and-go usually goes up a level every ten hands
+impl Default for BlindSchedule {
+    fn default() -> Self {
+	BlindSchedule::EveryNHands(10)
+    }
+}
+
+impl fmt::Display for BlindSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+	let output = match self {
+	    BlindSchedule::EveryNHands(num_hands) => format!("every_{}_hands", num_hands),
+	    BlindSchedule::EveryNMinutes(num_minutes) => format!("every_{}_minutes", num_minutes),
+	};
+	write!(f, "{}", output)
+    }
+}
+
+/// the blinds (and ante) for one level of a tournament
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Deserialize, Serialize)]
+pub struct BlindLevel {
+    pub small_blind: u32,
+    pub big_blind: u32,
+    #[serde(default)]
+    pub ante: u32,
+}
+
+/// A sit-and-go tournament starts once every seat is filled, and everyone starts with the same stack.
+/// The blinds go up on a schedule, and a player who busts gets a finishing position
+/// instead of staying at the table. The last player with chips wins
+#[derive(Debug)]
+pub struct Tournament {
+    levels: Vec<BlindLevel>, // past the last level, the blinds keep doubling
+    schedule: BlindSchedule,
+    level_idx: usize,
+    hands_at_level: u32, // how many hands have been played at the current level
+    level_started: Option<Instant>, // None until the tournament starts
+    entrants: HashMap<Uuid, String>, // the name of everyone who started the tournament
+    finishers: Vec<Uuid>, // everyone who is out of the tournament, from the first to bust to the winner
+}
+
+impl Tournament {
+    pub fn new(levels: Vec<BlindLevel>, schedule: BlindSchedule) -> Self {
+	assert!(!levels.is_empty(), "a tournament needs at least one blind level");
+	Self {
+	    levels,
+	    schedule,
+	    level_idx: 0,
+	    hands_at_level: 0,
+	    level_started: None,
+	    entrants: HashMap::new(),
+	    finishers: vec![],
+	}
+    }
+
+    pub fn schedule(&self) -> BlindSchedule {
+	self.schedule
+    }
+
+    pub fn has_started(&self) -> bool {
+	self.level_started.is_some()
+    }
+
+    /// the tournament is over once there is a winner
+    pub fn is_over(&self) -> bool {
+	self.has_started() && self.finishers.len() == self.entrants.len()
+    }
+
+    /// how many players started the tournament
+    pub fn num_entrants(&self) -> usize {
+	self.entrants.len()
+    }
+
+    /// the level that is being played, starting at 1
+    pub fn level(&self) -> usize {
+	self.level_idx + 1
+    }
+
+    /// the blinds for the current level. Past the last level, the blinds double every level
+    pub fn current_level(&self) -> BlindLevel {
+	let last = self.levels.len() - 1;
+	let level = self.levels[self.level_idx.min(last)];
+	let multiplier = 1 << self.level_idx.saturating_sub(last).min(20);
+	BlindLevel {
+	    small_blind: level.small_blind.saturating_mul(multiplier),
+	    big_blind: level.big_blind.saturating_mul(multiplier),
+	    ante: level.ante.saturating_mul(multiplier),
+	}
+    }
+
+    /// start the tournament with the given players (by id and name), and start the clock on the first level
+    pub fn start(&mut self, entrants: HashMap<Uuid, String>) {
+	self.entrants = entrants;
+	self.level_started = Some(Instant::now());
+    }
+
+    /// called after each hand is played. Returns true if the blinds just went up
+    pub fn finish_hand(&mut self) -> bool {
+	self.hands_at_level += 1;
+	let level_started = match self.level_started {
+	    Some(level_started) => level_started,
+	    None => return false,
+	};
+	let level_over = match self.schedule {
+	    BlindSchedule::EveryNHands(num_hands) => self.hands_at_level >= num_hands.max(1),
+	    BlindSchedule::EveryNMinutes(num_minutes) => {
+		level_started.elapsed() >= Duration::from_secs(60 * num_minutes.max(1) as u64)
+	    }
+	};
+	if level_over {
+	    self.level_idx += 1;
+	    self.hands_at_level = 0;
+	    self.level_started = Some(Instant::now());
+	}
+	level_over
+    }
+
+    /// a player is out of the tournament. Returns the position they finished in,
+    /// e.g. the first of six players to bust finishes sixth
+    pub fn eliminate(&mut self, id: Uuid) -> Option<usize> {
+	if !self.entrants.contains_key(&id) || self.finishers.contains(&id) {
+	    return None;
+	}
+	self.finishers.push(id);
+	Some(self.entrants.len() - self.finishers.len() + 1)
+    }
+
+    /// the finishing position and name of everyone who is out of the tournament, starting with the winner
+    pub fn results(&self) -> Vec<(usize, String)> {
+	self.finishers
+	    .iter()
+	    .rev()
+	    .enumerate()
+	    .map(|(i, id)| (self.entrants.len() - self.finishers.len() + i + 1, self.entrants[id].clone()))
+	    .collect()
+    }
+}
+
+/// a player being moved from one table to another in a multi-table tournament
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Reseat {
+    pub id: Uuid,
+    pub from: String, // the table name
+    pub to: String,
+}
+
+/// seat everyone as evenly as possible at the fewest tables that fit them.
+/// The tables are named after the tournament, e.g. ABCD-1, ABCD-2
+pub fn seat_players(tournament_name: &str, ids: &[Uuid], max_players: usize) -> BTreeMap<String, Vec<Uuid>> {
+    let num_tables = ids.len().div_ceil(max_players.max(1)).max(1);
+    let mut seating: BTreeMap<String, Vec<Uuid>> = (1..=num_tables)
+	.map(|i| (format!("{}-{}", tournament_name, i), vec![]))
+	.collect();
+    let table_names: Vec<String> = seating.keys().cloned().collect();
+    for (i, id) in ids.iter().enumerate() {
+	seating.get_mut(&table_names[i % num_tables]).unwrap().push(*id);
+    }
+    seating
+}
+
+/// as players bust, break the emptiest tables until the field fits at as few tables as possible,
+/// then move players from the biggest tables to the smallest until no table has two more players than another.
+/// Eventually everyone left is at the final table.
+/// The seating is updated in place, and the moves that the tables need to make are returned
+pub fn balance_tables(seating: &mut BTreeMap<String, Vec<Uuid>>, max_players: usize) -> Vec<Reseat> {
+    let mut reseats = vec![];
+    let num_players: usize = seating.values().map(|ids| ids.len()).sum();
+    let num_tables = num_players.div_ceil(max_players.max(1)).max(1);
+    let smallest_table = |seating: &BTreeMap<String, Vec<Uuid>>| {
+	seating.iter().min_by_key(|(_, ids)| ids.len()).map(|(name, _)| name.clone()).unwrap()
+    };
+    while seating.len() > num_tables {
+	// the most recently opened of the emptiest tables breaks first
+	let (broken_name, _) = seating.iter().rev().min_by_key(|(_, ids)| ids.len()).unwrap();
+	let broken_name = broken_name.clone();
+	let ids = seating.remove(&broken_name).unwrap();
+	for id in ids {
+	    let to = smallest_table(seating);
+	    seating.get_mut(&to).unwrap().push(id);
+	    reseats.push(Reseat { id, from: broken_name.clone(), to });
+	}
+    }
+    loop {
+	let (biggest_name, biggest) = seating.iter().max_by_key(|(_, ids)| ids.len()).unwrap();
+	let smallest = smallest_table(seating);
+	if biggest.len() <= seating[&smallest].len() + 1 {
+	    break;
+	}
+	let biggest_name = biggest_name.clone();
+	let id = seating.get_mut(&biggest_name).unwrap().pop().unwrap();
+	seating.get_mut(&smallest).unwrap().push(id);
+	reseats.push(Reseat { id, from: biggest_name, to: smallest });
+    }
+    reseats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blinds_go_up_every_n_hands() {
+	let levels = vec![
+	    BlindLevel { small_blind: 10, big_blind: 20, ante: 0 },
+	    BlindLevel { small_blind: 20, big_blind: 40, ante: 5 },
+	];
+	let mut tournament = Tournament::new(levels, BlindSchedule::EveryNHands(2));
+	tournament.start(HashMap::new());
+	assert_eq!(tournament.current_level().big_blind, 20);
+	assert!(!tournament.finish_hand());
+	assert!(tournament.finish_hand());
+	assert_eq!(tournament.level(), 2);
+	assert_eq!(tournament.current_level(), BlindLevel { small_blind: 20, big_blind: 40, ante: 5 });
+	// past the last level the blinds keep doubling
+	tournament.finish_hand();
+	tournament.finish_hand();
+	assert_eq!(tournament.current_level(), BlindLevel { small_blind: 40, big_blind: 80, ante: 10 });
+    }
+
+    #[test]
+    fn finishing_positions() {
+	let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+	let entrants = ids.iter().enumerate().map(|(i, id)| (*id, format!("Player {}", i))).collect();
+	let mut tournament = Tournament::new(vec![BlindLevel { small_blind: 1, big_blind: 2, ante: 0 }], BlindSchedule::default());
+	tournament.start(entrants);
+	assert_eq!(tournament.eliminate(ids[1]), Some(3));
+	// nobody can bust twice
+	assert_eq!(tournament.eliminate(ids[1]), None);
+	assert!(!tournament.is_over());
+	assert_eq!(tournament.eliminate(ids[2]), Some(2));
+	assert_eq!(tournament.eliminate(ids[0]), Some(1));
+	assert!(tournament.is_over());
+	assert_eq!(tournament.results(), vec![
+	    (1, "Player 0".to_string()),
+	    (2, "Player 2".to_string()),
+	    (3, "Player 1".to_string()),
+	]);
+    }
+
+    #[test]
+    fn seat_and_balance_tables() {
+	let ids: Vec<Uuid> = (0..20).map(|_| Uuid::new_v4()).collect();
+	let mut seating = seat_players("ABCD", &ids, 8);
+	let sizes = |seating: &BTreeMap<String, Vec<Uuid>>| seating.values().map(|ids| ids.len()).collect::<Vec<_>>();
+	assert_eq!(sizes(&seating), vec![7, 7, 6]);
+	assert!(balance_tables(&mut seating, 8).is_empty());
+
+	// a few players bust at the first table, so someone moves over from another
+	seating.get_mut("ABCD-1").unwrap().truncate(4);
+	let reseats = balance_tables(&mut seating, 8);
+	assert_eq!(reseats.len(), 1);
+	assert_eq!(reseats[0].to, "ABCD-1");
+	assert_eq!(sizes(&seating), vec![5, 6, 6]);
+
+	// once everyone fits at two tables, the emptiest one breaks
+	seating.get_mut("ABCD-2").unwrap().truncate(3);
+	let reseats = balance_tables(&mut seating, 8);
+	assert!(reseats.iter().all(|reseat| reseat.from == "ABCD-2"));
+	assert_eq!(sizes(&seating), vec![7, 7]);
+
+	// and then down to the final table
+	seating.get_mut("ABCD-1").unwrap().truncate(1);
+	balance_tables(&mut seating, 8);
+	assert_eq!(seating.len(), 1);
+	assert_eq!(sizes(&seating), vec![8]);
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::betting_structure::BettingStructure;
+use super::deck::{Deck, StandardDeck};
+use super::game_hand::Street;
+use super::hand_analysis::HandRankingRules;
+
+/// The variant decides which game of poker is dealt at a table,
+/// e.g. how many hole cards each player gets and how the best hand is made.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Variant {
+    Holdem,
+    Omaha, // pot-limit omaha
+    OmahaHiLo, // pot-limit omaha 8-or-better, where the pot is split between the best high and low hands
+    ShortDeck, // 6+ hold'em, played with a 36 card deck
+    DoubleBoardHoldem, // hold'em with two boards, where each pot is split between the best hand on each board
+    Pineapple, // hold'em with three hole cards, where everyone throws one away before the flop
+    CrazyPineapple, // like Pineapple, but the extra hole card is thrown away after the flop
+    SevenCardStud, // no board, each player gets their own up cards and down cards
+    SevenCardStudHiLo, // seven card stud 8-or-better, where the pot is split like in Omaha Hi-Lo
+    Razz, // seven card stud where the best ace-to-five low hand wins
+    DeuceToSevenTripleDraw, // five cards with three draws, where the best deuce-to-seven low hand wins
+    FiveCardDraw, // five cards with one draw, where the best high hand wins
+    BadugiTripleDraw, // four cards with three draws, where the best badugi (lowest four cards of different suits) wins
+}
+
+/// A table that doesn't specify a variant just plays Hold'em
+impl Default for Variant {
+    fn default() -> Self {
+	Variant::Holdem
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+	let output = match self {
+	    Variant::Holdem => "holdem",
+	    Variant::Omaha => "omaha",
+	    Variant::OmahaHiLo => "omaha_hi_lo",
+	    Variant::ShortDeck => "short_deck",
+	    Variant::DoubleBoardHoldem => "double_board_holdem",
+	    Variant::Pineapple => "pineapple",
+	    Variant::CrazyPineapple => "crazy_pineapple",
+	    Variant::SevenCardStud => "seven_card_stud",
+	    Variant::SevenCardStudHiLo => "seven_card_stud_hi_lo",
+	    Variant::Razz => "razz",
+	    Variant::DeuceToSevenTripleDraw => "deuce_to_seven_triple_draw",
+	    Variant::FiveCardDraw => "five_card_draw",
+	    Variant::BadugiTripleDraw => "badugi_triple_draw",
+	};
+	write!(f, "{}", output)
+    }
+}
+
+impl Variant {
+    /// how many cards each player is dealt face down at the start of a hand
+    pub fn num_hole_cards(&self) -> usize {
+	match self {
+	    Variant::Holdem | Variant::ShortDeck | Variant::DoubleBoardHoldem | Variant::SevenCardStud
+		| Variant::SevenCardStudHiLo | Variant::Razz => 2,
+	    Variant::Pineapple | Variant::CrazyPineapple => 3,
+	    Variant::Omaha | Variant::OmahaHiLo | Variant::BadugiTripleDraw => 4,
+	    Variant::DeuceToSevenTripleDraw | Variant::FiveCardDraw => 5,
+	}
+    }
+
+    /// if a best hand must use an exact number of hole cards, return that number.
+    /// e.g. in Omaha you must use exactly two hole cards along with three board cards.
+    /// Otherwise None, i.e. any five of the available cards can be used
+    pub fn required_hole_cards(&self) -> Option<usize> {
+	match self {
+	    Variant::Holdem | Variant::ShortDeck | Variant::DoubleBoardHoldem | Variant::SevenCardStud
+		| Variant::SevenCardStudHiLo | Variant::Razz | Variant::DeuceToSevenTripleDraw
+		| Variant::Pineapple | Variant::CrazyPineapple | Variant::FiveCardDraw
+		| Variant::BadugiTripleDraw => None,
+	    Variant::Omaha | Variant::OmahaHiLo => Some(2),
+	}
+    }
+
+    /// the betting structure that a table plays if it doesn't choose one,
+    /// e.g. Omaha is usually played pot-limit
+    pub fn default_betting_structure(&self) -> BettingStructure {
+	match self {
+	    Variant::Omaha | Variant::OmahaHiLo => BettingStructure::PotLimit,
+	    _ => BettingStructure::NoLimit,
+	}
+    }
+
+    /// in a hi-lo game each pot is split between the best high hand and the best
+    /// qualifying low hand (eight or better)
+    pub fn is_hi_lo(&self) -> bool {
+	matches!(self, Variant::OmahaHiLo | Variant::SevenCardStudHiLo)
+    }
+
+    /// in a stud game there are no community cards. Each player gets some cards face up,
+    /// the betting is opened by a bring-in instead of blinds, and the best hand showing acts first
+    pub fn is_stud(&self) -> bool {
+	matches!(self, Variant::SevenCardStud | Variant::SevenCardStudHiLo | Variant::Razz)
+    }
+
+    /// in a draw game there are no community cards. Each player is dealt a full hand face down,
+    /// and between the betting rounds they can throw away cards to draw new ones
+    pub fn is_draw(&self) -> bool {
+	matches!(self, Variant::DeuceToSevenTripleDraw | Variant::FiveCardDraw | Variant::BadugiTripleDraw)
+    }
+
+    /// stud and draw games have no community cards, so nothing is ever dealt to the board
+    pub fn has_board(&self) -> bool {
+	!self.is_stud() && !self.is_draw()
+    }
+
+    /// how many boards are dealt each hand. In double-board Hold'em,
+    /// each pot is split between the best hand on each board
+    pub fn num_boards(&self) -> usize {
+	match self {
+	    Variant::DoubleBoardHoldem => 2,
+	    _ => 1,
+	}
+    }
+
+    /// in Pineapple, everyone still in the hand throws away one of their three hole cards
+    /// once the betting on this street is done (before the next street is dealt)
+    pub fn discard_street(&self) -> Option<Street> {
+	match self {
+	    Variant::Pineapple => Some(Street::Preflop),
+	    Variant::CrazyPineapple => Some(Street::Flop),
+	    _ => None,
+	}
+    }
+
+    /// in a lowball game the best low hand wins the whole pot
+    pub fn is_lowball(&self) -> bool {
+	matches!(self, Variant::Razz | Variant::DeuceToSevenTripleDraw | Variant::BadugiTripleDraw)
+    }
+
+    /// in deuce-to-seven lowball Aces are high, and straights and flushes count against you.
+    /// Otherwise lowball is played ace-to-five
+    pub fn is_deuce_to_seven(&self) -> bool {
+	matches!(self, Variant::DeuceToSevenTripleDraw)
+    }
+
+    /// in Badugi each player has four cards, and only the cards of different suits
+    /// and different ranks count towards the lowest hand
+    pub fn is_badugi(&self) -> bool {
+	matches!(self, Variant::BadugiTripleDraw)
+    }
+
+    /// the street that a hand starts on
+    pub fn first_street(&self) -> Street {
+	if self.is_stud() {
+	    Street::Third
+	} else if self.is_draw() {
+	    Street::PreDraw
+	} else {
+	    Street::Preflop
+	}
+    }
+
+    /// the last street with betting before the showdown.
+    /// Five card draw has only one draw, while triple draw has three
+    pub fn last_street(&self) -> Street {
+	match self {
+	    Variant::FiveCardDraw => Street::FirstDraw,
+	    _ if self.is_draw() => Street::ThirdDraw,
+	    _ if self.is_stud() => Street::Seventh,
+	    _ => Street::River,
+	}
+    }
+
+    /// the most players that can sit at the table.
+    /// Seven card stud needs up to seven cards for each player, so only seven can fit in one deck.
+    /// Draw games need plenty of cards left over to draw from, so they are played six-handed
+    pub fn max_players(&self) -> u8 {
+	if self.is_stud() {
+	    7
+	} else if self.is_draw() {
+	    6
+	} else {
+	    9
+	}
+    }
+
+    /// a fresh (unshuffled) deck of the cards this variant is played with,
+    /// along with any jokers that the table plays with
+    pub fn new_deck(&self, num_jokers: u8) -> Box<dyn Deck> {
+	match self {
+	    Variant::ShortDeck => Box::new(StandardDeck::short()),
+	    _ => Box::new(StandardDeck::with_jokers(num_jokers)),
+	}
+    }
+
+    /// wild cards can only make a better high hand, so they can't be played in a lowball
+    /// or hi-lo game, nor in short-deck, which has its own hand rankings
+    pub fn allows_wild_cards(&self) -> bool {
+	!self.is_lowball() && !self.is_hi_lo() && self.hand_ranking_rules() == HandRankingRules::Standard
+    }
+
+    /// the rules for ranking hands, e.g. in short-deck a flush beats a full house
+    pub fn hand_ranking_rules(&self) -> HandRankingRules {
+	match self {
+	    Variant::ShortDeck => HandRankingRules::ShortDeck,
+	    _ => HandRankingRules::Standard,
+	}
+    }
+}
@@ -0,0 +1,42 @@
+use super::card::Rank;
+
+/// the most jokers that can be added to a deck (one black, one red)
+pub const MAX_JOKERS: u8 = 2;
+
+/// A home game can play with wild cards, which stand for whichever cards make the best hand.
+/// Jokers can be added to the deck, and they are always wild.
+/// Every card of a wild rank is wild too, e.g. all four Twos when playing deuces wild
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct WildCards {
+    pub num_jokers: u8, // how many jokers are added to the deck
+    pub ranks: Vec<Rank>, // every card of these ranks is wild
+}
+
+impl WildCards {
+    /// the wild ranks are given with the same characters that we display them with,
+    /// e.g. "2" for deuces wild, or "2K" for deuces and kings wild
+    pub fn new(num_jokers: u8, wild_ranks: &str) -> Result<Self, String> {
+	if num_jokers > MAX_JOKERS {
+	    return Err(format!("A deck can have at most {} jokers.", MAX_JOKERS));
+	}
+	let mut ranks = vec![];
+	for c in wild_ranks.chars().filter(|c| !c.is_whitespace() && *c != ',') {
+	    match Rank::from_char(c) {
+		Some(Rank::Joker) | None => return Err(format!("{} is not a rank that can be wild.", c)),
+		Some(rank) if !ranks.contains(&rank) => ranks.push(rank),
+		Some(_) => (), // already wild
+	    }
+	}
+	Ok(Self { num_jokers, ranks })
+    }
+
+    /// a game without any jokers or wild ranks is played as usual
+    pub fn is_empty(&self) -> bool {
+	self.num_jokers == 0 && self.ranks.is_empty()
+    }
+
+    /// the wild ranks in the same format that they are given in, e.g. "2K"
+    pub fn ranks_string(&self) -> String {
+	self.ranks.iter().map(|rank| rank.to_string()).collect()
+    }
+}
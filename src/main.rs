@@ -15,6 +15,7 @@ use actix_web::{
 use actix_web_actors::ws;
 use uuid::Uuid;
 
+mod director;
 mod hub;
 mod messages;
 mod session;
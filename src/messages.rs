@@ -1,5 +1,9 @@
-use crate::logic::{player::PlayerAction, PlayerConfig};
+use crate::logic::{
+    player::PlayerAction, BettingStructure, BlindLevel, BlindSchedule, DealMethod, PlayerConfig, Reseat,
+    RotationSchedule, Straddle, Variant,
+};
 use actix::prelude::{Message, Recipient};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
@@ -12,12 +16,25 @@ pub enum MetaAction {
     UpdateAddress(Uuid, Recipient<WsMessage>), // update a player with an existing uuid and new message address
     Leave(Uuid),
     SitOut(Uuid),    
+    Straddle(Uuid, bool), // opt in or out of straddling
+    ChooseVariant(Uuid, Variant), // in dealer's choice, the button picks the next game
+    RabbitHunt(Uuid), // see the board cards that would have come, after a hand ended early
+    ShowCards(Uuid), // show your hole cards to the table once the hand is over
     ImBack(Uuid),
     SetPlayerName(Uuid, String),
     SendPlayerName(Uuid),    
     Chat(Uuid, String),
     Admin(Uuid, AdminCommand),
     TableInfo(Recipient<WsMessage>), // send the table info to the given address
+    Reseat(Uuid, String), // move the player to the named table in a multi-table tournament
+    TournamentSeat(PlayerConfig, u32), // seat a tournament player with the given stack
+    TournamentLevel(usize, BlindLevel), // the tournament's blinds went up to this level
+    SendMessage(Uuid, String), // pass a message from the hub along to the player
+    Rebuy(Uuid), // buy back in for the starting stack after going broke in a freezeout
+    AddOn(Uuid), // the one-time add-on at the end of a freezeout's rebuy window
+    ProposeDeal(Uuid, DealMethod, Vec<u32>), // end the game by splitting these payouts (for 1st, 2nd, etc.)
+    RespondToDeal(Uuid, bool), // accept or reject the deal on the table
+    TournamentOver, // the multi-table tournament has a winner, so the table closes
 }
 
 /// these admin commands can be taken by the owner of a PRIVATE game.
@@ -33,6 +50,9 @@ pub enum AdminCommand {
     AddBot,
     RemoveBot,
     Restart,
+    BombPot, // the next hand is a bomb pot
+    BombPotEvery(u32), // every Nth hand is a bomb pot (0 for never)
+    BombPotAmount(u32), // how much everyone puts in for a bomb pot
     // NewAdmin(Uuid), // todo? would they give the name of the player or what?
 }
 
@@ -66,7 +86,9 @@ impl actix::Message for ListTables {
 pub enum JoinTableError {
     GameIsFull,
     InvalidPassword,
-    MissingPassword
+    MissingPassword,
+    TournamentStarted,
+    LateEntryClosed,
 }
 
 impl fmt::Display for JoinTableError {
@@ -81,6 +103,12 @@ impl fmt::Display for JoinTableError {
             JoinTableError::MissingPassword => {
                 write!(f, "Password is required.")
             }
+            JoinTableError::TournamentStarted => {
+                write!(f, "The tournament has already started.")
+            }
+            JoinTableError::LateEntryClosed => {
+                write!(f, "Late entry has closed.")
+            }
         }
     }
 }
@@ -102,6 +130,8 @@ pub enum ReturnedReason {
     Left, // the player left
     HeartBeatFailed,
     FailureToJoin(JoinTableError),
+    Busted, // out of a multi-table tournament
+    TournamentOver, // the multi-table tournament has a winner
 }
 
 /// the game sends this message when a player config has been returned to the hub
@@ -128,6 +158,15 @@ pub enum CreateTableError {
     AlreadyAtTable(String),    // contains the table name
     TooManyBots,
     TooLargeBlinds,
+    TooManyPlayers(u8), // contains the most players the variant allows
+    NoBigBlindToAnte,
+    NoBlindsToStraddle,
+    EmptyRotation,
+    InvalidWildCards(String), // contains what was wrong with them
+    NoWildCards(Variant), // contains the variant that can't be played with wild cards
+    TooFewPlayers,
+    TournamentFreezeout,
+    NoEliminations, // bounties need a game where players are eliminated
 }
 
 impl fmt::Display for CreateTableError {
@@ -151,15 +190,82 @@ impl fmt::Display for CreateTableError {
             CreateTableError::TooLargeBlinds => {
                 write!(f, "Blinds must be smaller than the starting stacks.")
             }
+            CreateTableError::TooManyPlayers(max_players) => {
+                write!(f, "This game can have at most {} players", max_players)
+            }
+            CreateTableError::NoBigBlindToAnte => {
+                write!(f, "Stud games have no big blind to pay a big blind ante.")
+            }
+            CreateTableError::NoBlindsToStraddle => {
+                write!(f, "Stud games have no blinds to straddle.")
+            }
+            CreateTableError::EmptyRotation => {
+                write!(f, "A mixed game needs at least one variant to rotate through.")
+            }
+            CreateTableError::InvalidWildCards(error_msg) => {
+                write!(f, "Invalid wild cards: {}", error_msg)
+            }
+            CreateTableError::NoWildCards(variant) => {
+                write!(f, "{} can't be played with wild cards.", variant)
+            }
+            CreateTableError::TooFewPlayers => {
+                write!(f, "A table needs at least two players.")
+            }
+            CreateTableError::TournamentFreezeout => {
+                write!(f, "A sit-and-go can't also have rebuys or late entry.")
+            }
+            CreateTableError::NoEliminations => {
+                write!(f, "Bounties need a sit-and-go or a freezeout, where players are eliminated.")
+            }
         }
     }
 }
 
 #[derive(Deserialize, Serialize)]
 pub struct CreateFields {
+    #[serde(default)]
+    pub variant: Variant, // Hold'em if not given
+    pub rotation: Option<Vec<Variant>>, // a mixed game rotates through these variants instead
+    #[serde(default)]
+    pub rotation_schedule: RotationSchedule, // every orbit if not given
+    pub betting_structure: Option<BettingStructure>, // the variant's usual structure if not given
     pub max_players: u8,
     pub small_blind: u32,
     pub big_blind: u32,
+    #[serde(default)]
+    pub ante: u32,
+    #[serde(default)]
+    pub big_blind_ante: bool, // the big blind pays the ante for the whole table
+    pub straddle: Option<Straddle>, // no straddling if not given
+    #[serde(default)]
+    pub max_runouts: u8, // the most times the board can be run once everyone is all-in (up to 3)
+    #[serde(default)]
+    pub rabbit_hunting: bool, // can players see the rest of the board after a hand ends early
+    #[serde(default)]
+    pub jokers: u8, // how many jokers to add to the deck (up to 2), which are always wild
+    #[serde(default)]
+    pub wild_ranks: String, // every card of these ranks is wild, e.g. "2" for deuces wild
+    #[serde(default)]
+    pub seven_deuce_bounty: u32, // whoever wins with 7-2 and shows it collects this from everyone (0 for never)
+    #[serde(default)]
+    pub tournament: bool, // a sit-and-go, where everyone starts with the buy in and plays until one player has it all
+    pub blind_levels: Option<Vec<BlindLevel>>, // a tournament starts at the table's blinds, which double every level, if not given
+    #[serde(default)]
+    pub blind_schedule: BlindSchedule, // every 10 hands if not given
+    #[serde(default)]
+    pub freezeout: bool, // players with no chips left are eliminated
+    #[serde(default)]
+    pub max_rebuys: u32, // how many times a busted player in a freezeout can buy back in
+    #[serde(default)]
+    pub rebuy_hands: u32, // rebuys are allowed through this hand number
+    #[serde(default)]
+    pub add_on: u32, // how many chips the add-on at the end of the rebuy window is for (0 for none)
+    #[serde(default)]
+    pub late_entry_hands: u32, // new players can join a freezeout through this hand number
+    #[serde(default)]
+    pub bounty: u32, // every player's bounty, which goes to whoever busts them in a sit-and-go or freezeout (0 for none)
+    #[serde(default)]
+    pub progressive_bounty: bool, // half of a collected bounty is added to the eliminator's own bounty
     pub buy_in: u32,
     pub num_bots: u8,
     pub password: Option<String>,
@@ -174,6 +280,123 @@ pub struct Create {
     pub create_msg: String,
 }
 
+/// the settings for a multi-table tournament, which are shared by all of its tables
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CreateTournamentFields {
+    #[serde(default)]
+    pub variant: Variant, // Hold'em if not given
+    pub betting_structure: Option<BettingStructure>, // the variant's usual structure if not given
+    pub max_players: u8, // at each table
+    pub small_blind: u32,
+    pub big_blind: u32,
+    #[serde(default)]
+    pub ante: u32,
+    pub blind_levels: Option<Vec<BlindLevel>>, // starts at the given blinds, which double every level, if not given
+    #[serde(default)]
+    pub blind_schedule: BlindSchedule, // every 10 hands if not given
+    pub buy_in: u32, // everyone's starting stack
+}
+
+/// Session wants to create a multi-table tournament, which players register for before it starts
+#[derive(Message)]
+#[rtype(result = "Result<String, CreateTableError>")]
+pub struct CreateTournament {
+    /// Client ID
+    pub id: Uuid,
+    pub create_msg: String,
+}
+
+/// Session wants to register for a multi-table tournament that hasn't started yet
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct RegisterTournament {
+    pub id: Uuid,
+    pub tournament_name: String,
+}
+
+/// the creator of a multi-table tournament starts it once everyone has registered
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct StartTournament {
+    pub id: Uuid,
+    pub tournament_name: String,
+}
+
+/// the hub registers a player (who has a name) with a tournament director
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Register {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// the hub tells a tournament director that a player left the tournament without busting
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Withdraw {
+    pub id: Uuid,
+}
+
+/// a table in a multi-table tournament tells the hub (and so the director) how a hand went
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct TableReport {
+    pub tournament_name: String,
+    pub table_name: String,
+    pub busted: Vec<Uuid>, // everyone who busted this hand, from the smallest stack going into the hand
+    pub hand_played: bool,
+}
+
+/// a table has let go of a player who is moving to another table in a tournament
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Reseated {
+    pub config: PlayerConfig,
+    pub money: u32,
+    pub table_name: String, // where the player is going
+}
+
+/// the director tells the hub to open the tournament's tables and seat everyone at them
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SeatTournament {
+    pub tournament_name: String,
+    pub fields: CreateTournamentFields,
+    pub level: BlindLevel, // the blinds to start at
+    pub seating: BTreeMap<String, Vec<Uuid>>, // table name to the players seated there
+}
+
+/// the director tells the hub which players need to move to balance or break tables
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct ReseatPlayers {
+    pub reseats: Vec<Reseat>,
+}
+
+/// the director tells the hub to put every table of the tournament on the next level
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct TournamentLevelUp {
+    pub table_names: Vec<String>,
+    pub level: usize,
+    pub blinds: BlindLevel,
+}
+
+/// the director has something to say to these players, wherever they are
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct TournamentNotice {
+    pub ids: Vec<Uuid>,
+    pub message: String,
+}
+
+/// the director tells the hub that a tournament has a winner
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct TournamentOver {
+    pub tournament_name: String,
+}
+
 #[derive(Message)]
 #[rtype(result = "()")]
 pub struct PlayerActionMessage {
@@ -188,6 +411,7 @@ pub struct PlayerActionMessage {
 #[rtype(result = "()")]
 pub struct GameOver {
     pub table_name: String,
+    pub prizes: HashMap<Uuid, u32>, // what each player won, if the players made a deal to end the game
 }
 
 #[derive(Message)]
@@ -9,7 +9,7 @@ use serde_json::Value;
 use uuid::Uuid;
 
 use crate::hub;
-use crate::logic::{PlayerAction, PLAYER_TIMEOUT};
+use crate::logic::{Card, DealMethod, PlayerAction, PLAYER_TIMEOUT};
 use crate::messages;
 
 /// How often heartbeat pings are sent
@@ -26,7 +26,10 @@ pub fn get_help_message() -> Vec<String> {
 	 "!show_password".to_string(),	 
 	 "!add_bot".to_string(),
 	 "!remove_bot".to_string(),
-	 "!restart".to_string()	 
+	 "!restart".to_string(),
+	 "!bomb_pot".to_string(),
+	 "!bomb_pot_every NUM_HANDS".to_string(),
+	 "!bomb_pot_amount AMOUNT".to_string(),
     ]
 }
 
@@ -231,6 +234,29 @@ impl WsPlayerSession {
                 "create" => {
                     self.handle_create_table(m, ctx);
                 }
+                "create_tournament" => {
+                    self.handle_create_tournament(m, ctx);
+                }
+                "register" => {
+                    if let Some(Value::String(tournament_name)) = object.get("tournament_name") {
+                        self.hub_addr.do_send(messages::RegisterTournament {
+                            id: self.id,
+                            tournament_name: tournament_name.to_string(),
+                        });
+                    } else {
+                        ctx.text("!!! tournament_name is required");
+                    }
+                }
+                "start_tournament" => {
+                    if let Some(Value::String(tournament_name)) = object.get("tournament_name") {
+                        self.hub_addr.do_send(messages::StartTournament {
+                            id: self.id,
+                            tournament_name: tournament_name.to_string(),
+                        });
+                    } else {
+                        ctx.text("!!! tournament_name is required");
+                    }
+                }
                 "admin_command" => {
                     self.handle_admin_command(object, ctx);
                 }
@@ -252,6 +278,51 @@ impl WsPlayerSession {
                         meta_action: messages::MetaAction::SitOut(self.id),
                     });
                 }
+                "straddle" => {
+                    self.handle_straddle(object, ctx);
+                }
+                "choose_variant" => {
+                    self.handle_choose_variant(object, ctx);
+                }
+                "rabbit_hunt" => {
+                    self.hub_addr.do_send(messages::MetaActionMessage {
+                        id: self.id,
+                        meta_action: messages::MetaAction::RabbitHunt(self.id),
+                    });
+                }
+                "rebuy" => {
+                    self.hub_addr.do_send(messages::MetaActionMessage {
+                        id: self.id,
+                        meta_action: messages::MetaAction::Rebuy(self.id),
+                    });
+                }
+                "add_on" => {
+                    self.hub_addr.do_send(messages::MetaActionMessage {
+                        id: self.id,
+                        meta_action: messages::MetaAction::AddOn(self.id),
+                    });
+                }
+                "show_cards" => {
+                    self.hub_addr.do_send(messages::MetaActionMessage {
+                        id: self.id,
+                        meta_action: messages::MetaAction::ShowCards(self.id),
+                    });
+                }
+                "propose_deal" => {
+                    self.handle_propose_deal(object, ctx);
+                }
+                "accept_deal" => {
+                    self.hub_addr.do_send(messages::MetaActionMessage {
+                        id: self.id,
+                        meta_action: messages::MetaAction::RespondToDeal(self.id, true),
+                    });
+                }
+                "reject_deal" => {
+                    self.hub_addr.do_send(messages::MetaActionMessage {
+                        id: self.id,
+                        meta_action: messages::MetaAction::RespondToDeal(self.id, false),
+                    });
+                }
                 "name" => {
                     self.handle_player_name(object, ctx);
                 }
@@ -309,6 +380,39 @@ impl WsPlayerSession {
         // of tables back
     }
     
+    fn handle_create_tournament(&self, msg: &str, ctx: &mut <WsPlayerSession as Actor>::Context) {
+        self.hub_addr
+            .send(messages::CreateTournament {
+                id: self.id,
+                create_msg: msg.into(),
+            })
+            .into_actor(self)
+            .then(|res, _, ctx| {
+                match res {
+                    Ok(Ok(tournament_name)) => {
+                        println!("created tournament = {}", tournament_name);
+                        let message = json::object! {
+                            msg_type: "created_tournament".to_owned(),
+                            tournament_name: tournament_name,
+                        };
+                        ctx.text(message.dump());
+                    }
+                    Ok(Err(e)) => {
+                        println!("{}", e);
+                        let message = json::object! {
+                            msg_type: "error".to_owned(),
+                            error: "unable_to_create".to_owned(),
+                            reason: e.to_string(),
+                        };
+                        ctx.text(message.dump());
+                    }
+                    _ => println!("MailBox error"),
+                }
+                fut::ready(())
+            })
+            .wait(ctx)
+    }
+
     fn handle_list_tables(&self, ctx: &mut <WsPlayerSession as Actor>::Context) {
         // Send ListTables message to the hub and wait for response
         println!("List tables");
@@ -390,6 +494,58 @@ impl WsPlayerSession {
                         ctx.text("!!!You much specify how much to bet!");
                     }
                 }
+                "draw" => {
+                    // the cards to throw away are given together, e.g. "Kh9c", or empty to stand pat
+                    if let Some(Value::String(cards)) = object.get("cards") {
+                        let chars: Vec<char> = cards.chars().collect();
+                        let discards: Result<Vec<Card>, String> = chars
+                            .chunks(2)
+                            .map(|pair| pair.iter().collect::<String>().parse::<Card>())
+                            .collect();
+                        match discards {
+                            Ok(discards) => {
+                                self.hub_addr.do_send(messages::PlayerActionMessage {
+                                    id: self.id,
+                                    player_action: PlayerAction::Draw(discards),
+                                });
+                            }
+                            Err(err) => ctx.text(format!("!!! {}", err)),
+                        }
+                    } else {
+                        ctx.text("!!!You must specify which cards to draw (or an empty string to stand pat)!");
+                    }
+                }
+                "discard" => {
+                    // in Pineapple, the one hole card to throw away, e.g. "Kh"
+                    if let Some(Value::String(card)) = object.get("card") {
+                        match card.parse::<Card>() {
+                            Ok(card) => {
+                                self.hub_addr.do_send(messages::PlayerActionMessage {
+                                    id: self.id,
+                                    player_action: PlayerAction::Discard(card),
+                                });
+                            }
+                            Err(err) => ctx.text(format!("!!! {}", err)),
+                        }
+                    } else {
+                        ctx.text("!!!You must specify which card to throw away!");
+                    }
+                }
+                "run_it" => {
+                    // how many times to run the board, once everyone is all-in
+                    if let Some(Value::String(times)) = object.get("times") {
+                        if let Ok(times) = times.parse::<u8>() {
+                            self.hub_addr.do_send(messages::PlayerActionMessage {
+                                id: self.id,
+                                player_action: PlayerAction::RunIt(times),
+                            });
+                        } else {
+                            ctx.text("!!!The number of times must be a number!");
+                        }
+                    } else {
+                        ctx.text("!!!You must specify how many times to run it!");
+                    }
+                }
                 other => {
                     ctx.text(format!(
                         "invalid action set for type:player_action: {:?}",
@@ -427,6 +583,55 @@ impl WsPlayerSession {
         }
     }
 
+    // e.g. {"msg_type": "straddle", "straddle": true}
+    fn handle_straddle(&self, object: Value, ctx: &mut <WsPlayerSession as Actor>::Context) {
+        if let Some(Value::Bool(straddle)) = object.get("straddle") {
+            self.hub_addr.do_send(messages::MetaActionMessage {
+                id: self.id,
+                meta_action: messages::MetaAction::Straddle(self.id, *straddle),
+            })
+        } else {
+            println!("missing straddle!");
+            ctx.text("!!! straddle (true or false) is required");
+        }
+    }
+
+    // e.g. {"msg_type": "choose_variant", "variant": "razz"}
+    fn handle_choose_variant(&self, object: Value, ctx: &mut <WsPlayerSession as Actor>::Context) {
+        if let Some(Ok(variant)) = object.get("variant").map(|v| serde_json::from_value(v.clone())) {
+            self.hub_addr.do_send(messages::MetaActionMessage {
+                id: self.id,
+                meta_action: messages::MetaAction::ChooseVariant(self.id, variant),
+            })
+        } else {
+            println!("missing or unknown variant!");
+            ctx.text("!!! a valid variant is required");
+        }
+    }
+
+    // e.g. {"msg_type": "propose_deal", "payouts": [500, 300, 200], "method": "chip_chop"}
+    // the method is optional, and defaults to ICM. The payouts are only given at a cash game,
+    // since a freezeout or sit-and-go pays out of its own prize pool.
+    // Everyone with chips is in the deal (and busted players are not), so it can't be made while a bot has chips
+    fn handle_propose_deal(&self, object: Value, ctx: &mut <WsPlayerSession as Actor>::Context) {
+        let payouts = match object.get("payouts").map(|v| serde_json::from_value::<Vec<u32>>(v.clone())) {
+            None => Ok(vec![]),
+            Some(payouts) => payouts,
+        };
+        let method = match object.get("method").map(|v| serde_json::from_value(v.clone())) {
+            None => Ok(DealMethod::Icm),
+            Some(method) => method,
+        };
+        if let (Ok(payouts), Ok(method)) = (payouts, method) {
+            self.hub_addr.do_send(messages::MetaActionMessage {
+                id: self.id,
+                meta_action: messages::MetaAction::ProposeDeal(self.id, method, payouts),
+            })
+        } else {
+            ctx.text("!!! the payouts must be a list of amounts, and the method must be icm or chip_chop");
+        }
+    }
+
     // e.g. {"msg_type": "admin_command", "admin_command": "big_blind", "big_blind": 24}
     fn handle_admin_command(&self, object: Value, ctx: &mut <WsPlayerSession as Actor>::Context) {
         if let Some(Value::String(admin_command)) = object.get("admin_command") {
@@ -540,6 +745,53 @@ impl WsPlayerSession {
                     });
 		    false
                 }
+                "bomb_pot" => {
+		    self.hub_addr.do_send(messages::MetaActionMessage {
+			id: self.id,
+			meta_action: messages::MetaAction::Admin(
+			    self.id,
+			    messages::AdminCommand::BombPot),
+                    });
+		    false
+                }
+                "bomb_pot_every" => {
+		    if let Some(Value::String(num_hands)) = object.get("bomb_pot_every") {
+			if let Ok(num_hands) = num_hands.to_string().parse::<u32>() {
+			    self.hub_addr.do_send(messages::MetaActionMessage {
+				id: self.id,
+				meta_action: messages::MetaAction::Admin(
+				    self.id,
+				    messages::AdminCommand::BombPotEvery(num_hands),
+				)
+			    });
+			    false
+			} else {
+			    true
+			}
+		    } else {
+			// invalid json
+			true
+		    }
+                }
+                "bomb_pot_amount" => {
+		    if let Some(Value::String(amount)) = object.get("bomb_pot_amount") {
+			if let Ok(amount) = amount.to_string().parse::<u32>() {
+			    self.hub_addr.do_send(messages::MetaActionMessage {
+				id: self.id,
+				meta_action: messages::MetaAction::Admin(
+				    self.id,
+				    messages::AdminCommand::BombPotAmount(amount),
+				)
+			    });
+			    false
+			} else {
+			    true
+			}
+		    } else {
+			// invalid json
+			true
+		    }
+                }
                 _ => {
 		    // invalid command
 		    true 